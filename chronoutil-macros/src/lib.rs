@@ -0,0 +1,329 @@
+#![warn(missing_docs)]
+//! # Compile-time validated literals for [chronoutil](https://docs.rs/chronoutil)
+//!
+//! This crate provides the [`rd!`] and [`date!`] macros, which check relative durations and
+//! dates while compiling, and expand to values which can be used in `const` items:
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use chronoutil::RelativeDuration;
+//! use chronoutil_macros::{date, rd};
+//!
+//! const BILLING_PERIOD: RelativeDuration = rd!("P1M");
+//! const GRACE_PERIOD: RelativeDuration = rd!(1 week + 2 days);
+//! const LAUNCH: NaiveDate = date!(2024-02-29);
+//!
+//! assert_eq!(LAUNCH + BILLING_PERIOD, NaiveDate::from_ymd_opt(2024, 3, 29).unwrap());
+//! assert_eq!(GRACE_PERIOD, RelativeDuration::days(9));
+//! ```
+//!
+//! Invalid values are reported as compile errors pointing at the offending part of the input:
+//!
+//! ```rust,compile_fail
+//! # use chronoutil_macros::rd;
+//! let typo = rd!("P1Y2X");
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use chronoutil_macros::rd;
+//! let typo = rd!(1 mnth);
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use chronoutil_macros::date;
+//! let not_a_leap_year = date!(2023-02-29);
+//! ```
+extern crate proc_macro;
+
+use std::convert::TryFrom;
+
+use chrono::{Duration, NaiveDate};
+use chronoutil::RelativeDuration;
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+type Error = (Span, String);
+
+// Builds `compile_error!("message")` with every token at the given span
+fn compile_error((span, message): Error) -> TokenStream {
+    let mut message = TokenTree::Literal(Literal::string(&message));
+    message.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, message.into());
+    group.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ]
+    .into_iter()
+    .collect()
+}
+
+// Flattens invisible groups, as produced when the input is forwarded from a `macro_rules!` macro
+fn tokens(input: TokenStream) -> Vec<TokenTree> {
+    input
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                tokens(group.stream())
+            }
+            token => vec![token],
+        })
+        .collect()
+}
+
+fn string_literal(literal: &Literal) -> Option<String> {
+    let text = literal.to_string();
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    if inner.contains('\\') {
+        None
+    } else {
+        Some(inner.to_string())
+    }
+}
+
+fn integer<T: std::str::FromStr>(token: Option<&TokenTree>, end: Span) -> Result<T, Error> {
+    match token {
+        Some(TokenTree::Literal(literal)) => {
+            literal.to_string().replace('_', "").parse().map_err(|_| {
+                (
+                    literal.span(),
+                    format!("expected an integer, found {}", literal),
+                )
+            })
+        }
+        Some(token) => Err((
+            token.span(),
+            format!("expected an integer, found {}", token),
+        )),
+        None => Err((end, "expected an integer".to_string())),
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == c)
+}
+
+fn unit_duration(count: i64, unit: &Ident) -> Result<RelativeDuration, Error> {
+    let overflow = || {
+        (
+            unit.span(),
+            format!("{} {} overflows a RelativeDuration", count, unit),
+        )
+    };
+    let months = |factor: i64| {
+        count
+            .checked_mul(factor)
+            .and_then(|months| i32::try_from(months).ok())
+            .map(RelativeDuration::months)
+            .ok_or_else(overflow)
+    };
+    let duration =
+        |duration: Option<Duration>| duration.map(RelativeDuration::from).ok_or_else(overflow);
+
+    match unit.to_string().as_str() {
+        "year" | "years" => months(12),
+        "month" | "months" => months(1),
+        "week" | "weeks" => duration(Duration::try_weeks(count)),
+        "day" | "days" => duration(Duration::try_days(count)),
+        "hour" | "hours" => duration(Duration::try_hours(count)),
+        "minute" | "minutes" => duration(Duration::try_minutes(count)),
+        "second" | "seconds" => duration(Duration::try_seconds(count)),
+        "millisecond" | "milliseconds" => duration(Duration::try_milliseconds(count)),
+        "microsecond" | "microseconds" => duration(Some(Duration::microseconds(count))),
+        "nanosecond" | "nanoseconds" => duration(Some(Duration::nanoseconds(count))),
+        other => Err((
+            unit.span(),
+            format!(
+                "unknown unit {}, expected one of years, months, weeks, days, hours, minutes, \
+                 seconds, milliseconds, microseconds or nanoseconds",
+                other
+            ),
+        )),
+    }
+}
+
+// Parses a sum of amounts such as `1 month + 2 days - 3 hours`
+fn parse_units(tokens: &[TokenTree]) -> Result<RelativeDuration, Error> {
+    let end = tokens
+        .last()
+        .map_or_else(Span::call_site, |token| token.span());
+    let mut total = RelativeDuration::zero();
+    let mut index = 0;
+    let mut negative = false;
+
+    loop {
+        if is_punct(tokens.get(index), '-') {
+            negative = !negative;
+            index += 1;
+        }
+        let count: i64 = integer(tokens.get(index), end)?;
+        let count = if negative { -count } else { count };
+        let unit = match tokens.get(index + 1) {
+            Some(TokenTree::Ident(unit)) => unit,
+            Some(token) => return Err((token.span(), format!("expected a unit, found {}", token))),
+            None => return Err((end, "expected a unit".to_string())),
+        };
+        let amount = unit_duration(count, unit)?;
+        total = total
+            .num_months()
+            .checked_add(amount.num_months())
+            .and_then(|months| {
+                total
+                    .duration()
+                    .checked_add(&amount.duration())
+                    .map(|duration| RelativeDuration::months(months).with_duration(duration))
+            })
+            .ok_or_else(|| (unit.span(), "overflows a RelativeDuration".to_string()))?;
+        index += 2;
+
+        match tokens.get(index) {
+            None => return Ok(total),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '+' => negative = false,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '-' => negative = true,
+            Some(token) => return Err((token.span(), format!("expected + or -, found {}", token))),
+        }
+        index += 1;
+    }
+}
+
+fn parse_rd(input: TokenStream) -> Result<RelativeDuration, Error> {
+    let tokens = tokens(input);
+    match tokens.as_slice() {
+        [] => Err((
+            Span::call_site(),
+            "expected an ISO 8601 duration string or a sum of amounts such as 1 month + 2 days"
+                .to_string(),
+        )),
+        [TokenTree::Literal(literal)] if literal.to_string().starts_with('"') => {
+            let text = string_literal(literal).ok_or_else(|| {
+                (
+                    literal.span(),
+                    "expected a plain string without escapes".to_string(),
+                )
+            })?;
+            RelativeDuration::parse_from_iso8601(&text).map_err(|error| (literal.span(), error))
+        }
+        tokens => parse_units(tokens),
+    }
+}
+
+fn parse_date(input: TokenStream) -> Result<(i32, u32, u32), Error> {
+    let tokens = tokens(input);
+    let end = tokens
+        .last()
+        .map_or_else(Span::call_site, |token| token.span());
+    let (negative, tokens) = if is_punct(tokens.first(), '-') {
+        (true, &tokens[1..])
+    } else {
+        (false, &tokens[..])
+    };
+
+    let year: i32 = integer(tokens.first(), end)?;
+    let year = if negative { -year } else { year };
+    for index in [1, 3].iter() {
+        if !is_punct(tokens.get(*index), '-') {
+            let span = tokens.get(*index).map_or(end, |token| token.span());
+            return Err((span, "expected a date of the form 2024-02-29".to_string()));
+        }
+    }
+    let month: u32 = integer(tokens.get(2), end)?;
+    let day: u32 = integer(tokens.get(4), end)?;
+    if let Some(token) = tokens.get(5) {
+        return Err((token.span(), format!("unexpected {} after date", token)));
+    }
+
+    if !(1..=12).contains(&month) {
+        return Err((tokens[2].span(), format!("{} is not a valid month", month)));
+    }
+    if NaiveDate::from_ymd_opt(year, month, day).is_none() {
+        return Err((
+            tokens[4].span(),
+            format!("{}-{:02}-{:02} is not a valid date", year, month, day),
+        ));
+    }
+    Ok((year, month, day))
+}
+
+/// Creates a [`RelativeDuration`] which is validated at compile time.
+///
+/// The input is either an ISO 8601 duration string, using the same grammar as
+/// [`RelativeDuration::parse_from_iso8601`], or a sum of integer amounts of years, months,
+/// weeks, days, hours, minutes, seconds, milliseconds, microseconds or nanoseconds:
+///
+/// ```rust
+/// use chronoutil::RelativeDuration;
+/// use chronoutil_macros::rd;
+///
+/// assert_eq!(rd!("P1Y2M"), RelativeDuration::months(14));
+/// assert_eq!(
+///     rd!(1 month + 2 days),
+///     RelativeDuration::months(1) + RelativeDuration::days(2)
+/// );
+/// assert_eq!(rd!(-1 hour + 30 minutes), RelativeDuration::minutes(-30));
+/// ```
+///
+/// The result is a constant expression.
+#[proc_macro]
+pub fn rd(input: TokenStream) -> TokenStream {
+    let duration = match parse_rd(input) {
+        Ok(duration) => duration,
+        Err(error) => return compile_error(error),
+    };
+
+    // Split into whole seconds, rounded down, and non-negative nanoseconds
+    let (seconds, nanos) = match duration.duration().subsec_nanos() {
+        nanos if nanos < 0 => (duration.duration().num_seconds() - 1, nanos + 1_000_000_000),
+        nanos => (duration.duration().num_seconds(), nanos),
+    };
+    format!(
+        "{{
+            const DURATION: ::chronoutil::RelativeDuration =
+                ::chronoutil::RelativeDuration::months({}i32).with_duration(
+                    match ::chronoutil::__private::Duration::new({}i64, {}u32) {{
+                        ::core::option::Option::Some(duration) => duration,
+                        ::core::option::Option::None => ::core::panic!(\"invalid duration\"),
+                    }},
+                );
+            DURATION
+        }}",
+        duration.num_months(),
+        seconds,
+        nanos
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Creates a `chrono::NaiveDate` which is validated at compile time.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use chronoutil_macros::date;
+///
+/// assert_eq!(date!(2024-02-29), NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+/// ```
+///
+/// The result is a constant expression.
+#[proc_macro]
+pub fn date(input: TokenStream) -> TokenStream {
+    let (year, month, day) = match parse_date(input) {
+        Ok(date) => date,
+        Err(error) => return compile_error(error),
+    };
+
+    format!(
+        "{{
+            const DATE: ::chronoutil::__private::NaiveDate =
+                match ::chronoutil::__private::NaiveDate::from_ymd_opt({}i32, {}u32, {}u32) {{
+                    ::core::option::Option::Some(date) => date,
+                    ::core::option::Option::None => ::core::panic!(\"invalid date\"),
+                }};
+            DATE
+        }}",
+        year, month, day
+    )
+    .parse()
+    .unwrap()
+}
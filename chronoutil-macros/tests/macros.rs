@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+use chronoutil::RelativeDuration;
+use chronoutil_macros::{date, rd};
+
+const MONTHLY: RelativeDuration = rd!("P1M");
+const LEAP_DAY: NaiveDate = date!(2024 - 02 - 29);
+
+macro_rules! forwarded {
+    ($duration:expr) => {
+        rd!($duration)
+    };
+}
+
+#[test]
+fn test_rd_iso8601() {
+    [
+        (rd!("P1Y2M"), RelativeDuration::months(14)),
+        (rd!("P0D"), RelativeDuration::zero()),
+        (
+            rd!("P1Y2M3DT4H5M6.7S"),
+            RelativeDuration::months(14)
+                + RelativeDuration::days(3)
+                + RelativeDuration::hours(4)
+                + RelativeDuration::minutes(5)
+                + RelativeDuration::seconds(6)
+                + RelativeDuration::milliseconds(700),
+        ),
+        (
+            rd!("P-1MT-0.5S"),
+            RelativeDuration::months(-1) - RelativeDuration::milliseconds(500),
+        ),
+        (MONTHLY, RelativeDuration::months(1)),
+        (forwarded!("P2W"), RelativeDuration::weeks(2)),
+    ]
+    .iter()
+    .for_each(|(actual, expected)| assert_eq!(actual, expected));
+}
+
+#[test]
+fn test_rd_units() {
+    [
+        (
+            rd!(1 month + 2 days),
+            RelativeDuration::months(1) + RelativeDuration::days(2),
+        ),
+        (rd!(1 year), RelativeDuration::years(1)),
+        (rd!(-2 weeks), RelativeDuration::weeks(-2)),
+        (rd!(1 hour - 30 minutes), RelativeDuration::minutes(30)),
+        (rd!(-1 hour + 30 minutes), RelativeDuration::minutes(-30)),
+        (rd!(1 day - -1 day), RelativeDuration::days(2)),
+        (
+            rd!(1 second + 1 millisecond + 1 microsecond + 1 nanosecond),
+            RelativeDuration::nanoseconds(1_001_001_001),
+        ),
+        (rd!(1_000 days), RelativeDuration::days(1000)),
+    ]
+    .iter()
+    .for_each(|(actual, expected)| assert_eq!(actual, expected));
+}
+
+#[test]
+fn test_date() {
+    assert_eq!(LEAP_DAY, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    assert_eq!(
+        date!(1999 - 12 - 31),
+        NaiveDate::from_ymd_opt(1999, 12, 31).unwrap()
+    );
+    assert_eq!(
+        date!(-44 - 03 - 15),
+        NaiveDate::from_ymd_opt(-44, 3, 15).unwrap()
+    );
+    assert_eq!(LEAP_DAY + MONTHLY, date!(2024 - 03 - 29));
+}
@@ -0,0 +1,353 @@
+//! Implements `AnchoredDate` - a date which remembers its day of the month across shifts.
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use super::calendar::naive_date;
+use super::delta::{
+    resolve_day, shift_months_opt, with_date, with_day, with_day_opt, DayOutOfRange,
+};
+use super::relative_duration::RelativeDuration;
+
+/// A date which remembers the day of the month it is anchored to.
+///
+/// Shifted dates usually have no memory of the date they were shifted from, so one month
+/// after Jan 31st is Feb 29th, and a further month gives Mar 29th. An `AnchoredDate` instead
+/// moves back onto its anchor day whenever it is shifted by months or years:
+///
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::{AnchoredDate, RelativeDuration};
+/// let mut date = AnchoredDate::new(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap());
+/// date += RelativeDuration::months(1);
+/// assert_eq!(*date.date(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+/// date += RelativeDuration::months(1);
+/// assert_eq!(*date.date(), NaiveDate::from_ymd_opt(2020, 3, 31).unwrap());
+/// ```
+///
+/// The anchor uses the same day indices as [`with_day`](crate::with_day), so -1 anchors to
+/// the last day of the month. Shifts made up of days or smaller units are applied as usual,
+/// and do not change the anchor.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AnchoredDate<D> {
+    date: D,
+    anchor: i32,
+}
+
+impl<D: Datelike> AnchoredDate<D> {
+    /// Anchors the date to its own day of the month.
+    #[inline]
+    pub fn new(date: D) -> Self {
+        let anchor = date.day() as i32;
+        Self { date, anchor }
+    }
+
+    /// Moves the date to the last day of its month, and anchors it there.
+    ///
+    /// Panics if the last day of the month is an ambiguous or non-existing date/time.
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::{AnchoredDate, RelativeDuration};
+    /// let date = AnchoredDate::end_of_month(NaiveDate::from_ymd_opt(2021, 2, 10).unwrap());
+    /// assert_eq!(*date.date(), NaiveDate::from_ymd_opt(2021, 2, 28).unwrap());
+    /// assert_eq!(
+    ///     (date + RelativeDuration::months(1)).into_inner(),
+    ///     NaiveDate::from_ymd_opt(2021, 3, 31).unwrap()
+    /// );
+    /// ```
+    pub fn end_of_month(date: D) -> Self {
+        Self::end_of_month_opt(date).unwrap()
+    }
+
+    /// Same as [`AnchoredDate::end_of_month`] except fallible on unresolvable dates/times.
+    ///
+    /// Returns `None` rather than panicking when the last day of the month is an ambiguous or
+    /// non-existing date/time (e.g. in a DST transition).
+    pub fn end_of_month_opt(date: D) -> Option<Self> {
+        Some(Self {
+            date: with_day_opt(date, -1)?,
+            anchor: -1,
+        })
+    }
+
+    /// Moves the date to the given day of its month, and anchors it there.
+    ///
+    /// Returns Err if the day is not in the range 1-31 or -31 to -1.
+    pub fn with_anchor(self, day: i32) -> Result<Self, DayOutOfRange> {
+        Ok(Self {
+            date: with_day(self.date, day)?,
+            anchor: day,
+        })
+    }
+
+    /// The date.
+    #[inline]
+    pub fn date(&self) -> &D {
+        &self.date
+    }
+
+    /// The day of the month the date is anchored to, where negative days count back from
+    /// the end of the month.
+    #[inline]
+    pub fn anchor(&self) -> i32 {
+        self.anchor
+    }
+
+    /// Returns the date, forgetting its anchor.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.date
+    }
+}
+
+impl<D> AnchoredDate<D>
+where
+    D: Datelike + Add<Duration, Output = D>,
+{
+    /// Adds a relative duration, moving back onto the anchor day after any months are
+    /// shifted.
+    ///
+    /// The days and smaller units are then added as with `D + Duration`. Returns `None` if the
+    /// shifted date is an ambiguous or non-existing date/time (e.g. in a DST transition), or if
+    /// the result is out of range or within two days of the limits of chrono's dates.
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::{AnchoredDate, RelativeDuration};
+    /// let date = AnchoredDate::new(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap());
+    /// assert_eq!(
+    ///     date.checked_add(RelativeDuration::months(3)).map(AnchoredDate::into_inner),
+    ///     NaiveDate::from_ymd_opt(2020, 4, 30)
+    /// );
+    /// assert_eq!(date.checked_add(RelativeDuration::years(i32::MAX / 12)), None);
+    /// ```
+    pub fn checked_add(self, rhs: RelativeDuration) -> Option<Self> {
+        let date = if rhs.months == 0 {
+            self.date
+        } else {
+            let first = NaiveDate::from_ymd_opt(self.date.year(), self.date.month(), 1)?;
+            let first = shift_months_opt(first, rhs.months)?;
+            // The anchor was validated on construction
+            let day = resolve_day(first.year(), first.month(), self.anchor).ok()?;
+            with_date(self.date, first.with_day(day)?)?
+        };
+        // Adding a Duration panics on overflow, so check the day it lands on is in range with
+        // a day to spare for the time of day, and another for any UTC offset
+        let days = rhs.duration.num_days();
+        let day = naive_date(&date)?;
+        for margin in &[-2, 2] {
+            day.checked_add_signed(Duration::try_days(days.checked_add(*margin)?)?)?;
+        }
+        Some(AnchoredDate {
+            date: date + rhs.duration,
+            anchor: self.anchor,
+        })
+    }
+
+    /// Subtracts a relative duration, moving back onto the anchor day after any months are
+    /// shifted.
+    ///
+    /// Returns `None` under the same conditions as [`AnchoredDate::checked_add`].
+    #[inline]
+    pub fn checked_sub(self, rhs: RelativeDuration) -> Option<Self> {
+        self.checked_add(-rhs)
+    }
+}
+
+impl<D: Datelike> From<D> for AnchoredDate<D> {
+    /// Anchors the date to its own day of the month.
+    #[inline]
+    fn from(date: D) -> Self {
+        Self::new(date)
+    }
+}
+
+impl<D> AsRef<D> for AnchoredDate<D> {
+    #[inline]
+    fn as_ref(&self) -> &D {
+        &self.date
+    }
+}
+
+impl<D> Add<RelativeDuration> for AnchoredDate<D>
+where
+    D: Datelike + Add<Duration, Output = D>,
+{
+    type Output = AnchoredDate<D>;
+
+    #[inline]
+    fn add(self, rhs: RelativeDuration) -> AnchoredDate<D> {
+        self.checked_add(rhs).unwrap()
+    }
+}
+
+impl<D> Sub<RelativeDuration> for AnchoredDate<D>
+where
+    D: Datelike + Add<Duration, Output = D>,
+{
+    type Output = AnchoredDate<D>;
+
+    #[inline]
+    fn sub(self, rhs: RelativeDuration) -> AnchoredDate<D> {
+        self + -rhs
+    }
+}
+
+impl<D> AddAssign<RelativeDuration> for AnchoredDate<D>
+where
+    D: Datelike + Copy + Add<Duration, Output = D>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: RelativeDuration) {
+        *self = *self + rhs;
+    }
+}
+
+impl<D> SubAssign<RelativeDuration> for AnchoredDate<D>
+where
+    D: Datelike + Copy + Add<Duration, Output = D>,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: RelativeDuration) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone};
+
+    use super::*;
+    use crate::DateRule;
+
+    #[test]
+    fn test_repeated_shifts() {
+        [
+            (31, vec![31, 29, 31, 30, 31, 30, 31, 31]),
+            (30, vec![30, 29, 30, 30, 30, 30, 30, 30]),
+            (29, vec![29, 29, 29, 29, 29, 29, 29, 29]),
+            (15, vec![15, 15, 15, 15, 15, 15, 15, 15]),
+        ]
+        .iter()
+        .for_each(|(day, expected)| {
+            let start = NaiveDate::from_ymd_opt(2020, 1, *day).unwrap();
+            let mut date = AnchoredDate::new(start);
+            let mut days = vec![];
+            for _ in 0..8 {
+                days.push(date.date().day());
+                date += RelativeDuration::months(1);
+            }
+            assert_eq!(&days, expected);
+
+            // Agrees with a DateRule from the same start
+            let mut date = AnchoredDate::new(start);
+            DateRule::monthly(start).take(24).for_each(|expected| {
+                assert_eq!(date.into_inner(), expected);
+                date += RelativeDuration::months(1);
+            });
+        });
+    }
+
+    #[test]
+    fn test_end_of_month() {
+        let mut date = AnchoredDate::end_of_month(NaiveDate::from_ymd_opt(2021, 2, 1).unwrap());
+        let days: Vec<u32> = (0..6)
+            .map(|_| {
+                let day = date.date().day();
+                date += RelativeDuration::months(1);
+                day
+            })
+            .collect();
+        assert_eq!(days, vec![28, 31, 30, 31, 30, 31]);
+
+        let date = AnchoredDate::new(NaiveDate::from_ymd_opt(2021, 4, 5).unwrap())
+            .with_anchor(-2)
+            .unwrap();
+        assert_eq!(date.date().day(), 29);
+        assert_eq!((date - RelativeDuration::months(2)).date().day(), 27);
+        assert_eq!(
+            AnchoredDate::new(NaiveDate::from_ymd_opt(2021, 4, 5).unwrap())
+                .with_anchor(0)
+                .unwrap_err()
+                .day(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_durations_keep_anchor() {
+        let start = AnchoredDate::from(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap());
+
+        // Days are added after moving onto the anchor, and do not move it
+        let date = start + RelativeDuration::months(1) + RelativeDuration::days(1);
+        assert_eq!(*date.date(), NaiveDate::from_ymd_opt(2020, 3, 1).unwrap());
+        assert_eq!(date.anchor(), 31);
+        let date = date + RelativeDuration::months(1);
+        assert_eq!(*date.date(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap());
+
+        let date = start - RelativeDuration::years(1) + RelativeDuration::weeks(1);
+        assert_eq!(*date.date(), NaiveDate::from_ymd_opt(2019, 2, 7).unwrap());
+    }
+
+    #[test]
+    fn test_datetimes() {
+        let tz = &chrono_tz::Europe::London;
+        let start = tz.with_ymd_and_hms(2024, 1, 31, 9, 30, 0).unwrap();
+        let mut date = AnchoredDate::new(start);
+        date += RelativeDuration::months(1);
+        assert_eq!(
+            date.into_inner(),
+            tz.with_ymd_and_hms(2024, 2, 29, 9, 30, 0).unwrap()
+        );
+        date += RelativeDuration::months(1);
+        assert_eq!(
+            *date.as_ref(),
+            tz.with_ymd_and_hms(2024, 3, 31, 9, 30, 0).unwrap()
+        );
+
+        // Clocks go forward from 01:00 to 02:00 on 31st March 2024 in London
+        let start = AnchoredDate::new(tz.with_ymd_and_hms(2024, 1, 31, 1, 30, 0).unwrap());
+        assert_eq!(start.checked_add(RelativeDuration::months(2)), None);
+        assert_eq!(
+            start
+                .checked_add(RelativeDuration::months(1))
+                .map(AnchoredDate::into_inner),
+            tz.with_ymd_and_hms(2024, 2, 29, 1, 30, 0).single()
+        );
+        assert_eq!(
+            start
+                .checked_sub(RelativeDuration::months(2))
+                .map(AnchoredDate::into_inner),
+            tz.with_ymd_and_hms(2023, 11, 30, 1, 30, 0).single()
+        );
+        let march = tz.with_ymd_and_hms(2024, 3, 10, 1, 30, 0).unwrap();
+        assert_eq!(AnchoredDate::end_of_month_opt(march), None);
+    }
+
+    #[test]
+    fn test_checked_add_out_of_range() {
+        let last = AnchoredDate::new(NaiveDate::MAX);
+        assert_eq!(last.checked_add(RelativeDuration::days(1)), None);
+        assert_eq!(last.checked_add(RelativeDuration::months(1)), None);
+        let first = AnchoredDate::new(NaiveDate::MIN.and_hms_opt(12, 0, 0).unwrap());
+        // Results within two days of chrono's limits are treated as out of range
+        assert_eq!(first.checked_sub(RelativeDuration::seconds(1)), None);
+        assert_eq!(
+            first
+                .checked_add(RelativeDuration::weeks(1) + RelativeDuration::hours(1))
+                .map(AnchoredDate::into_inner),
+            NaiveDate::MIN
+                .and_hms_opt(13, 0, 0)
+                .map(|date| date + Duration::weeks(1))
+        );
+
+        let tz = &chrono_tz::Europe::London;
+        let date = AnchoredDate::new(tz.with_ymd_and_hms(2024, 1, 31, 9, 30, 0).unwrap());
+        assert_eq!(
+            date.checked_add(RelativeDuration::days(i64::MAX / 86_400_000)),
+            None
+        );
+        assert_eq!(date.checked_sub(RelativeDuration::years(300_000)), None);
+    }
+}
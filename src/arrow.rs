@@ -0,0 +1,417 @@
+//! Conversions between `RelativeDuration` and Apache Arrow interval types, along with
+//! kernels shifting Arrow date and timestamp arrays by intervals.
+//!
+//! Arrow's `IntervalMonthDayNano` keeps days separate from nanoseconds, whereas a
+//! `RelativeDuration` only distinguishes months from an absolute `Duration`. Days are read as
+//! exactly 24 hours, and every whole multiple of 24 hours is written back into the days field.
+//!
+//! The kernels apply the same rules as adding a `RelativeDuration` to a chrono value: the months
+//! are shifted first (see [`shift_months`](crate::shift_months)), then the remaining duration is
+//! added. Timestamps without a timezone are shifted as naive date-times. Timestamps with a
+//! timezone are shifted in local time, so month-ends are clamped on the local date, and the
+//! timezone is carried over to the output. Only fixed offsets such as `+05:30` are understood
+//! unless arrow-array's `chrono-tz` feature is enabled for named zones.
+use std::convert::{TryFrom, TryInto};
+
+use arrow_array::temporal_conversions::{as_datetime, as_datetime_with_timezone};
+use arrow_array::timezone::Tz;
+use arrow_array::types::{ArrowTimestampType, Date32Type, IntervalMonthDayNano};
+use arrow_array::{
+    Array, Date32Array, IntervalMonthDayNanoArray, IntervalYearMonthArray, PrimitiveArray,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime};
+
+use super::delta::shift_months_opt;
+use super::relative_duration::RelativeDuration;
+
+const DAYS_TO_UNIX_EPOCH: i32 = 719_163;
+
+impl From<IntervalMonthDayNano> for RelativeDuration {
+    /// Makes a new `RelativeDuration` from an Arrow `IntervalMonthDayNano`, treating each day
+    /// as 24 hours.
+    #[inline]
+    fn from(item: IntervalMonthDayNano) -> Self {
+        RelativeDuration::months(item.months).with_duration(
+            Duration::days(item.days.into()) + Duration::nanoseconds(item.nanoseconds),
+        )
+    }
+}
+
+impl TryFrom<RelativeDuration> for IntervalMonthDayNano {
+    type Error = String;
+
+    /// Converts a `RelativeDuration` into an Arrow `IntervalMonthDayNano`.
+    ///
+    /// Returns Err if the number of days does not fit in the days field.
+    fn try_from(item: RelativeDuration) -> Result<Self, Self::Error> {
+        let days = item.duration.num_days();
+        // The remainder is less than a day, so always fits in nanoseconds
+        let nanoseconds = (item.duration - Duration::days(days))
+            .num_nanoseconds()
+            .unwrap();
+
+        Ok(IntervalMonthDayNano {
+            months: item.months,
+            days: days.try_into().map_err(|_| {
+                format!(
+                    "{} overflows the days of an arrow interval",
+                    item.format_to_iso8601()
+                )
+            })?,
+            nanoseconds,
+        })
+    }
+}
+
+/// Makes a new `RelativeDuration` from an Arrow `IntervalYearMonth` value.
+#[inline]
+pub fn from_year_month(months: i32) -> RelativeDuration {
+    RelativeDuration::months(months)
+}
+
+/// Converts a `RelativeDuration` into an Arrow `IntervalYearMonth` value.
+///
+/// Returns Err if the duration has a non-zero `Duration` component.
+pub fn to_year_month(duration: &RelativeDuration) -> Result<i32, String> {
+    if duration.duration.is_zero() {
+        Ok(duration.months)
+    } else {
+        Err(format!(
+            "{} cannot be represented as a year-month interval",
+            duration.format_to_iso8601()
+        ))
+    }
+}
+
+/// Converts an Arrow `IntervalMonthDayNanoArray` into `RelativeDuration`s.
+pub fn from_month_day_nano_array(
+    array: &IntervalMonthDayNanoArray,
+) -> Vec<Option<RelativeDuration>> {
+    array
+        .iter()
+        .map(|v| v.map(RelativeDuration::from))
+        .collect()
+}
+
+/// Converts `RelativeDuration`s into an Arrow `IntervalMonthDayNanoArray`.
+///
+/// Returns Err if any of the durations cannot be represented.
+pub fn to_month_day_nano_array<I>(durations: I) -> Result<IntervalMonthDayNanoArray, String>
+where
+    I: IntoIterator<Item = Option<RelativeDuration>>,
+{
+    durations
+        .into_iter()
+        .map(|v| v.map(IntervalMonthDayNano::try_from).transpose())
+        .collect()
+}
+
+/// Converts an Arrow `IntervalYearMonthArray` into `RelativeDuration`s.
+pub fn from_year_month_array(array: &IntervalYearMonthArray) -> Vec<Option<RelativeDuration>> {
+    array.iter().map(|v| v.map(from_year_month)).collect()
+}
+
+/// Converts `RelativeDuration`s into an Arrow `IntervalYearMonthArray`.
+///
+/// Returns Err if any of the durations has a non-zero `Duration` component.
+pub fn to_year_month_array<I>(durations: I) -> Result<IntervalYearMonthArray, String>
+where
+    I: IntoIterator<Item = Option<RelativeDuration>>,
+{
+    durations
+        .into_iter()
+        .map(|v| v.as_ref().map(to_year_month).transpose())
+        .collect()
+}
+
+fn check_lengths(values: &dyn Array, intervals: &dyn Array) -> Result<(), String> {
+    if values.len() == intervals.len() {
+        Ok(())
+    } else {
+        Err(format!(
+            "cannot add {} intervals to {} values",
+            intervals.len(),
+            values.len()
+        ))
+    }
+}
+
+fn checked_add_date(date: NaiveDate, rhs: RelativeDuration) -> Option<NaiveDate> {
+    shift_months_opt(date, rhs.months)?.checked_add_signed(rhs.duration)
+}
+
+fn checked_add_datetime(datetime: NaiveDateTime, rhs: RelativeDuration) -> Option<NaiveDateTime> {
+    shift_months_opt(datetime, rhs.months)?.checked_add_signed(rhs.duration)
+}
+
+fn checked_add_datetime_tz(datetime: DateTime<Tz>, rhs: RelativeDuration) -> Option<DateTime<Tz>> {
+    shift_months_opt(datetime, rhs.months)?.checked_add_signed(rhs.duration)
+}
+
+fn add_to_timestamp_value<T: ArrowTimestampType>(
+    timestamp: i64,
+    rhs: RelativeDuration,
+    tz: Option<Tz>,
+) -> Option<i64> {
+    match tz {
+        Some(tz) => as_datetime_with_timezone::<T>(timestamp, tz)
+            .and_then(|d| checked_add_datetime_tz(d, rhs))
+            .and_then(|d| T::make_value(d.naive_utc())),
+        None => as_datetime::<T>(timestamp)
+            .and_then(|d| checked_add_datetime(d, rhs))
+            .and_then(T::make_value),
+    }
+}
+
+/// Adds an interval array to a `Date32Array` element-wise.
+///
+/// The result is null wherever either input is null. Any part of an interval smaller than a day
+/// is ignored, as when adding a `RelativeDuration` to a `NaiveDate`.
+///
+/// Returns Err if the arrays have different lengths or a result is out of range.
+pub fn add_to_date32(
+    dates: &Date32Array,
+    intervals: &IntervalMonthDayNanoArray,
+) -> Result<Date32Array, String> {
+    check_lengths(dates, intervals)?;
+
+    dates
+        .iter()
+        .zip(intervals.iter())
+        .map(|(date, interval)| match (date, interval) {
+            (Some(date), Some(interval)) => as_datetime::<Date32Type>(date.into())
+                .and_then(|d| checked_add_date(d.date(), interval.into()))
+                .map(|d| Some(d.num_days_from_ce() - DAYS_TO_UNIX_EPOCH))
+                .ok_or_else(|| format!("adding {:?} to date {} overflowed", interval, date)),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Adds an interval array to a timestamp array element-wise.
+///
+/// The result is null wherever either input is null, and keeps the timezone of `timestamps`.
+/// Intervals are applied to the local date-time when the array has a timezone, and to the
+/// naive date-time otherwise. Any part smaller than the unit of the timestamp array is truncated.
+///
+/// Returns Err if the arrays have different lengths, the timezone is not understood, or a result
+/// is out of range or falls in a daylight saving gap.
+pub fn add_to_timestamp<T: ArrowTimestampType>(
+    timestamps: &PrimitiveArray<T>,
+    intervals: &IntervalMonthDayNanoArray,
+) -> Result<PrimitiveArray<T>, String> {
+    check_lengths(timestamps, intervals)?;
+    let tz = timestamps
+        .timezone()
+        .map(|tz| {
+            tz.parse::<Tz>()
+                .map_err(|e| format!("unsupported timezone {}: {}", tz, e))
+        })
+        .transpose()?;
+
+    let shifted: PrimitiveArray<T> = timestamps
+        .iter()
+        .zip(intervals.iter())
+        .map(|(timestamp, interval)| match (timestamp, interval) {
+            (Some(timestamp), Some(interval)) => {
+                add_to_timestamp_value::<T>(timestamp, interval.into(), tz)
+                    .map(Some)
+                    .ok_or_else(|| {
+                        format!(
+                            "adding {:?} to timestamp {} overflowed",
+                            interval, timestamp
+                        )
+                    })
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(shifted.with_timezone_opt(timestamps.timezone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::types::TimestampMillisecondType;
+    use arrow_array::TimestampMillisecondArray;
+    use chrono::NaiveTime;
+
+    use super::*;
+
+    const NANOS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
+
+    fn date32(year: i32, month: u32, day: u32) -> i32 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .num_days_from_ce()
+            - DAYS_TO_UNIX_EPOCH
+    }
+
+    fn timestamp_ms(year: i32, month: u32, day: u32, time: NaiveTime) -> i64 {
+        TimestampMillisecondType::make_value(NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            time,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_month_day_nano_conversions() {
+        let interval = IntervalMonthDayNano::new(14, -3, -5);
+        let duration = RelativeDuration::years(1)
+            + RelativeDuration::months(2)
+            + RelativeDuration::days(-3)
+            + RelativeDuration::nanoseconds(-5);
+
+        assert_eq!(RelativeDuration::from(interval), duration);
+        assert_eq!(IntervalMonthDayNano::try_from(duration).unwrap(), interval);
+
+        // Mixed signs are normalised so that days and nanoseconds agree
+        let mixed = IntervalMonthDayNano::new(0, -3, 5);
+        assert_eq!(
+            IntervalMonthDayNano::try_from(RelativeDuration::from(mixed)).unwrap(),
+            IntervalMonthDayNano::new(0, -2, 5 - NANOS_PER_DAY)
+        );
+        assert_eq!(
+            IntervalMonthDayNano::try_from(RelativeDuration::hours(-25)).unwrap(),
+            IntervalMonthDayNano::new(0, -1, -60 * 60 * 1_000_000_000)
+        );
+        assert_eq!(
+            IntervalMonthDayNano::try_from(RelativeDuration::nanoseconds(NANOS_PER_DAY - 1))
+                .unwrap(),
+            IntervalMonthDayNano::new(0, 0, NANOS_PER_DAY - 1)
+        );
+        assert!(
+            IntervalMonthDayNano::try_from(RelativeDuration::days(i64::from(i32::MAX) + 1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_year_month_conversions() {
+        assert_eq!(from_year_month(-14), RelativeDuration::months(-14));
+        assert_eq!(to_year_month(&RelativeDuration::years(2)), Ok(24));
+        assert!(to_year_month(&(RelativeDuration::years(2) + RelativeDuration::days(1))).is_err());
+    }
+
+    #[test]
+    fn test_array_conversions() {
+        let durations = vec![
+            Some(RelativeDuration::months(1)),
+            None,
+            Some(RelativeDuration::days(2) + RelativeDuration::seconds(3)),
+        ];
+
+        let array = to_month_day_nano_array(durations.clone()).unwrap();
+        assert_eq!(array.len(), 3);
+        assert!(array.is_null(1));
+        assert_eq!(
+            array.value(2),
+            IntervalMonthDayNano::new(0, 2, 3_000_000_000)
+        );
+        assert_eq!(from_month_day_nano_array(&array), durations);
+
+        let array = to_year_month_array(vec![Some(RelativeDuration::years(1)), None]).unwrap();
+        assert_eq!(array.value(0), 12);
+        assert_eq!(
+            from_year_month_array(&array),
+            vec![Some(RelativeDuration::years(1)), None]
+        );
+        assert!(to_year_month_array(durations).is_err());
+    }
+
+    #[test]
+    fn test_add_to_date32() {
+        let dates = Date32Array::from(vec![
+            Some(date32(2020, 1, 31)),
+            Some(date32(2020, 1, 31)),
+            None,
+            Some(date32(2021, 3, 31)),
+        ]);
+        let intervals = IntervalMonthDayNanoArray::from(vec![
+            Some(IntervalMonthDayNano::new(1, 0, 0)),
+            Some(IntervalMonthDayNano::new(1, 1, 0)),
+            Some(IntervalMonthDayNano::new(1, 0, 0)),
+            Some(IntervalMonthDayNano::new(-1, 0, NANOS_PER_DAY - 1)),
+        ]);
+
+        assert_eq!(
+            add_to_date32(&dates, &intervals).unwrap(),
+            Date32Array::from(vec![
+                Some(date32(2020, 2, 29)),
+                Some(date32(2020, 3, 1)),
+                None,
+                Some(date32(2021, 2, 28)),
+            ])
+        );
+
+        let too_short = IntervalMonthDayNanoArray::from(vec![IntervalMonthDayNano::ZERO]);
+        assert!(add_to_date32(&dates, &too_short).is_err());
+
+        let overflow =
+            IntervalMonthDayNanoArray::from(vec![IntervalMonthDayNano::new(0, i32::MAX, 0)]);
+        let date = Date32Array::from(vec![date32(2020, 1, 1)]);
+        assert!(add_to_date32(&date, &overflow).is_err());
+    }
+
+    #[test]
+    fn test_add_to_timestamp() {
+        let time = NaiveTime::from_hms_milli_opt(1, 2, 3, 4).unwrap();
+        let timestamps = TimestampMillisecondArray::from(vec![
+            Some(timestamp_ms(2020, 1, 31, time)),
+            Some(timestamp_ms(2020, 1, 31, time)),
+            None,
+        ])
+        .with_timezone("+01:00");
+        let intervals = IntervalMonthDayNanoArray::from(vec![
+            Some(IntervalMonthDayNano::new(1, 0, 0)),
+            Some(IntervalMonthDayNano::new(13, 1, 1_000_001)),
+            Some(IntervalMonthDayNano::new(1, 0, 0)),
+        ]);
+
+        let shifted = add_to_timestamp(&timestamps, &intervals).unwrap();
+        assert_eq!(shifted.timezone(), Some("+01:00"));
+        assert_eq!(
+            shifted,
+            TimestampMillisecondArray::from(vec![
+                Some(timestamp_ms(2020, 2, 29, time)),
+                Some(timestamp_ms(
+                    2021,
+                    3,
+                    1,
+                    NaiveTime::from_hms_milli_opt(1, 2, 3, 5).unwrap()
+                )),
+                None,
+            ])
+            .with_timezone("+01:00")
+        );
+    }
+
+    #[test]
+    fn test_add_to_timestamp_local_month_end() {
+        // 2024-01-30 20:00 at -05:00 is 2024-01-31 01:00 UTC
+        let timestamps =
+            TimestampMillisecondArray::from(vec![timestamp_ms(2024, 1, 31, hms(1, 0, 0))]);
+        let intervals = IntervalMonthDayNanoArray::from(vec![IntervalMonthDayNano::new(1, 0, 0)]);
+
+        // Shifted from the local 30th, not the UTC 31st
+        let shifted = add_to_timestamp(&timestamps.clone().with_timezone("-05:00"), &intervals);
+        assert_eq!(
+            shifted.unwrap(),
+            TimestampMillisecondArray::from(vec![timestamp_ms(2024, 3, 1, hms(1, 0, 0))])
+                .with_timezone("-05:00")
+        );
+        let shifted = add_to_timestamp(&timestamps, &intervals);
+        assert_eq!(
+            shifted.unwrap(),
+            TimestampMillisecondArray::from(vec![timestamp_ms(2024, 2, 29, hms(1, 0, 0))])
+        );
+
+        let unknown = timestamps.with_timezone("Nowhere/Special");
+        assert!(add_to_timestamp(&unknown, &intervals).is_err());
+    }
+
+    fn hms(hour: u32, minute: u32, second: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, second).unwrap()
+    }
+}
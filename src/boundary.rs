@@ -0,0 +1,546 @@
+//! Contains functions for finding the start and end of calendar periods.
+//!
+//! Each period has a pair of functions: `start_of_*` floors a datelike value to the first day
+//! of the period containing it, and `end_of_*` ceils it to the last day of that period.
+//! For types with a time component the result is also moved to the first or last instant of
+//! that day:
+//!
+//! ```rust
+//! # use chrono::{NaiveDate, Weekday};
+//! # use chronoutil::boundary::{end_of_quarter, start_of_month, start_of_week};
+//! let datetime = NaiveDate::from_ymd_opt(2024, 2, 14).unwrap().and_hms_opt(9, 30, 0).unwrap();
+//!
+//! assert_eq!(
+//!     start_of_month(datetime),
+//!     NaiveDate::from_ymd_opt(2024, 2, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+//! );
+//! assert_eq!(
+//!     end_of_quarter(datetime),
+//!     NaiveDate::from_ymd_opt(2024, 3, 31).unwrap().and_hms_nano_opt(23, 59, 59, 999_999_999).unwrap()
+//! );
+//! assert_eq!(
+//!     start_of_week(datetime.date(), Weekday::Mon),
+//!     NaiveDate::from_ymd_opt(2024, 2, 12).unwrap()
+//! );
+//! ```
+//!
+//! Zoned values are resolved in their own time zone. When midnight is skipped by a daylight
+//! saving transition the day starts at the first valid local time, and when it is repeated
+//! the day starts at its first occurrence. The end of a day is always the last instant before
+//! the start of the next.
+//!
+//! The `start_of_*` and `end_of_*` functions panic if the boundary lies outside the range of
+//! representable dates, or cannot be resolved in its time zone. Each has an `_opt` counterpart
+//! which returns `None` instead.
+#[allow(deprecated)]
+use chrono::Date;
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
+
+use crate::delta::is_leap_year;
+
+/// A datelike type which can be moved to the first or last instant of a given day.
+///
+/// This is implemented for chrono's date and datetime types. Implement it for your own
+/// `Datelike` types to use them with the functions in this module.
+pub trait DayBounds: Datelike + Sized {
+    /// Returns the first instant of the given day, keeping any time zone of `self`.
+    fn at_start_of_day(&self, date: NaiveDate) -> Option<Self>;
+
+    /// Returns the last instant of the given day, keeping any time zone of `self`.
+    fn at_end_of_day(&self, date: NaiveDate) -> Option<Self>;
+}
+
+impl DayBounds for NaiveDate {
+    fn at_start_of_day(&self, date: NaiveDate) -> Option<Self> {
+        Some(date)
+    }
+
+    fn at_end_of_day(&self, date: NaiveDate) -> Option<Self> {
+        Some(date)
+    }
+}
+
+impl DayBounds for NaiveDateTime {
+    fn at_start_of_day(&self, date: NaiveDate) -> Option<Self> {
+        Some(date.and_time(NaiveTime::MIN))
+    }
+
+    fn at_end_of_day(&self, date: NaiveDate) -> Option<Self> {
+        date.and_hms_nano_opt(23, 59, 59, 999_999_999)
+    }
+}
+
+impl<Tz: TimeZone> DayBounds for DateTime<Tz> {
+    fn at_start_of_day(&self, date: NaiveDate) -> Option<Self> {
+        let timezone = self.timezone();
+        // Midnight may fall in a daylight saving gap, in which case the day starts at the
+        // first local time which exists
+        let mut local = date.and_time(NaiveTime::MIN);
+        while local.date() == date {
+            if let Some(start) = timezone.from_local_datetime(&local).earliest() {
+                return Some(start);
+            }
+            local = local.checked_add_signed(Duration::minutes(1))?;
+        }
+        None
+    }
+
+    fn at_end_of_day(&self, date: NaiveDate) -> Option<Self> {
+        self.at_start_of_day(date.succ_opt()?)?
+            .checked_sub_signed(Duration::nanoseconds(1))
+    }
+}
+
+#[allow(deprecated)]
+impl<Tz: TimeZone> DayBounds for Date<Tz> {
+    fn at_start_of_day(&self, date: NaiveDate) -> Option<Self> {
+        self.timezone().from_local_date(&date).earliest()
+    }
+
+    fn at_end_of_day(&self, date: NaiveDate) -> Option<Self> {
+        self.at_start_of_day(date)
+    }
+}
+
+/// Returns the number of days in the given month of the given year.
+///
+/// Panics if the month is not in the range 1-12.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    days_in_month_opt(year, month).unwrap_or_else(|| panic!("invalid month {}", month))
+}
+
+/// Same as [`days_in_month`] except returns `None` rather than panicking when the month is not
+/// in the range 1-12.
+pub fn days_in_month_opt(year: i32, month: u32) -> Option<u32> {
+    month_length(month, is_leap_year(year))
+}
+
+// Shared with calendars which follow the Gregorian month lengths with their own leap years
+pub(crate) fn month_length(month: u32, leap: bool) -> Option<u32> {
+    match month {
+        2 => Some(28 + leap as u32),
+        4 | 6 | 9 | 11 => Some(30),
+        1..=12 => Some(31),
+        _ => None,
+    }
+}
+
+/// Returns the number of days in the given year.
+pub fn days_in_year(year: i32) -> u32 {
+    365 + is_leap_year(year) as u32
+}
+
+/// Creates a date from a year, month and day, clamping the day to lie within the month.
+///
+/// Returns None if the month is not in the range 1-12 or the date is out of range.
+/// For example:
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::boundary::ymd;
+/// assert_eq!(ymd(2023, 2, 31), NaiveDate::from_ymd_opt(2023, 2, 28));
+/// assert_eq!(ymd(2023, 2, 0), NaiveDate::from_ymd_opt(2023, 2, 1));
+/// assert_eq!(ymd(2023, 13, 1), None);
+/// ```
+pub fn ymd(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day.clamp(1, days_in_month_opt(year, month)?))
+}
+
+fn naive_date<D: Datelike>(date: &D) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(date.num_days_from_ce())
+}
+
+fn start<D: DayBounds>(date: D, day: Option<NaiveDate>) -> Option<D> {
+    date.at_start_of_day(day?)
+}
+
+fn end<D: DayBounds>(date: D, day: Option<NaiveDate>) -> Option<D> {
+    date.at_end_of_day(day?)
+}
+
+// Returns the first month of the block of `months` months containing the date's month
+fn first_month<D: Datelike>(date: &D, months: u32) -> u32 {
+    (date.month() - 1) / months * months + 1
+}
+
+/// Returns the first instant of the day.
+pub fn start_of_day<D: DayBounds>(date: D) -> D {
+    start_of_day_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`start_of_day`] except returns `None` rather than panicking.
+pub fn start_of_day_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = naive_date(&date);
+    start(date, day)
+}
+
+/// Returns the last instant of the day.
+pub fn end_of_day<D: DayBounds>(date: D) -> D {
+    end_of_day_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`end_of_day`] except returns `None` rather than panicking.
+pub fn end_of_day_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = naive_date(&date);
+    end(date, day)
+}
+
+// Returns the first day of the week containing the date
+fn first_day_of_week<D: Datelike>(date: &D, week_start: Weekday) -> Option<NaiveDate> {
+    let day = naive_date(date)?;
+    let days = (7 + day.weekday().num_days_from_monday() - week_start.num_days_from_monday()) % 7;
+    day.checked_sub_signed(Duration::days(days.into()))
+}
+
+/// Returns the first instant of the week, for weeks beginning on `week_start`.
+pub fn start_of_week<D: DayBounds>(date: D, week_start: Weekday) -> D {
+    start_of_week_opt(date, week_start).expect("boundary out of range")
+}
+
+/// Same as [`start_of_week`] except returns `None` rather than panicking.
+pub fn start_of_week_opt<D: DayBounds>(date: D, week_start: Weekday) -> Option<D> {
+    let day = first_day_of_week(&date, week_start);
+    start(date, day)
+}
+
+/// Returns the last instant of the week, for weeks beginning on `week_start`.
+pub fn end_of_week<D: DayBounds>(date: D, week_start: Weekday) -> D {
+    end_of_week_opt(date, week_start).expect("boundary out of range")
+}
+
+/// Same as [`end_of_week`] except returns `None` rather than panicking.
+pub fn end_of_week_opt<D: DayBounds>(date: D, week_start: Weekday) -> Option<D> {
+    let day = first_day_of_week(&date, week_start)
+        .and_then(|day| day.checked_add_signed(Duration::days(6)));
+    end(date, day)
+}
+
+/// Returns the first instant of the month.
+pub fn start_of_month<D: DayBounds>(date: D) -> D {
+    start_of_month_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`start_of_month`] except returns `None` rather than panicking.
+pub fn start_of_month_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = NaiveDate::from_ymd_opt(date.year(), date.month(), 1);
+    start(date, day)
+}
+
+/// Returns the last instant of the month.
+pub fn end_of_month<D: DayBounds>(date: D) -> D {
+    end_of_month_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`end_of_month`] except returns `None` rather than panicking.
+pub fn end_of_month_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = ymd(date.year(), date.month(), 31);
+    end(date, day)
+}
+
+/// Returns the first instant of the quarter, where quarters begin in January, April, July
+/// and October.
+pub fn start_of_quarter<D: DayBounds>(date: D) -> D {
+    start_of_quarter_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`start_of_quarter`] except returns `None` rather than panicking.
+pub fn start_of_quarter_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = NaiveDate::from_ymd_opt(date.year(), first_month(&date, 3), 1);
+    start(date, day)
+}
+
+/// Returns the last instant of the quarter, where quarters end in March, June, September
+/// and December.
+pub fn end_of_quarter<D: DayBounds>(date: D) -> D {
+    end_of_quarter_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`end_of_quarter`] except returns `None` rather than panicking.
+pub fn end_of_quarter_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = ymd(date.year(), first_month(&date, 3) + 2, 31);
+    end(date, day)
+}
+
+/// Returns the first instant of the half-year, which begins in January or July.
+pub fn start_of_half<D: DayBounds>(date: D) -> D {
+    start_of_half_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`start_of_half`] except returns `None` rather than panicking.
+pub fn start_of_half_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = NaiveDate::from_ymd_opt(date.year(), first_month(&date, 6), 1);
+    start(date, day)
+}
+
+/// Returns the last instant of the half-year, which ends in June or December.
+pub fn end_of_half<D: DayBounds>(date: D) -> D {
+    end_of_half_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`end_of_half`] except returns `None` rather than panicking.
+pub fn end_of_half_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = ymd(date.year(), first_month(&date, 6) + 5, 31);
+    end(date, day)
+}
+
+/// Returns the first instant of the year.
+pub fn start_of_year<D: DayBounds>(date: D) -> D {
+    start_of_year_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`start_of_year`] except returns `None` rather than panicking.
+pub fn start_of_year_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = NaiveDate::from_ymd_opt(date.year(), 1, 1);
+    start(date, day)
+}
+
+/// Returns the last instant of the year.
+pub fn end_of_year<D: DayBounds>(date: D) -> D {
+    end_of_year_opt(date).expect("boundary out of range")
+}
+
+/// Same as [`end_of_year`] except returns `None` rather than panicking.
+pub fn end_of_year_opt<D: DayBounds>(date: D) -> Option<D> {
+    let day = NaiveDate::from_ymd_opt(date.year(), 12, 31);
+    end(date, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Utc};
+    use chrono_tz::America::{Havana, Sao_Paulo};
+    use chrono_tz::Europe::London;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        [
+            (2023, 1, 31),
+            (2023, 2, 28),
+            (2024, 2, 29),
+            (1900, 2, 28),
+            (2000, 2, 29),
+            (2023, 4, 30),
+            (2023, 12, 31),
+        ]
+        .iter()
+        .for_each(|(year, month, days)| {
+            assert_eq!(days_in_month(*year, *month), *days);
+            assert_eq!(days_in_month_opt(*year, *month), Some(*days));
+        });
+        [0, 13].iter().for_each(|month| {
+            assert_eq!(days_in_month_opt(2023, *month), None);
+        });
+
+        [(2023, 365), (2024, 366), (1900, 365), (2000, 366)]
+            .iter()
+            .for_each(|(year, days)| assert_eq!(days_in_year(*year), *days));
+    }
+
+    #[test]
+    fn test_opt_boundaries() {
+        let datetime = date(2024, 2, 14).and_hms_opt(9, 30, 0).unwrap();
+        [
+            (start_of_day_opt(datetime), (2024, 2, 14)),
+            (start_of_week_opt(datetime, Weekday::Mon), (2024, 2, 12)),
+            (start_of_month_opt(datetime), (2024, 2, 1)),
+            (start_of_quarter_opt(datetime), (2024, 1, 1)),
+            (start_of_half_opt(datetime), (2024, 1, 1)),
+            (start_of_year_opt(datetime), (2024, 1, 1)),
+        ]
+        .iter()
+        .for_each(|(actual, (y, m, d))| {
+            assert_eq!(*actual, Some(date(*y, *m, *d).and_time(NaiveTime::MIN)));
+        });
+        assert_eq!(end_of_month_opt(datetime), Some(end_of_month(datetime)));
+
+        // The boundaries lie beyond the range of dates
+        assert_eq!(end_of_year_opt(NaiveDate::MAX), Some(NaiveDate::MAX));
+        assert_eq!(start_of_week_opt(NaiveDate::MIN, Weekday::Tue), None);
+        assert_eq!(end_of_week_opt(NaiveDate::MAX, Weekday::Wed), None);
+        assert_eq!(
+            end_of_day_opt(Utc.from_utc_datetime(&NaiveDateTime::MAX)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ymd() {
+        [
+            ((2024, 2, 30), Some(date(2024, 2, 29))),
+            ((2023, 2, 30), Some(date(2023, 2, 28))),
+            ((2023, 4, 31), Some(date(2023, 4, 30))),
+            ((2023, 4, 0), Some(date(2023, 4, 1))),
+            ((2023, 4, 15), Some(date(2023, 4, 15))),
+            ((2023, 0, 1), None),
+            ((2023, 13, 1), None),
+            ((i32::MAX, 1, 1), None),
+        ]
+        .iter()
+        .for_each(|((year, month, day), expected)| {
+            assert_eq!(ymd(*year, *month, *day), *expected);
+        });
+    }
+
+    type Boundary = fn(NaiveDate) -> NaiveDate;
+
+    #[test]
+    fn test_naive_date_boundaries() {
+        let periods: [(Boundary, Boundary); 6] = [
+            (start_of_day, end_of_day),
+            (
+                |d| start_of_week(d, Weekday::Mon),
+                |d| end_of_week(d, Weekday::Mon),
+            ),
+            (start_of_month, end_of_month),
+            (start_of_quarter, end_of_quarter),
+            (start_of_half, end_of_half),
+            (start_of_year, end_of_year),
+        ];
+        // 2024-02-14 is a Wednesday
+        [
+            (date(2024, 2, 14), date(2024, 2, 14)),
+            (date(2024, 2, 12), date(2024, 2, 18)),
+            (date(2024, 2, 1), date(2024, 2, 29)),
+            (date(2024, 1, 1), date(2024, 3, 31)),
+            (date(2024, 1, 1), date(2024, 6, 30)),
+            (date(2024, 1, 1), date(2024, 12, 31)),
+        ]
+        .iter()
+        .zip(periods.iter())
+        .for_each(|((start, end), (start_of, end_of))| {
+            assert_eq!(start_of(date(2024, 2, 14)), *start);
+            assert_eq!(end_of(date(2024, 2, 14)), *end);
+            // Boundaries are fixed points
+            assert_eq!(start_of(*start), *start);
+            assert_eq!(end_of(*end), *end);
+        });
+
+        [
+            (date(2023, 8, 15), date(2023, 7, 1), date(2023, 9, 30)),
+            (date(2023, 11, 30), date(2023, 10, 1), date(2023, 12, 31)),
+            (date(2023, 4, 1), date(2023, 4, 1), date(2023, 6, 30)),
+        ]
+        .iter()
+        .for_each(|(day, start, end)| {
+            assert_eq!(start_of_quarter(*day), *start);
+            assert_eq!(end_of_quarter(*day), *end);
+        });
+        assert_eq!(start_of_half(date(2023, 8, 15)), date(2023, 7, 1));
+        assert_eq!(end_of_half(date(2023, 8, 15)), date(2023, 12, 31));
+    }
+
+    #[test]
+    fn test_week_start() {
+        // 2024-02-14 is a Wednesday
+        [
+            (Weekday::Mon, date(2024, 2, 12), date(2024, 2, 18)),
+            (Weekday::Sun, date(2024, 2, 11), date(2024, 2, 17)),
+            (Weekday::Wed, date(2024, 2, 14), date(2024, 2, 20)),
+            (Weekday::Thu, date(2024, 2, 8), date(2024, 2, 14)),
+            (Weekday::Sat, date(2024, 2, 10), date(2024, 2, 16)),
+        ]
+        .iter()
+        .for_each(|(week_start, start, end)| {
+            assert_eq!(start_of_week(date(2024, 2, 14), *week_start), *start);
+            assert_eq!(end_of_week(date(2024, 2, 14), *week_start), *end);
+        });
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_datetime_boundaries() {
+        let datetime = date(2024, 5, 17).and_hms_opt(13, 45, 10).unwrap();
+        assert_eq!(
+            start_of_month(datetime),
+            date(2024, 5, 1).and_hms_opt(0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            end_of_month(datetime),
+            date(2024, 5, 31)
+                .and_hms_nano_opt(23, 59, 59, 999_999_999)
+                .unwrap()
+        );
+
+        let offset = FixedOffset::east_opt(5 * 3600).unwrap();
+        let zoned = offset.from_local_datetime(&datetime).unwrap();
+        assert_eq!(
+            start_of_year(zoned),
+            offset.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            end_of_half(zoned),
+            offset.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap() - Duration::nanoseconds(1)
+        );
+
+        let utc = Utc.from_utc_datetime(&datetime);
+        assert_eq!(
+            start_of_day(utc),
+            Utc.with_ymd_and_hms(2024, 5, 17, 0, 0, 0).unwrap()
+        );
+
+        let day = Utc.from_utc_datetime(&datetime).date();
+        assert_eq!(end_of_quarter(day).naive_utc(), date(2024, 6, 30));
+    }
+
+    #[test]
+    fn test_daylight_saving() {
+        // London switches to BST on 2024-03-31, so the month starts in GMT and ends in BST
+        let datetime = London.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let start = start_of_month(datetime);
+        let end = end_of_month(datetime);
+        assert_eq!(start, London.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            end,
+            London.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap() - Duration::nanoseconds(1)
+        );
+        assert_eq!(
+            end.naive_local(),
+            date(2024, 3, 31)
+                .and_hms_nano_opt(23, 59, 59, 999_999_999)
+                .unwrap()
+        );
+        assert_eq!(
+            end - start,
+            Duration::days(31) - Duration::hours(1) - Duration::nanoseconds(1)
+        );
+
+        // Midnight did not exist in Sao Paulo on 2018-11-04, the day began at 01:00
+        let datetime = Sao_Paulo.with_ymd_and_hms(2018, 11, 4, 12, 0, 0).unwrap();
+        let start = start_of_day(datetime);
+        assert_eq!(
+            start.naive_local(),
+            date(2018, 11, 4).and_hms_opt(1, 0, 0).unwrap()
+        );
+        assert_eq!(
+            start,
+            Sao_Paulo.with_ymd_and_hms(2018, 11, 3, 23, 0, 0).unwrap() + Duration::hours(1)
+        );
+        assert_eq!(
+            end_of_day(Sao_Paulo.with_ymd_and_hms(2018, 11, 3, 12, 0, 0).unwrap()),
+            start - Duration::nanoseconds(1)
+        );
+
+        // Midnight happened twice in Havana on 2023-11-05, the day began at the first
+        let datetime = Havana.with_ymd_and_hms(2023, 11, 5, 12, 0, 0).unwrap();
+        let start = start_of_day(datetime);
+        assert_eq!(
+            start.naive_local(),
+            date(2023, 11, 5).and_hms_opt(0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            end_of_day(Havana.with_ymd_and_hms(2023, 11, 4, 12, 0, 0).unwrap()),
+            start - Duration::nanoseconds(1)
+        );
+        assert_eq!(
+            end_of_day(datetime) - start,
+            Duration::hours(25) - Duration::nanoseconds(1)
+        );
+    }
+}
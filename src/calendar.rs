@@ -0,0 +1,1331 @@
+//! Contains calendar systems which month and year arithmetic can be carried out in.
+//!
+//! The rest of the crate works in the proleptic Gregorian calendar, which is the
+//! [`Gregorian`] calendar here. The [`Julian`] calendar, the [`JulianGregorian`] calendar,
+//! which switches from Julian to Gregorian on a changeover date, the tabular Islamic
+//! [`Hijri`] calendar and the [`Hebrew`] calendar can be used instead:
+//!
+//! ```rust
+//! # use chrono::NaiveDate;
+//! # use chronoutil::calendar::{Calendar, Julian, JulianGregorian};
+//! // 1700 is a leap year in the Julian calendar but not in the Gregorian
+//! assert_eq!(Julian.days_in_month(1700, 2), 29);
+//! let start = Julian.date_from_calendar(1700, 1, 31).unwrap();
+//! assert_eq!(start, NaiveDate::from_ymd_opt(1700, 2, 10).unwrap());
+//! assert_eq!(Julian.to_calendar_date(Julian.shift_months(start, 1)), (1700, 2, 29));
+//!
+//! // Great Britain skipped from 2nd to 14th September 1752
+//! let start = NaiveDate::from_ymd_opt(1752, 8, 16).unwrap();
+//! assert_eq!(JulianGregorian::BRITISH.to_calendar_date(start), (1752, 8, 5));
+//! assert_eq!(
+//!     JulianGregorian::BRITISH.shift_months(start, 1),
+//!     NaiveDate::from_ymd_opt(1752, 9, 14).unwrap()
+//! );
+//! ```
+//!
+//! Dates are always stored as chrono types, which use the proleptic Gregorian calendar, and
+//! are converted to and from the calendar's own year, month and day as needed. A calendar can
+//! also be given to a [`DateRule`](crate::DateRule) with
+//! [`DateRule::with_calendar`](crate::DateRule::with_calendar), and is serialized with the rule
+//! when the `serde` feature is enabled.
+use std::convert::TryInto;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::boundary;
+use crate::delta::{self, resolve_weekday};
+
+// Julian day number 1721426 is 0001-01-01, the first day of the common era
+const JDN_CE_OFFSET: i64 = 1_721_425;
+
+/// A calendar system, with its own years, months and days.
+///
+/// Only [`is_leap_year`](Calendar::is_leap_year) and the conversions to and from chrono's
+/// proleptic Gregorian dates are required. The other methods follow from these, and may be
+/// overridden with faster versions.
+pub trait Calendar {
+    /// Returns true if the year is a leap-year in this calendar.
+    fn is_leap_year(&self, year: i32) -> bool;
+
+    /// Returns the year, month and day of a proleptic Gregorian date in this calendar.
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32);
+
+    /// Returns the proleptic Gregorian date with the given year, month and day in this
+    /// calendar.
+    ///
+    /// Days which were skipped when changing calendar are moved forwards to the first day
+    /// after the change. Returns None if the date is invalid or out of range.
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate>;
+
+    /// Returns the number of days in the given month of the given year.
+    ///
+    /// Panics if the year has no such month, which for most calendars means the month is not
+    /// in the range 1-12.
+    fn days_in_month(&self, year: i32, month: u32) -> u32 {
+        boundary::month_length(month, self.is_leap_year(year))
+            .unwrap_or_else(|| panic!("invalid month {}", month))
+    }
+
+    /// If the day lies within the month, returns it unchanged. Otherwise, shifts it backwards
+    /// to the final day of the month.
+    fn normalise_day(&self, year: i32, month: u32, day: u32) -> u32 {
+        day.min(self.days_in_month(year, month))
+    }
+
+    /// Shift a date by the given number of months in this calendar.
+    /// Ambiguous month-ends are shifted backwards as necessary.
+    fn shift_months<D: Datelike>(&self, date: D, months: i32) -> D {
+        self.shift_months_opt(date, months).unwrap()
+    }
+
+    /// Same as [`shift_months`](Calendar::shift_months) except fallible on unresolvable
+    /// dates/times.
+    ///
+    /// Returns `None` rather than panicking when shift results in an ambiguous or
+    /// non-existing date/time (e.g. in a DST transition).
+    fn shift_months_opt<D: Datelike>(&self, date: D, months: i32) -> Option<D> {
+        let (year, month, day) = self.to_calendar_date(naive_date(&date)?);
+        let total = i64::from(month) - 1 + i64::from(months);
+        let year = year.checked_add(total.div_euclid(12).try_into().ok()?)?;
+        let month = total.rem_euclid(12) as u32 + 1;
+        let target = self.date_from_calendar(year, month, self.normalise_day(year, month, day))?;
+        delta::with_date(date, target)
+    }
+
+    /// Shift a date by the given number of years in this calendar.
+    /// Ambiguous month-ends are shifted backwards as necessary.
+    fn shift_years<D: Datelike>(&self, date: D, years: i32) -> D {
+        self.shift_years_opt(date, years).unwrap()
+    }
+
+    /// Same as [`shift_years`](Calendar::shift_years) except fallible on unresolvable
+    /// dates/times.
+    fn shift_years_opt<D: Datelike>(&self, date: D, years: i32) -> Option<D> {
+        self.shift_months_opt(date, years.checked_mul(12)?)
+    }
+
+    /// Shift the date to have the given day of its month in this calendar, where negative
+    /// days count back from the end of the month as in [`with_day`](crate::with_day).
+    ///
+    /// Returns None if the day is out of range, or the result is an ambiguous or non-existing
+    /// date/time (e.g. in a DST transition).
+    fn with_day_opt<D: Datelike>(&self, date: D, day: i32) -> Option<D> {
+        let (year, month, _) = self.to_calendar_date(naive_date(&date)?);
+        let days = self.days_in_month(year, month);
+        let day = match day {
+            1..=31 => self.normalise_day(year, month, day as u32),
+            -31..=-1 => (days as i32 + 1 + day).max(1) as u32,
+            _ => return None,
+        };
+        delta::with_date(date, self.date_from_calendar(year, month, day)?)
+    }
+
+    /// Shift the date to the `n`th given weekday of its month in this calendar, where
+    /// negative values of `n` count back from the end of the month.
+    ///
+    /// A 5th (or -5th) weekday which does not exist is shifted back to the 4th (or -4th), as
+    /// in [`DateRule::with_rolling_weekday`](crate::DateRule::with_rolling_weekday).
+    /// Returns None if `n` is out of range, or the result is an ambiguous or non-existing
+    /// date/time (e.g. in a DST transition).
+    fn with_weekday_of_month_opt<D: Datelike>(
+        &self,
+        date: D,
+        weekday: Weekday,
+        n: i32,
+    ) -> Option<D> {
+        let (year, month, _) = self.to_calendar_date(naive_date(&date)?);
+        let mut days: Vec<NaiveDate> = (1..=self.days_in_month(year, month))
+            .filter_map(|day| self.date_from_calendar(year, month, day))
+            .filter(|day| day.weekday() == weekday)
+            .collect();
+        // Skipped days are moved onto the same date
+        days.dedup();
+
+        let count = days.len() as i32;
+        let index = match n {
+            1..=5 => (n - 1).min(count - 1),
+            -5..=-1 => (count + n).max(0),
+            _ => return None,
+        };
+        delta::with_date(date, *days.get(index as usize)?)
+    }
+}
+
+pub(crate) fn naive_date<D: Datelike>(date: &D) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(date.num_days_from_ce())
+}
+
+/// The proleptic Gregorian calendar, as used by chrono and the rest of this crate.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Gregorian;
+
+impl Calendar for Gregorian {
+    #[inline]
+    fn is_leap_year(&self, year: i32) -> bool {
+        delta::is_leap_year(year)
+    }
+
+    #[inline]
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+        (date.year(), date.month(), date.day())
+    }
+
+    #[inline]
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    #[inline]
+    fn normalise_day(&self, year: i32, month: u32, day: u32) -> u32 {
+        delta::normalise_day(year, month, day)
+    }
+
+    #[inline]
+    fn shift_months_opt<D: Datelike>(&self, date: D, months: i32) -> Option<D> {
+        delta::shift_months_opt(date, months)
+    }
+
+    #[inline]
+    fn with_day_opt<D: Datelike>(&self, date: D, day: i32) -> Option<D> {
+        delta::with_day_opt(date, day)
+    }
+
+    #[inline]
+    fn with_weekday_of_month_opt<D: Datelike>(
+        &self,
+        date: D,
+        weekday: Weekday,
+        n: i32,
+    ) -> Option<D> {
+        let day = resolve_weekday(date.year(), date.month(), weekday, n).ok()??;
+        date.with_day(day)
+    }
+}
+
+/// The proleptic Julian calendar, in which every fourth year is a leap year.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Julian;
+
+impl Calendar for Julian {
+    #[inline]
+    fn is_leap_year(&self, year: i32) -> bool {
+        year.rem_euclid(4) == 0
+    }
+
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+        // Converts from the Julian day number, with floored division for early dates
+        let c = i64::from(date.num_days_from_ce()) + JDN_CE_OFFSET + 32082;
+        let d = (4 * c + 3).div_euclid(1461);
+        let e = c - (1461 * d).div_euclid(4);
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let month = m + 3 - 12 * (m / 10);
+        let year = d - 4800 + m / 10;
+        (year as i32, month as u32, day as u32)
+    }
+
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        if !(1..=12).contains(&month) || day == 0 || day > self.days_in_month(year, month) {
+            return None;
+        }
+        let a = (14 - i64::from(month)) / 12;
+        let y = i64::from(year) + 4800 - a;
+        let m = i64::from(month) + 12 * a - 3;
+        let jdn = i64::from(day) + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32083;
+        NaiveDate::from_num_days_from_ce_opt((jdn - JDN_CE_OFFSET).try_into().ok()?)
+    }
+}
+
+/// The Julian calendar until a changeover date, and the Gregorian calendar from then on.
+///
+/// The days between the last Julian date and the changeover were skipped, so for example
+/// Thursday 4th October 1582 was followed by Friday 15th October 1582.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct JulianGregorian {
+    changeover: NaiveDate,
+}
+
+impl JulianGregorian {
+    /// The changeover of 15th October 1582, when the Gregorian calendar was introduced.
+    pub const PAPAL: Self = Self::from_ymd(1582, 10, 15);
+
+    /// The changeover of 14th September 1752 in Great Britain and its colonies.
+    pub const BRITISH: Self = Self::from_ymd(1752, 9, 14);
+
+    const fn from_ymd(year: i32, month: u32, day: u32) -> Self {
+        match NaiveDate::from_ymd_opt(year, month, day) {
+            Some(changeover) => Self { changeover },
+            None => panic!("invalid changeover"),
+        }
+    }
+
+    /// Creates a calendar changing to the Gregorian calendar on the given date.
+    #[inline]
+    pub fn new(changeover: NaiveDate) -> Self {
+        Self { changeover }
+    }
+
+    /// The first date in the Gregorian calendar.
+    #[inline]
+    pub fn changeover(&self) -> NaiveDate {
+        self.changeover
+    }
+}
+
+impl Default for JulianGregorian {
+    fn default() -> Self {
+        Self::PAPAL
+    }
+}
+
+impl Calendar for JulianGregorian {
+    fn is_leap_year(&self, year: i32) -> bool {
+        if year < self.changeover.year()
+            || (year == self.changeover.year() && self.changeover.month() > 2)
+        {
+            Julian.is_leap_year(year)
+        } else {
+            delta::is_leap_year(year)
+        }
+    }
+
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+        if date < self.changeover {
+            Julian.to_calendar_date(date)
+        } else {
+            Gregorian.to_calendar_date(date)
+        }
+    }
+
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        if let Some(date) = Julian.date_from_calendar(year, month, day) {
+            if date < self.changeover {
+                return Some(date);
+            }
+        }
+        let date = NaiveDate::from_ymd_opt(year, month, day)?;
+        Some(date.max(self.changeover))
+    }
+}
+
+/// A choice of leap years in the 30-year cycle of the tabular [`Hijri`] calendar.
+///
+/// Each cycle has 11 leap years, and the variants differ in which years these are.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum HijriCycle {
+    /// Years 2, 5, 7, 10, 13, 15, 18, 21, 24, 26 and 29, as attributed to Kūshyār.
+    Kushyar,
+    /// Years 2, 5, 7, 10, 13, 16, 18, 21, 24, 26 and 29, as used by most software.
+    #[default]
+    Standard,
+    /// Years 2, 5, 8, 10, 13, 16, 19, 21, 24, 27 and 29, as used by the Fatimids.
+    Fatimid,
+    /// Years 2, 5, 8, 11, 13, 16, 19, 21, 24, 27 and 30, as attributed to Habash al-Hasib.
+    HabashAlHasib,
+}
+
+impl HijriCycle {
+    // Bit n is set for leap year n of the cycle
+    fn leap_years(&self) -> u32 {
+        let years: [u32; 11] = match self {
+            HijriCycle::Kushyar => [2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29],
+            HijriCycle::Standard => [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29],
+            HijriCycle::Fatimid => [2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29],
+            HijriCycle::HabashAlHasib => [2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30],
+        };
+        years.iter().fold(0, |bits, year| bits | 1 << year)
+    }
+}
+
+// 1st Muharram 1 AH is Friday 16th July 622 in the Julian calendar
+const HIJRI_EPOCH: i64 = 227_015;
+const HIJRI_CYCLE_DAYS: i64 = 30 * 354 + 11;
+
+/// The tabular (arithmetical) Islamic calendar, with the civil epoch.
+///
+/// Odd months have 30 days and even months have 29, except in leap years when the 12th month
+/// has 30 days. Leap years are chosen by the [`HijriCycle`], which defaults to
+/// [`HijriCycle::Standard`].
+///
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::calendar::{Calendar, Hijri};
+/// // 1st Ramadan 1445 to 1st Shawwal 1445
+/// let start = Hijri::default().date_from_calendar(1445, 9, 1).unwrap();
+/// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 3, 11).unwrap());
+/// assert_eq!(
+///     Hijri::default().shift_months(start, 1),
+///     NaiveDate::from_ymd_opt(2024, 4, 10).unwrap()
+/// );
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Hijri {
+    cycle: HijriCycle,
+}
+
+impl Hijri {
+    /// Creates a tabular Hijri calendar with the given leap years.
+    #[inline]
+    pub fn new(cycle: HijriCycle) -> Self {
+        Self { cycle }
+    }
+
+    /// The choice of leap years.
+    #[inline]
+    pub fn cycle(&self) -> HijriCycle {
+        self.cycle
+    }
+
+    // The number of days from 1st Muharram 1 AH to the start of the given year
+    fn days_before_year(&self, year: i64) -> i64 {
+        let cycles = (year - 1).div_euclid(30);
+        let years = (year - 1).rem_euclid(30);
+        let leap_years = self.cycle.leap_years() & ((1 << (years + 1)) - 2);
+        cycles * HIJRI_CYCLE_DAYS + years * 354 + i64::from(leap_years.count_ones())
+    }
+}
+
+impl Calendar for Hijri {
+    #[inline]
+    fn is_leap_year(&self, year: i32) -> bool {
+        let year = (i64::from(year) - 1).rem_euclid(30) + 1;
+        self.cycle.leap_years() & (1 << year) != 0
+    }
+
+    #[inline]
+    fn days_in_month(&self, year: i32, month: u32) -> u32 {
+        match month {
+            12 => 29 + self.is_leap_year(year) as u32,
+            1..=11 => 30 - (month + 1) % 2,
+            _ => panic!("invalid month {}", month),
+        }
+    }
+
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+        let days = i64::from(date.num_days_from_ce()) - HIJRI_EPOCH;
+        let mut year = days.div_euclid(HIJRI_CYCLE_DAYS) * 30 + 1;
+        let mut days = days.rem_euclid(HIJRI_CYCLE_DAYS);
+        loop {
+            let length = 354 + self.is_leap_year(year as i32) as i64;
+            if days < length {
+                break;
+            }
+            days -= length;
+            year += 1;
+        }
+
+        // Months alternate between 30 and 29 days
+        let month = (days * 2 / 59 + 1).min(12);
+        let day = days - 29 * (month - 1) - month / 2 + 1;
+        (year as i32, month as u32, day as u32)
+    }
+
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        if !(1..=12).contains(&month) || day == 0 || day > self.days_in_month(year, month) {
+            return None;
+        }
+        let month = i64::from(month);
+        let days = HIJRI_EPOCH
+            + self.days_before_year(i64::from(year))
+            + 29 * (month - 1)
+            + month / 2
+            + i64::from(day)
+            - 1;
+        NaiveDate::from_num_days_from_ce_opt(days.try_into().ok()?)
+    }
+}
+
+/// How [`Hebrew`] dates are moved onto the same date in another year, for
+/// [`shift_years`](Calendar::shift_years), [`Hebrew::anniversary`] and yearly `DateRule`s.
+///
+/// Adar II always moves onto the last month of the other year, which is Adar in a common year,
+/// and Adar I moves onto Adar in a common year.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum HebrewAnniversary {
+    /// Days which do not exist in the month are shifted backwards to its final day, as for
+    /// other calendars. Adar in a common year moves onto Adar II in a leap year.
+    #[default]
+    Clamped,
+    /// Days which do not exist in the month roll over into the next month, so a birthday on
+    /// 30th Adar I falls on 1st Nisan in a common year. Adar in a common year moves onto Adar II
+    /// in a leap year.
+    Birthday,
+    /// The customary rules for the anniversary of a death. A death on 30th Marheshvan or 30th
+    /// Kislev is remembered on the last day of that month if the following year had no such
+    /// day, and otherwise on the following day when the month is short. A death on 30th Adar I
+    /// is remembered on 30th Shevat in a common year, and a death in Adar of a common year is
+    /// remembered in Adar I of a leap year.
+    Yahrzeit,
+}
+
+// 1st Tishrei AM 1 is Monday 7th October 3761 BCE in the Julian calendar
+const HEBREW_EPOCH: i64 = -1_373_427;
+
+/// The Hebrew calendar.
+///
+/// Months are numbered from Nisan, so that Tishrei, the first month of the year, is month 7
+/// and Adar is month 12. In leap years, month 12 is Adar I and the extra month 13 is Adar II.
+/// Leap years have 13 months, so [`shift_months`](Calendar::shift_months) counts Adar I and
+/// Adar II separately, while [`shift_years`](Calendar::shift_years) keeps the month and
+/// handles Adar and missing days according to the [`HebrewAnniversary`] rule:
+///
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::calendar::{Calendar, Hebrew, HebrewAnniversary};
+/// let calendar = Hebrew::default();
+/// // 30th Adar I 5784
+/// let start = calendar.date_from_calendar(5784, 12, 30).unwrap();
+/// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+/// // 30th Shevat 5785
+/// assert_eq!(calendar.to_calendar_date(calendar.shift_months(start, 12)), (5785, 11, 30));
+/// // 29th Adar 5785
+/// assert_eq!(calendar.to_calendar_date(calendar.shift_years(start, 1)), (5785, 12, 29));
+///
+/// let yahrzeit = Hebrew::new(HebrewAnniversary::Yahrzeit);
+/// assert_eq!(yahrzeit.to_calendar_date(yahrzeit.shift_years(start, 1)), (5785, 11, 30));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Hebrew {
+    anniversary: HebrewAnniversary,
+}
+
+impl Hebrew {
+    /// Creates a Hebrew calendar with the given rule for moving dates between years.
+    #[inline]
+    pub fn new(anniversary: HebrewAnniversary) -> Self {
+        Self { anniversary }
+    }
+
+    /// The rule for moving dates between years.
+    #[inline]
+    pub fn anniversary_rule(&self) -> HebrewAnniversary {
+        self.anniversary
+    }
+
+    /// Returns the number of months in the given year, which is 13 in leap years and 12
+    /// otherwise.
+    #[inline]
+    pub fn months_in_year(&self, year: i32) -> u32 {
+        12 + self.is_leap_year(year) as u32
+    }
+
+    /// Returns the number of days in the given year, which is one of 353, 354 or 355 in a
+    /// common year and one of 383, 384 or 385 in a leap year.
+    pub fn days_in_year(&self, year: i32) -> u32 {
+        let year = i64::from(year);
+        (hebrew_new_year(year + 1) - hebrew_new_year(year)) as u32
+    }
+
+    /// Returns the anniversary of the date in the given year, using the calendar's
+    /// [`HebrewAnniversary`] rule.
+    ///
+    /// Returns None if the result is out of range, or is an ambiguous or non-existing
+    /// date/time (e.g. in a DST transition).
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::calendar::{Hebrew, HebrewAnniversary};
+    /// // 30th Marheshvan 5783, which 5784 did not have
+    /// let death = NaiveDate::from_ymd_opt(2022, 11, 24).unwrap();
+    /// let yahrzeit = Hebrew::new(HebrewAnniversary::Yahrzeit);
+    /// // 29th Marheshvan 5784
+    /// assert_eq!(yahrzeit.anniversary(death, 5784), NaiveDate::from_ymd_opt(2023, 11, 13));
+    /// ```
+    pub fn anniversary<D: Datelike>(&self, date: D, year: i32) -> Option<D> {
+        let (from_year, month, day) = self.to_calendar_date(naive_date(&date)?);
+        let days = self.anniversary_days(from_year, month, day, year)?;
+        delta::with_date(
+            date,
+            NaiveDate::from_num_days_from_ce_opt(days.try_into().ok()?)?,
+        )
+    }
+
+    fn anniversary_days(&self, from_year: i32, month: u32, day: u32, year: i32) -> Option<i64> {
+        let adar_month = if month == self.months_in_year(from_year) {
+            self.months_in_year(year)
+        } else {
+            month
+        };
+        // The day before the 1st of the month after
+        let month_end = |month| self.days_from_calendar(year, month, 1) - 1;
+        let days = match self.anniversary {
+            HebrewAnniversary::Clamped => {
+                let day = self.normalise_day(year, adar_month, day);
+                self.days_from_calendar(year, adar_month, day)
+            }
+            HebrewAnniversary::Birthday => {
+                self.days_from_calendar(year, adar_month, 1) + i64::from(day) - 1
+            }
+            HebrewAnniversary::Yahrzeit => {
+                let next_year = self.days_in_year(from_year.checked_add(1)?);
+                if month == 8 && day == 30 && next_year % 10 != 5 {
+                    month_end(9)
+                } else if month == 9 && day == 30 && next_year % 10 == 3 {
+                    month_end(10)
+                } else if month == 13 {
+                    self.days_from_calendar(year, self.months_in_year(year), day)
+                } else if month == 12 && day == 30 && !self.is_leap_year(year) {
+                    self.days_from_calendar(year, 11, 30)
+                } else {
+                    self.days_from_calendar(year, month, 1) + i64::from(day) - 1
+                }
+            }
+        };
+        Some(days)
+    }
+
+    // The position of a month in the year, counting from Tishrei as 0
+    fn month_index(&self, year: i32, month: u32) -> u32 {
+        if month >= 7 {
+            month - 7
+        } else {
+            month + 5 + self.is_leap_year(year) as u32
+        }
+    }
+
+    // The month at a position in the year, counting from Tishrei as 0
+    fn month_at(&self, year: i32, index: u32) -> u32 {
+        let months = self.months_in_year(year);
+        if index + 6 < months {
+            index + 7
+        } else {
+            index + 6 - months + 1
+        }
+    }
+
+    // The number of days from the start of the common era, without checking the date
+    fn days_from_calendar(&self, year: i32, month: u32, day: u32) -> i64 {
+        let days_before_month: u32 = (0..self.month_index(year, month))
+            .map(|index| self.days_in_month(year, self.month_at(year, index)))
+            .sum();
+        hebrew_new_year(i64::from(year)) + i64::from(days_before_month) + i64::from(day) - 1
+    }
+}
+
+// The number of days from the epoch to the molad of Tishrei, postponed when it falls on a
+// Sunday, Wednesday or Friday
+fn hebrew_elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12084 + 13753 * months;
+    let days = 29 * months + parts.div_euclid(25920);
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+// The number of days from the start of the common era to 1st Tishrei of the year
+fn hebrew_new_year(year: i64) -> i64 {
+    let (previous, current, next) = (
+        hebrew_elapsed_days(year - 1),
+        hebrew_elapsed_days(year),
+        hebrew_elapsed_days(year + 1),
+    );
+    // Further postponements which keep the year to a valid length
+    let delay = if next - current == 356 {
+        2
+    } else if current - previous == 382 {
+        1
+    } else {
+        0
+    };
+    HEBREW_EPOCH + current + delay
+}
+
+impl Calendar for Hebrew {
+    #[inline]
+    fn is_leap_year(&self, year: i32) -> bool {
+        (7 * i64::from(year) + 1).rem_euclid(19) < 7
+    }
+
+    fn days_in_month(&self, year: i32, month: u32) -> u32 {
+        match month {
+            2 | 4 | 6 | 10 => 29,
+            // Adar I in a leap year, and Adar otherwise
+            12 => 29 + self.is_leap_year(year) as u32,
+            13 if self.is_leap_year(year) => 29,
+            // Marheshvan and Kislev vary with the length of the year
+            8 => 29 + (self.days_in_year(year) % 10 == 5) as u32,
+            9 => 30 - (self.days_in_year(year) % 10 == 3) as u32,
+            1 | 3 | 5 | 7 | 11 => 30,
+            _ => panic!("invalid month {}", month),
+        }
+    }
+
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+        let days = i64::from(date.num_days_from_ce());
+        // An underestimate of the year, using the average year length
+        let mut year = ((days - HEBREW_EPOCH) * 98496).div_euclid(35_975_351);
+        while hebrew_new_year(year + 1) <= days {
+            year += 1;
+        }
+        let year = year as i32;
+
+        let mut days = days - hebrew_new_year(i64::from(year));
+        let mut index = 0;
+        loop {
+            let length = i64::from(self.days_in_month(year, self.month_at(year, index)));
+            if days < length {
+                break;
+            }
+            days -= length;
+            index += 1;
+        }
+        (year, self.month_at(year, index), days as u32 + 1)
+    }
+
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        if !(1..=self.months_in_year(year)).contains(&month)
+            || day == 0
+            || day > self.days_in_month(year, month)
+        {
+            return None;
+        }
+        let days = self.days_from_calendar(year, month, day);
+        NaiveDate::from_num_days_from_ce_opt(days.try_into().ok()?)
+    }
+
+    fn shift_months_opt<D: Datelike>(&self, date: D, months: i32) -> Option<D> {
+        let (year, month, day) = self.to_calendar_date(naive_date(&date)?);
+        // Every 19 years have 235 months
+        let index = i64::from(self.month_index(year, month)) + i64::from(months);
+        let mut year = i64::from(year) + 19 * index.div_euclid(235);
+        let mut index = index.rem_euclid(235) as u32;
+        loop {
+            let months = self.months_in_year(year.try_into().ok()?);
+            if index < months {
+                break;
+            }
+            index -= months;
+            year += 1;
+        }
+
+        let year = year.try_into().ok()?;
+        let month = self.month_at(year, index);
+        let target = self.date_from_calendar(year, month, self.normalise_day(year, month, day))?;
+        delta::with_date(date, target)
+    }
+
+    fn shift_years_opt<D: Datelike>(&self, date: D, years: i32) -> Option<D> {
+        if years == 0 {
+            return Some(date);
+        }
+        let (year, _, _) = self.to_calendar_date(naive_date(&date)?);
+        self.anniversary(date, year.checked_add(years)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_julian_conversions() {
+        // Julian dates alongside their proleptic Gregorian equivalents
+        [
+            ((1, 1, 3), date(1, 1, 1)),
+            ((0, 12, 31), date(0, 12, 29)),
+            ((1582, 10, 4), date(1582, 10, 14)),
+            ((1582, 10, 5), date(1582, 10, 15)),
+            ((1700, 2, 18), date(1700, 2, 28)),
+            ((1700, 2, 19), date(1700, 3, 1)),
+            ((1700, 2, 29), date(1700, 3, 11)),
+            ((1752, 9, 2), date(1752, 9, 13)),
+            ((1918, 1, 31), date(1918, 2, 13)),
+            ((2024, 4, 22), date(2024, 5, 5)),
+        ]
+        .iter()
+        .for_each(|((year, month, day), gregorian)| {
+            assert_eq!(Julian.to_calendar_date(*gregorian), (*year, *month, *day));
+            assert_eq!(
+                Julian.date_from_calendar(*year, *month, *day),
+                Some(*gregorian)
+            );
+        });
+
+        [(1900, 2, 30), (1901, 2, 29), (1900, 13, 1), (1900, 1, 0)]
+            .iter()
+            .for_each(|(year, month, day)| {
+                assert_eq!(Julian.date_from_calendar(*year, *month, *day), None);
+            });
+        assert!(Julian.date_from_calendar(1900, 2, 29).is_some());
+
+        // Round trips across the full range of NaiveDate
+        let mut day = NaiveDate::MIN;
+        while day < NaiveDate::MAX - chrono::Duration::days(1000) {
+            let (year, month, d) = Julian.to_calendar_date(day);
+            assert_eq!(Julian.date_from_calendar(year, month, d), Some(day));
+            day += chrono::Duration::days(997);
+        }
+    }
+
+    #[test]
+    fn test_changeover() {
+        let calendar = JulianGregorian::PAPAL;
+        assert_eq!(calendar.to_calendar_date(date(1582, 10, 14)), (1582, 10, 4));
+        assert_eq!(
+            calendar.to_calendar_date(date(1582, 10, 15)),
+            (1582, 10, 15)
+        );
+        assert_eq!(
+            calendar.date_from_calendar(1582, 10, 4),
+            Some(date(1582, 10, 14))
+        );
+        assert_eq!(
+            calendar.date_from_calendar(1582, 10, 15),
+            Some(date(1582, 10, 15))
+        );
+        // Skipped days move forwards to the changeover
+        assert_eq!(
+            calendar.date_from_calendar(1582, 10, 10),
+            Some(date(1582, 10, 15))
+        );
+
+        [
+            (1500, true),
+            (1582, false),
+            (1600, true),
+            (1700, false),
+            (1584, true),
+        ]
+        .iter()
+        .for_each(|(year, leap)| assert_eq!(calendar.is_leap_year(*year), *leap));
+        assert!(JulianGregorian::BRITISH.is_leap_year(1700));
+        assert!(!JulianGregorian::BRITISH.is_leap_year(1800));
+        assert_eq!(JulianGregorian::default(), calendar);
+    }
+
+    #[test]
+    fn test_shift_months() {
+        [
+            // Month-ends in a Julian leap year
+            (date(1700, 1, 31), 1, (1700, 2, 29)),
+            (date(1700, 1, 31), 2, (1700, 3, 31)),
+            (date(1699, 12, 15), -1, (1699, 11, 15)),
+            (date(2023, 1, 14), 12, (2024, 1, 14)),
+        ]
+        .iter()
+        .for_each(|(start, months, (year, month, day))| {
+            let julian = Julian
+                .date_from_calendar(start.year(), start.month(), start.day())
+                .unwrap();
+            assert_eq!(
+                Julian.to_calendar_date(Julian.shift_months(julian, *months)),
+                (*year, *month, *day)
+            );
+        });
+
+        let calendar = JulianGregorian::BRITISH;
+        [
+            // Julian 1752-08-02 to the last Julian day, 1752-09-02
+            (date(1752, 8, 13), 1, date(1752, 9, 13)),
+            (date(1752, 8, 13), 2, date(1752, 10, 2)),
+            // Julian 1752-08-05 to a skipped day, which moves to the changeover
+            (date(1752, 8, 16), 1, date(1752, 9, 14)),
+            (date(1752, 9, 14), -1, date(1752, 8, 25)),
+            // Julian 1752-08-31 to the Gregorian month-end
+            (date(1752, 9, 11), 1, date(1752, 9, 30)),
+            (date(1751, 2, 28), 12, date(1752, 2, 28)),
+            (date(1753, 2, 28), -12, date(1752, 3, 10)),
+        ]
+        .iter()
+        .for_each(|(start, months, expected)| {
+            assert_eq!(
+                calendar.shift_months(*start, *months),
+                *expected,
+                "{} {}",
+                start,
+                months
+            );
+        });
+
+        // Gregorian agrees with the shift functions
+        (-30..30).for_each(|months| {
+            assert_eq!(
+                Gregorian.shift_months(date(2020, 1, 31), months),
+                delta::shift_months(date(2020, 1, 31), months)
+            );
+        });
+        assert_eq!(
+            Julian.shift_years(date(1700, 3, 11), -1),
+            Julian.date_from_calendar(1699, 2, 28).unwrap()
+        );
+
+        // Out of range rather than overflowing
+        let start = date(2024, 12, 15);
+        [i32::MAX, i32::MIN].iter().for_each(|months| {
+            assert_eq!(Julian.shift_months_opt(start, *months), None);
+            assert_eq!(
+                JulianGregorian::PAPAL.shift_months_opt(start, *months),
+                None
+            );
+            assert_eq!(Julian.shift_years_opt(start, *months / 12), None);
+            assert_eq!(
+                JulianGregorian::PAPAL.shift_years_opt(start, *months / 12),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn test_with_day_and_weekday() {
+        let calendar = JulianGregorian::PAPAL;
+        // October 1582 ran 1-4 then 15-31, so had three Mondays and three Thursdays
+        let start = date(1582, 10, 20);
+        assert_eq!(calendar.with_day_opt(start, 1), Some(date(1582, 10, 11)));
+        assert_eq!(calendar.with_day_opt(start, -1), Some(date(1582, 10, 31)));
+        assert_eq!(calendar.with_day_opt(start, 7), Some(date(1582, 10, 15)));
+        assert_eq!(calendar.with_day_opt(start, 0), None);
+        [
+            (Weekday::Mon, 1, date(1582, 10, 11)),
+            (Weekday::Mon, 2, date(1582, 10, 18)),
+            (Weekday::Mon, 5, date(1582, 10, 25)),
+            (Weekday::Thu, -1, date(1582, 10, 28)),
+            (Weekday::Thu, -5, date(1582, 10, 14)),
+        ]
+        .iter()
+        .for_each(|(weekday, n, expected)| {
+            assert_eq!(
+                calendar.with_weekday_of_month_opt(start, *weekday, *n),
+                Some(*expected),
+                "{} {}",
+                weekday,
+                n
+            );
+        });
+    }
+
+    #[test]
+    fn test_hijri_conversions() {
+        let calendar = Hijri::default();
+        [
+            ((1, 1, 1), date(622, 7, 19)),
+            (
+                (-1245, 12, 9),
+                NaiveDate::from_num_days_from_ce_opt(-214_193).unwrap(),
+            ),
+            ((1364, 12, 6), date(1945, 11, 12)),
+            ((1420, 9, 24), date(2000, 1, 1)),
+            ((1444, 9, 1), date(2023, 3, 23)),
+            ((1445, 1, 1), date(2023, 7, 19)),
+            ((1445, 12, 30), date(2024, 7, 7)),
+            ((1446, 12, 29), date(2025, 6, 26)),
+        ]
+        .iter()
+        .for_each(|((year, month, day), gregorian)| {
+            assert_eq!(calendar.to_calendar_date(*gregorian), (*year, *month, *day));
+            assert_eq!(
+                calendar.date_from_calendar(*year, *month, *day),
+                Some(*gregorian)
+            );
+        });
+
+        [(1446, 12, 30), (1445, 2, 30), (1445, 13, 1), (1445, 1, 0)]
+            .iter()
+            .for_each(|(year, month, day)| {
+                assert_eq!(calendar.date_from_calendar(*year, *month, *day), None);
+            });
+
+        // Agrees with the closed form for the standard cycle, and round trips across the
+        // full range of NaiveDate
+        let mut day = NaiveDate::MIN;
+        while day < NaiveDate::MAX - chrono::Duration::days(1000) {
+            let (year, month, d) = calendar.to_calendar_date(day);
+            let (y, m) = (i64::from(year), i64::from(month));
+            let expected = HIJRI_EPOCH - 1
+                + (y - 1) * 354
+                + (3 + 11 * y).div_euclid(30)
+                + 29 * (m - 1)
+                + m / 2
+                + i64::from(d);
+            assert_eq!(i64::from(day.num_days_from_ce()), expected);
+            assert_eq!(calendar.date_from_calendar(year, month, d), Some(day));
+            day += chrono::Duration::days(997);
+        }
+    }
+
+    #[test]
+    fn test_hijri_cycles() {
+        [
+            (
+                HijriCycle::Kushyar,
+                [2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29],
+            ),
+            (
+                HijriCycle::Standard,
+                [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29],
+            ),
+            (
+                HijriCycle::Fatimid,
+                [2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29],
+            ),
+            (
+                HijriCycle::HabashAlHasib,
+                [2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30],
+            ),
+        ]
+        .iter()
+        .for_each(|(cycle, expected)| {
+            let calendar = Hijri::new(*cycle);
+            assert_eq!(calendar.cycle(), *cycle);
+            [-60, 0, 1410].iter().for_each(|start| {
+                let leap_years: Vec<i32> = (1..=30)
+                    .filter(|year| calendar.is_leap_year(start + year))
+                    .collect();
+                assert_eq!(leap_years, expected.to_vec());
+            });
+
+            // Every cycle has the same length
+            let cycle_start = calendar.date_from_calendar(1411, 1, 1).unwrap();
+            let next_cycle = calendar.date_from_calendar(1441, 1, 1).unwrap();
+            assert_eq!((next_cycle - cycle_start).num_days(), HIJRI_CYCLE_DAYS);
+            assert_eq!(next_cycle, date(2019, 9, 1));
+        });
+
+        // The cycles differ on the dates following a leap year
+        assert_eq!(
+            Hijri::new(HijriCycle::Kushyar).date_from_calendar(1426, 1, 1),
+            Some(date(2005, 2, 11))
+        );
+        assert_eq!(
+            Hijri::new(HijriCycle::HabashAlHasib).date_from_calendar(1421, 1, 1),
+            Some(date(2000, 4, 5))
+        );
+    }
+
+    #[test]
+    fn test_hijri_shifts() {
+        let calendar = Hijri::default();
+        let hijri = |year, month, day| calendar.date_from_calendar(year, month, day).unwrap();
+        [
+            ((1445, 1, 30), 1, (1445, 2, 29)),
+            ((1445, 1, 30), 2, (1445, 3, 30)),
+            ((1445, 11, 30), 1, (1445, 12, 30)),
+            ((1446, 11, 30), 1, (1446, 12, 29)),
+            ((1445, 12, 30), 12, (1446, 12, 29)),
+            ((1445, 3, 15), -3, (1444, 12, 15)),
+        ]
+        .iter()
+        .for_each(|((year, month, day), months, expected)| {
+            let start = hijri(*year, *month, *day);
+            assert_eq!(
+                calendar.to_calendar_date(calendar.shift_months(start, *months)),
+                *expected
+            );
+        });
+        assert_eq!(calendar.normalise_day(1445, 2, 30), 29);
+        assert_eq!(
+            calendar.with_day_opt(hijri(1446, 12, 1), -1),
+            Some(hijri(1446, 12, 29))
+        );
+
+        // Monthly payments on the last day of each Hijri month
+        let dates: Vec<(i32, u32, u32)> = crate::DateRule::monthly(hijri(1445, 11, 1))
+            .with_rolling_day(-1)
+            .unwrap()
+            .with_calendar(calendar)
+            .with_count(4)
+            .map(|date| calendar.to_calendar_date(date))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![(1445, 11, 30), (1445, 12, 30), (1446, 1, 30), (1446, 2, 29)]
+        );
+
+        // Out of range rather than overflowing
+        let start = hijri(1445, 9, 1);
+        // The 22nd and 7th years of their cycles
+        assert!(!calendar.is_leap_year(i32::MIN));
+        assert!(calendar.is_leap_year(i32::MAX));
+        [i32::MAX, i32::MIN].iter().for_each(|months| {
+            assert_eq!(calendar.shift_months_opt(start, *months), None);
+            assert_eq!(calendar.shift_years_opt(start, *months / 12), None);
+        });
+    }
+
+    #[test]
+    fn test_hebrew_conversions() {
+        let calendar = Hebrew::default();
+        [
+            (
+                (1, 7, 1),
+                NaiveDate::from_num_days_from_ce_opt(-1_373_427).unwrap(),
+            ),
+            (
+                (3174, 5, 10),
+                NaiveDate::from_num_days_from_ce_opt(-214_193).unwrap(),
+            ),
+            ((5706, 9, 7), date(1945, 11, 12)),
+            ((5783, 8, 30), date(2022, 11, 24)),
+            ((5784, 7, 1), date(2023, 9, 16)),
+            ((5784, 12, 30), date(2024, 3, 10)),
+            ((5784, 13, 14), date(2024, 3, 24)),
+            ((5784, 1, 15), date(2024, 4, 23)),
+            ((5785, 7, 1), date(2024, 10, 3)),
+            ((5785, 9, 25), date(2024, 12, 26)),
+            ((5785, 12, 14), date(2025, 3, 14)),
+            ((5786, 7, 1), date(2025, 9, 23)),
+        ]
+        .iter()
+        .for_each(|((year, month, day), gregorian)| {
+            assert_eq!(calendar.to_calendar_date(*gregorian), (*year, *month, *day));
+            assert_eq!(
+                calendar.date_from_calendar(*year, *month, *day),
+                Some(*gregorian)
+            );
+        });
+
+        // Each kind of year, alongside the lengths of Marheshvan, Kislev and Adar
+        [
+            (5781, 353, [29, 29, 29]),
+            (5786, 354, [29, 30, 29]),
+            (5785, 355, [30, 30, 29]),
+            (5784, 383, [29, 29, 30]),
+            (5782, 384, [29, 30, 30]),
+            (5787, 385, [30, 30, 30]),
+        ]
+        .iter()
+        .for_each(|(year, days, months)| {
+            assert_eq!(calendar.days_in_year(*year), *days);
+            assert_eq!(calendar.months_in_year(*year), 12 + (*days > 355) as u32);
+            assert_eq!(
+                [8, 9, 12].map(|month| calendar.days_in_month(*year, month)),
+                *months
+            );
+            let total: u32 = (1..=calendar.months_in_year(*year))
+                .map(|month| calendar.days_in_month(*year, month))
+                .sum();
+            assert_eq!(total, *days);
+        });
+
+        [
+            (5785, 13, 1),
+            (5786, 8, 30),
+            (5784, 9, 30),
+            (5784, 14, 1),
+            (5784, 1, 0),
+        ]
+        .iter()
+        .for_each(|(year, month, day)| {
+            assert_eq!(calendar.date_from_calendar(*year, *month, *day), None);
+        });
+
+        // Round trips across the full range of NaiveDate
+        let mut day = NaiveDate::MIN;
+        while day < NaiveDate::MAX - chrono::Duration::days(1000) {
+            let (year, month, d) = calendar.to_calendar_date(day);
+            assert_eq!(calendar.date_from_calendar(year, month, d), Some(day));
+            day += chrono::Duration::days(997);
+        }
+    }
+
+    #[test]
+    fn test_hebrew_shift_months() {
+        let calendar = Hebrew::default();
+        let hebrew = |year, month, day| calendar.date_from_calendar(year, month, day).unwrap();
+        [
+            // Months are counted through Adar I and Adar II
+            ((5784, 12, 30), 1, (5784, 13, 29)),
+            ((5784, 12, 30), 2, (5784, 1, 30)),
+            ((5784, 12, 30), 12, (5785, 11, 30)),
+            ((5784, 12, 30), 13, (5785, 12, 29)),
+            ((5784, 7, 1), 13, (5785, 7, 1)),
+            ((5785, 7, 1), 12, (5786, 7, 1)),
+            ((5785, 7, 1), -13, (5784, 7, 1)),
+            ((5785, 6, 29), 1, (5786, 7, 29)),
+            // 235 months is 19 years
+            ((5785, 8, 30), 235, (5804, 8, 29)),
+            ((5785, 8, 30), -235 * 3 + 1, (5728, 9, 30)),
+        ]
+        .iter()
+        .for_each(|((year, month, day), months, expected)| {
+            let start = hebrew(*year, *month, *day);
+            assert_eq!(
+                calendar.to_calendar_date(calendar.shift_months(start, *months)),
+                *expected,
+                "{:?} {}",
+                (year, month, day),
+                months
+            );
+        });
+        assert_eq!(calendar.shift_months_opt(date(2024, 1, 1), i32::MAX), None);
+    }
+
+    #[test]
+    fn test_hebrew_anniversaries() {
+        // The anniversary of a date under the clamped, birthday and yahrzeit rules
+        [
+            (
+                (5784, 12, 30),
+                5785,
+                [(5785, 12, 29), (5785, 1, 1), (5785, 11, 30)],
+            ),
+            (
+                (5784, 12, 30),
+                5787,
+                [(5787, 12, 30), (5787, 12, 30), (5787, 12, 30)],
+            ),
+            (
+                (5784, 13, 14),
+                5785,
+                [(5785, 12, 14), (5785, 12, 14), (5785, 12, 14)],
+            ),
+            (
+                (5784, 13, 14),
+                5787,
+                [(5787, 13, 14), (5787, 13, 14), (5787, 13, 14)],
+            ),
+            (
+                (5785, 12, 10),
+                5787,
+                [(5787, 13, 10), (5787, 13, 10), (5787, 12, 10)],
+            ),
+            (
+                (5783, 8, 30),
+                5784,
+                [(5784, 8, 29), (5784, 9, 1), (5784, 8, 29)],
+            ),
+            (
+                (5783, 8, 30),
+                5785,
+                [(5785, 8, 30), (5785, 8, 30), (5785, 8, 30)],
+            ),
+            (
+                (5779, 8, 30),
+                5781,
+                [(5781, 8, 29), (5781, 9, 1), (5781, 9, 1)],
+            ),
+            (
+                (5782, 9, 30),
+                5783,
+                [(5783, 9, 30), (5783, 9, 30), (5783, 9, 30)],
+            ),
+            (
+                (5785, 7, 1),
+                5786,
+                [(5786, 7, 1), (5786, 7, 1), (5786, 7, 1)],
+            ),
+            (
+                (5784, 1, 15),
+                5790,
+                [(5790, 1, 15), (5790, 1, 15), (5790, 1, 15)],
+            ),
+        ]
+        .iter()
+        .for_each(|((year, month, day), target, expected)| {
+            [
+                HebrewAnniversary::Clamped,
+                HebrewAnniversary::Birthday,
+                HebrewAnniversary::Yahrzeit,
+            ]
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(rule, expected)| {
+                let calendar = Hebrew::new(*rule);
+                assert_eq!(calendar.anniversary_rule(), *rule);
+                let start = calendar.date_from_calendar(*year, *month, *day).unwrap();
+                let anniversary = calendar.anniversary(start, *target).unwrap();
+                assert_eq!(
+                    calendar.to_calendar_date(anniversary),
+                    *expected,
+                    "{:?} {:?}",
+                    (year, month, day),
+                    rule
+                );
+                assert_eq!(calendar.shift_years(start, target - year), anniversary);
+            });
+        });
+
+        // Yearly rules give anniversaries, while monthly rules count every month
+        let calendar = Hebrew::new(HebrewAnniversary::Yahrzeit);
+        let death = calendar.date_from_calendar(5784, 12, 30).unwrap();
+        let dates: Vec<(i32, u32, u32)> = crate::DateRule::yearly(death)
+            .with_calendar(calendar)
+            .with_count(4)
+            .map(|date| calendar.to_calendar_date(date))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                (5784, 12, 30),
+                (5785, 11, 30),
+                (5786, 11, 30),
+                (5787, 12, 30)
+            ]
+        );
+        let dates: Vec<(i32, u32, u32)> = crate::DateRule::monthly(death)
+            .with_calendar(calendar)
+            .with_count(4)
+            .map(|date| calendar.to_calendar_date(date))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![(5784, 12, 30), (5784, 13, 29), (5784, 1, 30), (5784, 2, 29)]
+        );
+    }
+
+    // The Gregorian calendar without any of its faster methods
+    struct PlainGregorian;
+
+    impl Calendar for PlainGregorian {
+        fn is_leap_year(&self, year: i32) -> bool {
+            Gregorian.is_leap_year(year)
+        }
+
+        fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+            Gregorian.to_calendar_date(date)
+        }
+
+        fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+            Gregorian.date_from_calendar(year, month, day)
+        }
+    }
+
+    #[test]
+    fn test_provided_methods() {
+        let mut start = date(2019, 12, 1);
+        while start < date(2021, 3, 1) {
+            [-25, -13, -1, 1, 2, 12, 14].iter().for_each(|months| {
+                assert_eq!(
+                    PlainGregorian.shift_months_opt(start, *months),
+                    Gregorian.shift_months_opt(start, *months)
+                );
+            });
+            [-32, -31, -29, -1, 0, 1, 15, 28, 29, 30, 31, 32]
+                .iter()
+                .for_each(|day| {
+                    assert_eq!(
+                        PlainGregorian.with_day_opt(start, *day),
+                        Gregorian.with_day_opt(start, *day)
+                    );
+                });
+            [-6, -5, -4, -1, 0, 1, 4, 5, 6].iter().for_each(|n| {
+                assert_eq!(
+                    PlainGregorian.with_weekday_of_month_opt(start, Weekday::Wed, *n),
+                    Gregorian.with_weekday_of_month_opt(start, Weekday::Wed, *n)
+                );
+            });
+            start = start.succ_opt().unwrap();
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Value parsers for using [`RelativeDuration`]s and [`DateRule`]s as `clap` arguments.
+//!
+//! With these, `RelativeDuration` and `DateRule<D>` fields work directly in `clap` derives:
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use chronoutil::{DateRule, RelativeDuration};
+//! use clap::{value_parser, Arg, Command};
+//!
+//! let command = Command::new("report")
+//!     .arg(Arg::new("interval").long("interval").value_parser(value_parser!(RelativeDuration)))
+//!     .arg(Arg::new("schedule").long("schedule").value_parser(value_parser!(DateRule<NaiveDate>)));
+//!
+//! let matches = command
+//!     .try_get_matches_from(["report", "--interval", "P1M", "--schedule", "R3/2025-01-31/P1M"])
+//!     .unwrap();
+//! assert_eq!(
+//!     matches.get_one::<RelativeDuration>("interval"),
+//!     Some(&RelativeDuration::months(1))
+//! );
+//! assert_eq!(matches.get_one::<DateRule<NaiveDate>>("schedule").unwrap().count(), 3);
+//! ```
+use std::ffi::OsStr;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use ::clap::builder::{TypedValueParser, ValueParserFactory};
+use ::clap::error::ErrorKind;
+use ::clap::{Arg, Command, Error};
+use chrono::Datelike;
+
+use crate::{DateRule, RelativeDuration};
+
+fn to_str<'a>(cmd: &Command, value: &'a OsStr) -> Result<&'a str, Error> {
+    value
+        .to_str()
+        .ok_or_else(|| cmd.clone().error(ErrorKind::InvalidUtf8, "invalid UTF-8"))
+}
+
+fn invalid_value(
+    cmd: &Command,
+    arg: Option<&Arg>,
+    value: &str,
+    reason: &str,
+    example: &str,
+) -> Error {
+    let arg = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+    cmd.clone().error(
+        ErrorKind::ValueValidation,
+        format!(
+            "invalid value '{}' for '{}': {}\n\n  tip: expected a value such as '{}'",
+            value, arg, reason, example
+        ),
+    )
+}
+
+/// Parses an ISO 8601 duration string such as `P1Y2M3DT4H` into a [`RelativeDuration`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RelativeDurationValueParser;
+
+impl TypedValueParser for RelativeDurationValueParser {
+    type Value = RelativeDuration;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = to_str(cmd, value)?;
+        RelativeDuration::parse_from_iso8601(value)
+            .map_err(|reason| invalid_value(cmd, arg, value, &reason, "P1Y2M3DT4H"))
+    }
+}
+
+impl ValueParserFactory for RelativeDuration {
+    type Parser = RelativeDurationValueParser;
+
+    fn value_parser() -> Self::Parser {
+        RelativeDurationValueParser
+    }
+}
+
+/// Parses an ISO 8601 repeating interval such as `R12/2025-01-31/P1M` into a [`DateRule`].
+///
+/// See [`DateRule::parse_from_iso8601`] for the accepted forms.
+#[derive(Clone, Copy, Debug)]
+pub struct DateRuleValueParser<D> {
+    _date: PhantomData<fn() -> D>,
+}
+
+impl<D> DateRuleValueParser<D> {
+    /// Creates a new `DateRuleValueParser`.
+    pub fn new() -> Self {
+        Self { _date: PhantomData }
+    }
+}
+
+impl<D> Default for DateRuleValueParser<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> TypedValueParser for DateRuleValueParser<D>
+where
+    D: Datelike + Copy + FromStr + Send + Sync + 'static,
+    D::Err: Display,
+{
+    type Value = DateRule<D>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = to_str(cmd, value)?;
+        DateRule::parse_from_iso8601(value)
+            .map_err(|reason| invalid_value(cmd, arg, value, &reason, "R12/2025-01-31/P1M"))
+    }
+}
+
+impl<D> ValueParserFactory for DateRule<D>
+where
+    D: Datelike + Copy + FromStr + Send + Sync + 'static,
+    D::Err: Display,
+{
+    type Parser = DateRuleValueParser<D>;
+
+    fn value_parser() -> Self::Parser {
+        DateRuleValueParser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::clap::value_parser;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("report")
+            .arg(
+                Arg::new("interval")
+                    .long("interval")
+                    .value_parser(value_parser!(RelativeDuration)),
+            )
+            .arg(
+                Arg::new("dates")
+                    .long("dates")
+                    .value_parser(value_parser!(DateRule<NaiveDate>)),
+            )
+            .arg(
+                Arg::new("times")
+                    .long("times")
+                    .value_parser(value_parser!(DateRule<NaiveDateTime>)),
+            )
+    }
+
+    #[test]
+    fn test_value_parsers() {
+        let matches = command()
+            .try_get_matches_from([
+                "report",
+                "--interval",
+                "P1Y2MT3H",
+                "--dates",
+                "R2/2025-01-31/P1M",
+                "--times",
+                "R/2025-01-31T09:30:00/PT1H",
+            ])
+            .unwrap();
+
+        assert_eq!(
+            matches.get_one::<RelativeDuration>("interval"),
+            Some(&(RelativeDuration::months(14) + RelativeDuration::hours(3)))
+        );
+        assert_eq!(
+            matches
+                .get_one::<DateRule<NaiveDate>>("dates")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![
+                NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+            ]
+        );
+        assert!(matches
+            .get_one::<DateRule<NaiveDateTime>>("times")
+            .is_some());
+    }
+
+    #[test]
+    fn test_value_parser_errors() {
+        [
+            (
+                ["report", "--interval", "1M"],
+                "duration was not prefixed with P",
+            ),
+            (
+                ["report", "--dates", "R2/2025-02-30/P1M"],
+                "is not a valid start",
+            ),
+        ]
+        .iter()
+        .for_each(|(args, reason)| {
+            let error = command().try_get_matches_from(args).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::ValueValidation);
+            let message = error.to_string();
+            assert!(
+                message.contains(&format!("invalid value '{}'", args[2])),
+                "{}",
+                message
+            );
+            assert!(message.contains(reason), "{}", message);
+            assert!(
+                message.contains("tip: expected a value such as"),
+                "{}",
+                message
+            );
+        });
+    }
+}
@@ -1,5 +1,15 @@
 //! Contains utility functions for shifting Date objects.
-use chrono::Datelike;
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Weekday,
+};
+
+use crate::boundary::days_in_month;
+use crate::calendar::{Calendar, Julian};
 
 /// Returns true if the year is a leap-year, as naively defined in the Gregorian calendar.
 #[inline]
@@ -7,11 +17,75 @@ pub fn is_leap_year(year: i32) -> bool {
     year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
 }
 
+/// The method used to compute the date of Easter Sunday by [`easter`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EasterMethod {
+    /// The original method, giving a date in the Julian calendar.
+    Julian,
+    /// The original method, converted to a date in the Gregorian calendar, as used by the
+    /// Orthodox churches.
+    Orthodox,
+    /// The revised method, giving a date in the Gregorian calendar, as used by the Western
+    /// churches.
+    Western,
+}
+
+/// Returns the date of Easter Sunday in the given year.
+///
+/// The [`EasterMethod::Julian`] date is a Julian calendar date, whose year, month and day are
+/// returned as a `NaiveDate` (so it will not generally fall on a Sunday). The other methods
+/// give a proleptic Gregorian date. Returns None if the date is out of range.
+///
+/// Other moveable feasts are fixed offsets from Easter Sunday:
+/// ```rust
+/// # use chrono::{Duration, NaiveDate};
+/// # use chronoutil::delta::{easter, EasterMethod};
+/// let easter_sunday = easter(2024, EasterMethod::Western).unwrap();
+/// assert_eq!(easter_sunday, NaiveDate::from_ymd_opt(2024, 3, 31).unwrap());
+///
+/// let good_friday = easter_sunday - Duration::days(2);
+/// let easter_monday = easter_sunday + Duration::days(1);
+/// let ascension = easter_sunday + Duration::days(39);
+/// let whit_monday = easter_sunday + Duration::days(50);
+/// let corpus_christi = easter_sunday + Duration::days(60);
+/// assert_eq!(corpus_christi, NaiveDate::from_ymd_opt(2024, 5, 30).unwrap());
+///
+/// assert_eq!(easter(2024, EasterMethod::Orthodox), NaiveDate::from_ymd_opt(2024, 5, 5));
+/// ```
+pub fn easter(year: i32, method: EasterMethod) -> Option<NaiveDate> {
+    let y = i64::from(year);
+    let (month, day) = match method {
+        EasterMethod::Julian | EasterMethod::Orthodox => {
+            // Meeus's Julian algorithm
+            let d = (19 * y.rem_euclid(19) + 15) % 30;
+            let e = (2 * y.rem_euclid(4) + 4 * y.rem_euclid(7) - d + 34).rem_euclid(7);
+            ((d + e + 114) / 31, (d + e + 114) % 31 + 1)
+        }
+        EasterMethod::Western => {
+            // The anonymous Gregorian algorithm, with floored division for negative years
+            let a = y.rem_euclid(19);
+            let (b, c) = (y.div_euclid(100), y.rem_euclid(100));
+            let (d, e) = (b.div_euclid(4), b.rem_euclid(4));
+            let f = (b + 8).div_euclid(25);
+            let g = (b - f + 1).div_euclid(3);
+            let h = (19 * a + b - d - g + 15).rem_euclid(30);
+            let l = (32 + 2 * e + 2 * (c / 4) - h - c % 4).rem_euclid(7);
+            let m = (a + 11 * h + 22 * l) / 451;
+            ((h + l - 7 * m + 114) / 31, (h + l - 7 * m + 114) % 31 + 1)
+        }
+    };
+
+    match method {
+        EasterMethod::Orthodox => Julian.date_from_calendar(year, month as u32, day as u32),
+        _ => NaiveDate::from_ymd_opt(year, month as u32, day as u32),
+    }
+}
+
 // If the day lies within the month, this function has no effect. Otherwise, it shifts
 // day backwards to the final day of the month.
 // XXX: No attempt is made to handle days outside the 1-31 range.
 #[inline]
-fn normalise_day(year: i32, month: u32, day: u32) -> u32 {
+pub(crate) fn normalise_day(year: i32, month: u32, day: u32) -> u32 {
     if day <= 28 {
         day
     } else if month == 2 {
@@ -72,23 +146,81 @@ pub fn shift_years_opt<D: Datelike>(date: D, years: i32) -> Option<D> {
     shift_months_opt(date, years * 12)
 }
 
-/// Shift the date to have the given day.  Returns None if the day is not in the range 1-31.
+/// An error from shifting to a day of the month.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DayOutOfRange {
+    /// The day index was outside the ranges 1 to 31 and -31 to -1.
+    Index(i32),
+    /// The day exists, but the date/time on it is ambiguous or does not exist (e.g. in a DST
+    /// transition).
+    Unresolvable(i32),
+}
+
+impl DayOutOfRange {
+    /// The day index which could not be shifted to.
+    pub fn day(&self) -> i32 {
+        match self {
+            DayOutOfRange::Index(day) | DayOutOfRange::Unresolvable(day) => *day,
+        }
+    }
+}
+
+impl fmt::Display for DayOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayOutOfRange::Index(day) => write!(f, "Day {} not in range 1-31 or -31 to -1", day),
+            DayOutOfRange::Unresolvable(day) => {
+                write!(f, "Day {} is ambiguous or does not exist at this time", day)
+            }
+        }
+    }
+}
+
+impl Error for DayOutOfRange {}
+
+// Resolves a day index to a day of the given month. Positive days past the end of the month
+// are shifted backwards to the final day, and negative days count back from the final day,
+// stopping at the first.
+pub(crate) fn resolve_day(year: i32, month: u32, day: i32) -> Result<u32, DayOutOfRange> {
+    match day {
+        1..=31 => Ok(normalise_day(year, month, day as u32)),
+        -31..=-1 => Ok((days_in_month(year, month) as i32 + 1 + day).max(1) as u32),
+        _ => Err(DayOutOfRange::Index(day)),
+    }
+}
+
+/// Shift the date to have the given day. Returns Err if the day is not in the range 1-31
+/// or -31 to -1, or if the result is an ambiguous or non-existing date/time (e.g. in a DST
+/// transition).
 ///
-/// Ambiguous month-ends are shifted backwards as necessary.
+/// Negative days count back from the end of the month, so -1 is the last day and -2 the
+/// penultimate day. Ambiguous month-ends are shifted backwards as necessary, and negative
+/// days before the start of the month are shifted forwards to the first day.
 /// For example:
 /// ```rust
 /// # use chrono::NaiveDate;
 /// # use chronoutil::with_day;
+/// # use chronoutil::delta::DayOutOfRange;
 /// let start = NaiveDate::from_ymd_opt(2020, 2, 1).unwrap();
-/// assert_eq!(with_day(start, 31), NaiveDate::from_ymd_opt(2020, 2, 29));
-/// assert_eq!(with_day(start, 42), None);
+/// assert_eq!(with_day(start, 31), Ok(NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()));
+/// assert_eq!(with_day(start, -1), Ok(NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()));
+/// assert_eq!(with_day(start, -3), Ok(NaiveDate::from_ymd_opt(2020, 2, 27).unwrap()));
+/// assert_eq!(with_day(start, 42), Err(DayOutOfRange::Index(42)));
 /// ```
-pub fn with_day<D: Datelike>(date: D, day: u32) -> Option<D> {
-    if day == 0 || day > 31 {
-        None
-    } else {
-        date.with_day(normalise_day(date.year(), date.month(), day))
-    }
+pub fn with_day<D: Datelike>(date: D, day: i32) -> Result<D, DayOutOfRange> {
+    let resolved = resolve_day(date.year(), date.month(), day)?;
+    date.with_day(resolved)
+        .ok_or(DayOutOfRange::Unresolvable(day))
+}
+
+/// Similar to [`with_day`] except fallible on unresolvable dates/times.
+///
+/// Returns `None` rather than an error when the day is out of range, and rather than
+/// panicking when the result is an ambiguous or non-existing date/time (e.g. in a DST
+/// transition).
+pub fn with_day_opt<D: Datelike>(date: D, day: i32) -> Option<D> {
+    let day = resolve_day(date.year(), date.month(), day).ok()?;
+    date.with_day(day)
 }
 
 /// Shift the date to have the given month. Returns None if the month is out of range.
@@ -148,6 +280,437 @@ pub fn with_year_opt<D: Datelike>(date: D, year: i32) -> Option<D> {
     shift_years_opt(date, delta)
 }
 
+/// Returns the `n`th given weekday of a month, e.g. the 3rd Wednesday.
+///
+/// Negative values of `n` count back from the end of the month, so `-1` gives the last such
+/// weekday. Returns None if `n` is zero, the month does not have that many of the weekday,
+/// or the month is out of range.
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, Weekday};
+/// # use chronoutil::nth_weekday_of_month;
+/// assert_eq!(
+///     nth_weekday_of_month(2024, 3, Weekday::Wed, 3),
+///     NaiveDate::from_ymd_opt(2024, 3, 20)
+/// );
+/// assert_eq!(
+///     nth_weekday_of_month(2024, 3, Weekday::Fri, -1),
+///     NaiveDate::from_ymd_opt(2024, 3, 29)
+/// );
+/// assert_eq!(nth_weekday_of_month(2024, 3, Weekday::Mon, 5), None);
+/// ```
+pub fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, n: i32) -> Option<NaiveDate> {
+    let day = nth_weekday_day(year, month, weekday, n)?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+// Returns the day of the month of the nth weekday, if there is one
+fn nth_weekday_day(year: i32, month: u32, weekday: Weekday, n: i32) -> Option<u32> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?.weekday();
+    let days = days_in_month(year, month);
+    let first_day = 1 + (7 + weekday.num_days_from_monday() - first.num_days_from_monday()) % 7;
+    let count = (days - first_day) / 7 + 1;
+
+    let index = match n {
+        0 => return None,
+        n if n > 0 => n.unsigned_abs() - 1,
+        n => count.checked_sub(n.unsigned_abs())?,
+    };
+    if index < count {
+        Some(first_day + 7 * index)
+    } else {
+        None
+    }
+}
+
+/// Shift the date to the `n`th given weekday of its month, keeping any time of day.
+///
+/// Negative values of `n` count back from the end of the month. Returns None if there is no
+/// such weekday, or if the result is an ambiguous or non-existing date/time (e.g. in a DST
+/// transition).
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, Weekday};
+/// # use chronoutil::delta::with_nth_weekday;
+/// let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(16, 0, 0).unwrap();
+/// assert_eq!(
+///     with_nth_weekday(start, Weekday::Fri, -1),
+///     NaiveDate::from_ymd_opt(2024, 3, 29).unwrap().and_hms_opt(16, 0, 0)
+/// );
+/// ```
+pub fn with_nth_weekday<D: Datelike>(date: D, weekday: Weekday, n: i32) -> Option<D> {
+    let day = nth_weekday_day(date.year(), date.month(), weekday, n)?;
+    date.with_day(day)
+}
+
+/// Returns which occurrence of its weekday within the month the date is, counting from 1.
+///
+/// For example, the 2nd Tuesday of a month gives `(2, Weekday::Tue)`:
+/// ```rust
+/// # use chrono::{NaiveDate, Weekday};
+/// # use chronoutil::weekday_ordinal_in_month;
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+/// assert_eq!(weekday_ordinal_in_month(date), (2, Weekday::Tue));
+/// ```
+///
+/// Together with [`nth_weekday_of_month`], this gives the same date in another month:
+/// ```rust
+/// # use chrono::{Datelike, NaiveDate};
+/// # use chronoutil::{nth_weekday_of_month, weekday_ordinal_in_month};
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+/// let (n, weekday) = weekday_ordinal_in_month(date);
+/// assert_eq!(
+///     nth_weekday_of_month(2024, 4, weekday, n),
+///     NaiveDate::from_ymd_opt(2024, 4, 9)
+/// );
+/// ```
+pub fn weekday_ordinal_in_month<D: Datelike>(date: D) -> (i32, Weekday) {
+    (((date.day() - 1) / 7 + 1) as i32, date.weekday())
+}
+
+/// An error from a weekday ordinal outside the ranges 1 to 5 and -5 to -1.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct WeekdayOrdinalOutOfRange {
+    n: i32,
+}
+
+impl WeekdayOrdinalOutOfRange {
+    /// The ordinal which was out of range.
+    pub fn ordinal(&self) -> i32 {
+        self.n
+    }
+}
+
+impl fmt::Display for WeekdayOrdinalOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Weekday ordinal {} not in range 1-5 or -5 to -1", self.n)
+    }
+}
+
+impl Error for WeekdayOrdinalOutOfRange {}
+
+// Resolves the nth weekday of a month to a day of the month. A 5th (or -5th) weekday which
+// does not exist is shifted back to the last (or forwards to the first) such weekday.
+pub(crate) fn resolve_weekday(
+    year: i32,
+    month: u32,
+    weekday: Weekday,
+    n: i32,
+) -> Result<Option<u32>, WeekdayOrdinalOutOfRange> {
+    match n {
+        1..=4 | -4..=-1 => Ok(nth_weekday_day(year, month, weekday, n)),
+        5 | -5 => Ok(nth_weekday_day(year, month, weekday, n)
+            .or_else(|| nth_weekday_day(year, month, weekday, n.signum() * 4))),
+        _ => Err(WeekdayOrdinalOutOfRange { n }),
+    }
+}
+
+/// Shift a date by the given number of months, keeping its position among the same weekdays
+/// of the month, so the 3rd Tuesday of March becomes the 3rd Tuesday of April.
+///
+/// The 5th occurrence of a weekday is always the last in its month, and becomes the last
+/// occurrence in the target month: the 4th if there is no 5th. A 4th occurrence which happens
+/// to be the last stays the 4th. To always take the last occurrence, use a `DateRule` with
+/// [`DateRule::with_rolling_weekday`](crate::DateRule::with_rolling_weekday) and an ordinal
+/// of -1.
+/// For example:
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::delta::shift_months_by_weekday;
+/// // The 3rd Tuesday of March 2024
+/// let start = NaiveDate::from_ymd_opt(2024, 3, 19).unwrap();
+/// assert_eq!(shift_months_by_weekday(start, 1), NaiveDate::from_ymd_opt(2024, 4, 16).unwrap());
+///
+/// // The 5th Friday of March 2024, and April has only four
+/// let start = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+/// assert_eq!(shift_months_by_weekday(start, 1), NaiveDate::from_ymd_opt(2024, 4, 26).unwrap());
+/// ```
+pub fn shift_months_by_weekday<D: Datelike>(date: D, months: i32) -> D {
+    shift_months_by_weekday_opt(date, months).unwrap()
+}
+
+/// Same as [`shift_months_by_weekday`] except fallible on unresolvable dates/times.
+///
+/// Returns `None` rather than panicking when shift results in an ambiguous or non-existing
+/// date/time (e.g. in a DST transition).
+pub fn shift_months_by_weekday_opt<D: Datelike>(date: D, months: i32) -> Option<D> {
+    let n = ((date.day() - 1) / 7 + 1) as i32;
+    let weekday = date.weekday();
+    let first = shift_months_opt(
+        NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?,
+        months,
+    )?;
+    let day = resolve_weekday(first.year(), first.month(), weekday, n).ok()??;
+    with_date(date, first.with_day(day)?)
+}
+
+/// Returns the number of ISO 8601 weeks, 52 or 53, in the given ISO week-year.
+///
+/// A year has 53 weeks when it starts on a Thursday, or is a leap-year starting on a
+/// Wednesday.
+/// For example:
+/// ```rust
+/// # use chronoutil::delta::iso_weeks_in_year;
+/// assert_eq!(iso_weeks_in_year(2020), 53);
+/// assert_eq!(iso_weeks_in_year(2024), 52);
+/// ```
+pub fn iso_weeks_in_year(year: i32) -> u32 {
+    match NaiveDate::from_ymd_opt(year, 1, 1).map(|date| date.weekday()) {
+        Some(Weekday::Thu) => 53,
+        Some(Weekday::Wed) if is_leap_year(year) => 53,
+        _ => 52,
+    }
+}
+
+/// Shift a date by the given number of ISO weeks, keeping its weekday and any time of day.
+///
+/// Unlike adding a `Duration`, the local time of day is kept across daylight saving
+/// transitions. Panics if the result is out of range or an ambiguous or non-existing
+/// date/time.
+/// For example:
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::delta::shift_iso_weeks;
+/// // 2020-W53-4 to 2021-W01-4
+/// let start = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+/// assert_eq!(shift_iso_weeks(start, 1), NaiveDate::from_ymd_opt(2021, 1, 7).unwrap());
+/// ```
+pub fn shift_iso_weeks<D: Datelike>(date: D, weeks: i32) -> D {
+    shift_iso_weeks_opt(date, weeks).unwrap()
+}
+
+/// Same as [`shift_iso_weeks`] except fallible on unresolvable dates/times.
+///
+/// Returns `None` rather than panicking when the result is out of range, or an ambiguous or
+/// non-existing date/time (e.g. in a DST transition).
+pub fn shift_iso_weeks_opt<D: Datelike>(date: D, weeks: i32) -> Option<D> {
+    shift_days(date, i64::from(weeks) * 7)
+}
+
+/// Shift the date to the given ISO week of its ISO week-year, keeping its weekday and any
+/// time of day.
+///
+/// Week 53 is shifted back to week 52 in years with only 52 weeks. Returns None if the week
+/// is not in the range 1-53, or the result is an ambiguous or non-existing date/time.
+/// For example:
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::delta::with_iso_week;
+/// // 2024-W01-1 is 2024-01-01
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// assert_eq!(with_iso_week(start, 10), NaiveDate::from_ymd_opt(2024, 3, 4));
+/// assert_eq!(with_iso_week(start, 53), NaiveDate::from_ymd_opt(2024, 12, 23));
+/// ```
+pub fn with_iso_week<D: Datelike>(date: D, week: u32) -> Option<D> {
+    if week == 0 || week > 53 {
+        return None;
+    }
+    let current = date.iso_week();
+    let week = week.min(iso_weeks_in_year(current.year()));
+    shift_iso_weeks_opt(date, week as i32 - current.week() as i32)
+}
+
+// Moves the date onto the given date, keeping any time of day
+pub(crate) fn with_date<D: Datelike>(date: D, target: NaiveDate) -> Option<D> {
+    date.with_ordinal(1)?
+        .with_year(target.year())?
+        .with_ordinal(target.ordinal())
+}
+
+// Moves the date by a number of days, keeping any time of day
+fn shift_days<D: Datelike>(date: D, days: i64) -> Option<D> {
+    let days: i32 = days.try_into().ok()?;
+    let target = NaiveDate::from_num_days_from_ce_opt(date.num_days_from_ce().checked_add(days)?)?;
+    with_date(date, target)
+}
+
+/// Shift the date forward to the next given weekday, keeping any time of day.
+///
+/// If the date already falls on that weekday it is returned unchanged when `inclusive` is
+/// true, and moved on a week otherwise. Returns None if the result is an ambiguous or
+/// non-existing date/time (e.g. in a DST transition).
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, Weekday};
+/// # use chronoutil::delta::next_weekday;
+/// let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+/// assert_eq!(next_weekday(monday, Weekday::Fri, false), NaiveDate::from_ymd_opt(2024, 3, 8));
+/// assert_eq!(next_weekday(monday, Weekday::Mon, true), Some(monday));
+/// assert_eq!(next_weekday(monday, Weekday::Mon, false), NaiveDate::from_ymd_opt(2024, 3, 11));
+/// ```
+pub fn next_weekday<D: Datelike>(date: D, weekday: Weekday, inclusive: bool) -> Option<D> {
+    let ahead = (7 + weekday.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+    let days = if ahead == 0 && !inclusive { 7 } else { ahead };
+    shift_days(date, days.into())
+}
+
+/// Shift the date back to the previous given weekday, keeping any time of day.
+///
+/// If the date already falls on that weekday it is returned unchanged when `inclusive` is
+/// true, and moved back a week otherwise. Returns None if the result is an ambiguous or
+/// non-existing date/time (e.g. in a DST transition).
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, Weekday};
+/// # use chronoutil::delta::previous_weekday;
+/// let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+/// assert_eq!(previous_weekday(monday, Weekday::Fri, false), NaiveDate::from_ymd_opt(2024, 3, 1));
+/// assert_eq!(previous_weekday(monday, Weekday::Mon, false), NaiveDate::from_ymd_opt(2024, 2, 26));
+/// ```
+pub fn previous_weekday<D: Datelike>(date: D, weekday: Weekday, inclusive: bool) -> Option<D> {
+    let behind = (7 + date.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    let days = if behind == 0 && !inclusive { 7 } else { behind };
+    shift_days(date, -i64::from(days))
+}
+
+/// Shift the date to the nearest given weekday, keeping any time of day.
+///
+/// The nearest weekday is at most three days away, unless the date already falls on that
+/// weekday: it is then returned unchanged when `inclusive` is true, and moved on a week
+/// otherwise. Returns None if the result is an ambiguous or non-existing date/time (e.g. in
+/// a DST transition).
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, Weekday};
+/// # use chronoutil::delta::nearest_weekday;
+/// let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+/// assert_eq!(nearest_weekday(monday, Weekday::Thu, true), NaiveDate::from_ymd_opt(2024, 3, 7));
+/// assert_eq!(nearest_weekday(monday, Weekday::Fri, true), NaiveDate::from_ymd_opt(2024, 3, 1));
+/// ```
+pub fn nearest_weekday<D: Datelike>(date: D, weekday: Weekday, inclusive: bool) -> Option<D> {
+    let ahead = (7 + weekday.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+    let days = match ahead {
+        0 if inclusive => 0,
+        0 => 7,
+        1..=3 => i64::from(ahead),
+        _ => i64::from(ahead) - 7,
+    };
+    shift_days(date, days)
+}
+
+/// A datetime with a local date and time of day, as used by [`next_time_of_day`] and
+/// [`previous_time_of_day`].
+pub trait LocalDateTime: Datelike + Timelike + PartialOrd + Sized {
+    /// Returns the local date and time.
+    fn local(&self) -> NaiveDateTime;
+
+    /// Returns the value with the given local date and time, in the time zone of `self`.
+    ///
+    /// Local times which are skipped by a daylight saving transition are moved forward by
+    /// the length of the gap.
+    fn with_local(&self, local: NaiveDateTime) -> LocalResult<Self>;
+}
+
+impl LocalDateTime for NaiveDateTime {
+    fn local(&self) -> NaiveDateTime {
+        *self
+    }
+
+    fn with_local(&self, local: NaiveDateTime) -> LocalResult<Self> {
+        LocalResult::Single(local)
+    }
+}
+
+impl<Tz: TimeZone> LocalDateTime for DateTime<Tz> {
+    fn local(&self) -> NaiveDateTime {
+        self.naive_local()
+    }
+
+    fn with_local(&self, local: NaiveDateTime) -> LocalResult<Self> {
+        let timezone = self.timezone();
+        match timezone.from_local_datetime(&local) {
+            LocalResult::None => {
+                // Read the local time with the offset in force the day before the gap
+                let before = local
+                    .checked_sub_signed(Duration::days(1))
+                    .and_then(|before| timezone.from_local_datetime(&before).earliest());
+                before
+                    .and_then(|before| {
+                        let offset =
+                            Duration::seconds(before.offset().fix().local_minus_utc().into());
+                        local.checked_sub_signed(offset)
+                    })
+                    .map_or(LocalResult::None, |utc| {
+                        LocalResult::Single(timezone.from_utc_datetime(&utc))
+                    })
+            }
+            result => result,
+        }
+    }
+}
+
+// Returns the values at the given time on the local date shifted by `days`, in order
+fn times_of_day<D: LocalDateTime>(datetime: &D, time: NaiveTime, days: i64) -> Vec<D> {
+    let date = datetime
+        .local()
+        .date()
+        .checked_add_signed(Duration::days(days));
+    match date.map(|date| datetime.with_local(date.and_time(time))) {
+        Some(LocalResult::Single(value)) => vec![value],
+        Some(LocalResult::Ambiguous(earliest, latest)) => vec![earliest, latest],
+        _ => vec![],
+    }
+}
+
+/// Returns the first time after the datetime at which the clock shows the given time of day.
+///
+/// When `inclusive` is true, a datetime already at that time is returned unchanged.
+/// When the time is skipped by a daylight saving transition, the result is moved forward by
+/// the length of the gap, and when it is repeated each occurrence is considered in turn.
+/// Returns None if the result is out of range.
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, NaiveTime};
+/// # use chronoutil::delta::next_time_of_day;
+/// let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+/// let now = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(17, 30, 0).unwrap();
+/// assert_eq!(
+///     next_time_of_day(now, nine, false),
+///     NaiveDate::from_ymd_opt(2024, 3, 5).unwrap().and_hms_opt(9, 0, 0)
+/// );
+/// ```
+pub fn next_time_of_day<D: LocalDateTime>(
+    datetime: D,
+    time: NaiveTime,
+    inclusive: bool,
+) -> Option<D> {
+    (0..=2).find_map(|days| {
+        times_of_day(&datetime, time, days)
+            .into_iter()
+            .find(|value| *value > datetime || (inclusive && *value == datetime))
+    })
+}
+
+/// Returns the last time before the datetime at which the clock showed the given time of day.
+///
+/// When `inclusive` is true, a datetime already at that time is returned unchanged.
+/// When the time is skipped by a daylight saving transition, the result is moved forward by
+/// the length of the gap, and when it is repeated each occurrence is considered in turn.
+/// Returns None if the result is out of range.
+/// For example:
+/// ```rust
+/// # use chrono::{NaiveDate, NaiveTime};
+/// # use chronoutil::delta::previous_time_of_day;
+/// let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+/// let now = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(8, 30, 0).unwrap();
+/// assert_eq!(
+///     previous_time_of_day(now, nine, false),
+///     NaiveDate::from_ymd_opt(2024, 3, 3).unwrap().and_hms_opt(9, 0, 0)
+/// );
+/// ```
+pub fn previous_time_of_day<D: LocalDateTime>(
+    datetime: D,
+    time: NaiveTime,
+    inclusive: bool,
+) -> Option<D> {
+    (0..=2).find_map(|days| {
+        times_of_day(&datetime, time, -days)
+            .into_iter()
+            .rev()
+            .find(|value| *value < datetime || (inclusive && *value == datetime))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -601,4 +1164,494 @@ mod tests {
             NaiveDate::from_ymd_opt(2016, 2, 29).unwrap()
         );
     }
+
+    #[test]
+    fn test_nth_weekday_of_month() {
+        // March 2024 starts on a Friday and has 31 days
+        [
+            (Weekday::Fri, 1, Some(1)),
+            (Weekday::Fri, 5, Some(29)),
+            (Weekday::Fri, 6, None),
+            (Weekday::Fri, -1, Some(29)),
+            (Weekday::Fri, -5, Some(1)),
+            (Weekday::Fri, -6, None),
+            (Weekday::Wed, 3, Some(20)),
+            (Weekday::Thu, 1, Some(7)),
+            (Weekday::Sun, 5, Some(31)),
+            (Weekday::Mon, 5, None),
+            (Weekday::Mon, -1, Some(25)),
+            (Weekday::Mon, 0, None),
+            (Weekday::Mon, i32::MIN, None),
+            (Weekday::Mon, i32::MAX, None),
+        ]
+        .iter()
+        .for_each(|(weekday, n, day)| {
+            assert_eq!(
+                nth_weekday_of_month(2024, 3, *weekday, *n),
+                day.map(|day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap()),
+                "{} {}",
+                n,
+                weekday
+            );
+        });
+
+        // February of a non-leap year starting on a Monday has exactly four of each weekday
+        assert_eq!(
+            nth_weekday_of_month(2021, 2, Weekday::Sun, -1),
+            NaiveDate::from_ymd_opt(2021, 2, 28)
+        );
+        assert_eq!(nth_weekday_of_month(2021, 2, Weekday::Mon, 5), None);
+        assert_eq!(nth_weekday_of_month(2021, 13, Weekday::Mon, 1), None);
+    }
+
+    #[test]
+    fn test_weekday_ordinal_in_month() {
+        (1..=31).for_each(|day| {
+            let date = NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+            let (n, weekday) = weekday_ordinal_in_month(date);
+            assert_eq!(weekday, date.weekday());
+            assert_eq!(nth_weekday_of_month(2024, 3, weekday, n), Some(date));
+        });
+    }
+
+    #[test]
+    fn test_with_nth_weekday() {
+        let base = NaiveDate::from_ymd_opt(2024, 3, 14)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        assert_eq!(
+            with_nth_weekday(base, Weekday::Wed, 3),
+            NaiveDate::from_ymd_opt(2024, 3, 20)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+        );
+
+        let tz = &chrono_tz::Australia::Melbourne;
+        let base = tz.from_local_datetime(&base).unwrap();
+        assert_eq!(
+            with_nth_weekday(base, Weekday::Fri, -1),
+            tz.with_ymd_and_hms(2024, 3, 29, 9, 30, 0).single()
+        );
+        assert_eq!(with_nth_weekday(base, Weekday::Mon, 5), None);
+    }
+
+    #[test]
+    fn test_next_previous_nearest_weekday() {
+        // 2024-02-28 is a Wednesday, and the next day is a leap day
+        let base = NaiveDate::from_ymd_opt(2024, 2, 28).unwrap();
+        [
+            (Weekday::Wed, true, (28, 28, 28)),
+            (Weekday::Wed, false, (35, 21, 35)),
+            (Weekday::Thu, false, (29, 22, 29)),
+            (Weekday::Fri, true, (30, 23, 30)),
+            (Weekday::Sat, true, (31, 24, 31)),
+            (Weekday::Sun, true, (32, 25, 25)),
+            (Weekday::Mon, true, (33, 26, 26)),
+            (Weekday::Tue, false, (34, 27, 27)),
+        ]
+        .iter()
+        .for_each(|(weekday, inclusive, (next, previous, nearest))| {
+            // Days are counted from 2024-02-01
+            let day = |n: i64| base.with_day(1).unwrap() + Duration::days(n - 1);
+            assert_eq!(next_weekday(base, *weekday, *inclusive), Some(day(*next)));
+            assert_eq!(
+                previous_weekday(base, *weekday, *inclusive),
+                Some(day(*previous))
+            );
+            assert_eq!(
+                nearest_weekday(base, *weekday, *inclusive),
+                Some(day(*nearest))
+            );
+        });
+
+        assert_eq!(
+            next_weekday(
+                NaiveDate::from_ymd_opt(2024, 12, 30).unwrap(),
+                Weekday::Fri,
+                false
+            ),
+            NaiveDate::from_ymd_opt(2025, 1, 3)
+        );
+        assert_eq!(
+            previous_weekday(
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                Weekday::Sat,
+                false
+            ),
+            NaiveDate::from_ymd_opt(2024, 12, 28)
+        );
+        assert_eq!(next_weekday(NaiveDate::MAX, Weekday::Mon, false), None);
+    }
+
+    #[test]
+    fn test_next_weekday_keeps_time() {
+        let tz = &chrono_tz::Australia::Melbourne;
+        let base = tz.with_ymd_and_hms(2024, 3, 28, 10, 15, 0).unwrap();
+        assert_eq!(
+            next_weekday(base, Weekday::Mon, false),
+            tz.with_ymd_and_hms(2024, 4, 1, 10, 15, 0).single()
+        );
+        // Clocks go back from 03:00 to 02:00 on 2024-04-07 in Melbourne
+        let base = tz.with_ymd_and_hms(2024, 4, 1, 2, 30, 0).unwrap();
+        assert_eq!(next_weekday(base, Weekday::Sun, false), None);
+    }
+
+    #[test]
+    fn test_iso_weeks() {
+        let years = [
+            (2015, 53),
+            (2016, 52),
+            (2020, 53),
+            (2021, 52),
+            (2024, 52),
+            (2026, 53),
+            (2032, 53),
+            (2037, 53),
+        ];
+        years.iter().for_each(|(year, weeks)| {
+            assert_eq!(iso_weeks_in_year(*year), *weeks, "{}", year);
+            let last = NaiveDate::from_isoywd_opt(*year, *weeks, Weekday::Mon);
+            assert!(last.is_some());
+            assert_eq!(
+                NaiveDate::from_isoywd_opt(*year, weeks + 1, Weekday::Mon),
+                None
+            );
+        });
+        (1900..2100).for_each(|year| {
+            let weeks = NaiveDate::from_ymd_opt(year, 12, 28)
+                .unwrap()
+                .iso_week()
+                .week();
+            assert_eq!(iso_weeks_in_year(year), weeks, "{}", year);
+        });
+
+        let base = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+        let shifts = [
+            (0, (2020, 12, 31)),
+            (1, (2021, 1, 7)),
+            (-53, (2019, 12, 26)),
+            (52, (2021, 12, 30)),
+        ];
+        shifts.iter().for_each(|(weeks, (y, m, d))| {
+            assert_eq!(
+                shift_iso_weeks(base, *weeks),
+                NaiveDate::from_ymd_opt(*y, *m, *d).unwrap()
+            );
+        });
+
+        // 2020-W53-4
+        let cases = [
+            (1, Some((2020, 1, 2))),
+            (10, Some((2020, 3, 5))),
+            (53, Some((2020, 12, 31))),
+            (0, None),
+            (54, None),
+        ];
+        cases.iter().for_each(|(week, expected)| {
+            let expected = expected.map(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap());
+            assert_eq!(with_iso_week(base, *week), expected);
+        });
+        // 2021-W01-5 is 2021-01-08, and 2021 has only 52 weeks
+        let base = NaiveDate::from_ymd_opt(2021, 1, 8).unwrap();
+        assert_eq!(
+            with_iso_week(base, 53),
+            NaiveDate::from_ymd_opt(2021, 12, 31)
+        );
+        // ISO week-year 2021 starts in 2021, but 2020-W53 runs into January
+        let base = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        assert_eq!(with_iso_week(base, 1), NaiveDate::from_ymd_opt(2020, 1, 3));
+    }
+
+    #[test]
+    fn test_shift_iso_weeks_keeps_time() {
+        let tz = &chrono_tz::Europe::London;
+        // Clocks go forward on 2024-03-31 in London
+        let base = tz.with_ymd_and_hms(2024, 3, 25, 9, 0, 0).unwrap();
+        assert_eq!(
+            shift_iso_weeks(base, 1),
+            tz.with_ymd_and_hms(2024, 4, 1, 9, 0, 0).unwrap()
+        );
+        assert_eq!(
+            with_iso_week(base, 14),
+            tz.with_ymd_and_hms(2024, 4, 1, 9, 0, 0).single()
+        );
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let at = |day: u32, hour: u32, minute: u32| {
+            NaiveDate::from_ymd_opt(2024, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        [
+            (at(4, 8, 30), true, at(4, 9, 0), at(3, 9, 0)),
+            (at(4, 9, 0), true, at(4, 9, 0), at(4, 9, 0)),
+            (at(4, 9, 0), false, at(5, 9, 0), at(3, 9, 0)),
+            (at(4, 17, 0), false, at(5, 9, 0), at(4, 9, 0)),
+            (
+                at(31, 23, 59),
+                false,
+                NaiveDate::from_ymd_opt(2024, 4, 1)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+                at(31, 9, 0),
+            ),
+        ]
+        .iter()
+        .for_each(|(datetime, inclusive, next, previous)| {
+            assert_eq!(next_time_of_day(*datetime, nine, *inclusive), Some(*next));
+            assert_eq!(
+                previous_time_of_day(*datetime, nine, *inclusive),
+                Some(*previous)
+            );
+        });
+    }
+
+    #[test]
+    fn test_time_of_day_dst() {
+        let tz = &chrono_tz::Europe::London;
+        let half_one = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
+
+        // 01:30 does not exist on 2024-03-31, so move forward to 02:30 BST
+        let base = tz.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap();
+        let next = next_time_of_day(base, half_one, false).unwrap();
+        assert_eq!(
+            next.naive_local(),
+            NaiveDate::from_ymd_opt(2024, 3, 31)
+                .unwrap()
+                .and_hms_opt(2, 30, 0)
+                .unwrap()
+        );
+        assert_eq!(next - base, Duration::hours(13) + Duration::minutes(30));
+        assert_eq!(
+            previous_time_of_day(
+                tz.with_ymd_and_hms(2024, 3, 31, 12, 0, 0).unwrap(),
+                half_one,
+                false
+            ),
+            Some(next)
+        );
+
+        // 01:30 happens twice on 2024-10-27, first in BST and then in GMT
+        let first = tz.with_ymd_and_hms(2024, 10, 27, 0, 30, 0).unwrap() + Duration::hours(1);
+        let second = first + Duration::hours(1);
+        let base = tz.with_ymd_and_hms(2024, 10, 26, 12, 0, 0).unwrap();
+        assert_eq!(next_time_of_day(base, half_one, false), Some(first));
+        assert_eq!(next_time_of_day(first, half_one, false), Some(second));
+        assert_eq!(next_time_of_day(first, half_one, true), Some(first));
+        assert_eq!(previous_time_of_day(second, half_one, false), Some(first));
+        let base = tz.with_ymd_and_hms(2024, 10, 27, 12, 0, 0).unwrap();
+        assert_eq!(previous_time_of_day(base, half_one, false), Some(second));
+    }
+
+    #[test]
+    fn test_with_day() {
+        let base = NaiveDate::from_ymd_opt(2021, 4, 10).unwrap();
+        [
+            (1, Some(1)),
+            (30, Some(30)),
+            (31, Some(30)),
+            (-1, Some(30)),
+            (-2, Some(29)),
+            (-30, Some(1)),
+            (-31, Some(1)),
+            (0, None),
+            (32, None),
+            (-32, None),
+            (i32::MIN, None),
+        ]
+        .iter()
+        .for_each(|(day, expected)| {
+            let expected = expected.map(|day| NaiveDate::from_ymd_opt(2021, 4, day).unwrap());
+            assert_eq!(with_day_opt(base, *day), expected);
+            match expected {
+                Some(expected) => assert_eq!(with_day(base, *day), Ok(expected)),
+                None => assert_eq!(with_day(base, *day).unwrap_err().day(), *day),
+            }
+        });
+
+        [(2021, 28), (2024, 29)].iter().for_each(|(year, last)| {
+            let base = NaiveDate::from_ymd_opt(*year, 2, 10).unwrap();
+            assert_eq!(with_day(base, -1).unwrap().day(), *last);
+            assert_eq!(with_day(base, -3).unwrap().day(), *last - 2);
+            assert_eq!(with_day(base, 31).unwrap().day(), *last);
+        });
+
+        assert_eq!(
+            with_day(base, 0).unwrap_err().to_string(),
+            "Day 0 not in range 1-31 or -31 to -1"
+        );
+    }
+
+    #[test]
+    fn test_with_day_opt_dst() {
+        // Clocks go back from 03:00 to 02:00 on 2024-04-07 in Melbourne
+        let tz = &chrono_tz::Australia::Melbourne;
+        let base = tz.with_ymd_and_hms(2024, 4, 1, 2, 30, 0).unwrap();
+        assert_eq!(with_day_opt(base, 7), None);
+        assert_eq!(
+            with_day_opt(base, -1),
+            tz.with_ymd_and_hms(2024, 4, 30, 2, 30, 0).single()
+        );
+        assert_eq!(with_day(base, 7), Err(DayOutOfRange::Unresolvable(7)));
+        assert_eq!(with_day(base, -24), Err(DayOutOfRange::Unresolvable(-24)));
+        assert_eq!(with_day(base, 32), Err(DayOutOfRange::Index(32)));
+        assert_eq!(
+            with_day(base, -1),
+            Ok(tz.with_ymd_and_hms(2024, 4, 30, 2, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_shift_months_by_weekday() {
+        let date = |month: u32, day: u32| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        [
+            // 1st Friday
+            (date(3, 1), 1, date(4, 5)),
+            // 3rd Tuesday
+            (date(3, 19), 1, date(4, 16)),
+            (date(3, 19), -1, date(2, 20)),
+            (
+                date(3, 19),
+                12,
+                NaiveDate::from_ymd_opt(2025, 3, 18).unwrap(),
+            ),
+            // 5th Friday to a month with only four
+            (date(3, 29), 1, date(4, 26)),
+            // 5th Friday to a month with five
+            (date(3, 29), 2, date(5, 31)),
+            // 4th Friday which is also the last stays the 4th
+            (date(4, 26), 1, date(5, 24)),
+            // 5th Thursday in a leap February
+            (date(2, 29), 1, date(3, 28)),
+            (date(3, 19), 0, date(3, 19)),
+        ]
+        .iter()
+        .for_each(|(start, months, expected)| {
+            let shifted = shift_months_by_weekday(*start, *months);
+            assert_eq!(shifted, *expected, "{} {}", start, months);
+            assert_eq!(shifted.weekday(), start.weekday());
+        });
+    }
+
+    #[test]
+    fn test_shift_months_by_weekday_datetime() {
+        let tz = &chrono_tz::Australia::Melbourne;
+        let base = tz.with_ymd_and_hms(2024, 3, 19, 18, 30, 0).unwrap();
+        assert_eq!(
+            shift_months_by_weekday(base, 1),
+            tz.with_ymd_and_hms(2024, 4, 16, 18, 30, 0).unwrap()
+        );
+        // Clocks go back from 03:00 to 02:00 on the 1st Sunday of April 2024 in Melbourne
+        let base = tz.with_ymd_and_hms(2024, 3, 3, 2, 30, 0).unwrap();
+        assert_eq!(shift_months_by_weekday_opt(base, 1), None);
+        // Clocks go forward from 00:00 to 01:00 on 1st October 2023 in Asuncion, but only the
+        // target date needs to exist
+        let tz = &chrono_tz::America::Asuncion;
+        let base = tz.with_ymd_and_hms(2023, 10, 10, 0, 30, 0).unwrap();
+        assert_eq!(
+            shift_months_by_weekday_opt(base, 1),
+            tz.with_ymd_and_hms(2023, 11, 14, 0, 30, 0).single()
+        );
+    }
+
+    #[test]
+    fn test_resolve_weekday() {
+        // April 2024 has five Mondays and four Fridays
+        [
+            (Weekday::Mon, 5, Ok(Some(29))),
+            (Weekday::Fri, 5, Ok(Some(26))),
+            (Weekday::Fri, -5, Ok(Some(5))),
+            (Weekday::Fri, -1, Ok(Some(26))),
+            (Weekday::Fri, 0, Err(WeekdayOrdinalOutOfRange { n: 0 })),
+            (Weekday::Fri, 6, Err(WeekdayOrdinalOutOfRange { n: 6 })),
+        ]
+        .iter()
+        .for_each(|(weekday, n, expected)| {
+            assert_eq!(resolve_weekday(2024, 4, *weekday, *n), *expected);
+        });
+    }
+
+    #[test]
+    fn test_easter() {
+        // Dates from published Easter tables: Western, Orthodox and Julian
+        [
+            (1583, (4, 10), (4, 10), (3, 31)),
+            (1700, (4, 11), (4, 11), (3, 31)),
+            (1818, (3, 22), (4, 26), (4, 14)),
+            (1900, (4, 15), (4, 22), (4, 9)),
+            (1943, (4, 25), (4, 25), (4, 12)),
+            (1954, (4, 18), (4, 25), (4, 12)),
+            (2000, (4, 23), (4, 30), (4, 17)),
+            (2008, (3, 23), (4, 27), (4, 14)),
+            (2010, (4, 4), (4, 4), (3, 22)),
+            (2011, (4, 24), (4, 24), (4, 11)),
+            (2019, (4, 21), (4, 28), (4, 15)),
+            (2021, (4, 4), (5, 2), (4, 19)),
+            (2022, (4, 17), (4, 24), (4, 11)),
+            (2023, (4, 9), (4, 16), (4, 3)),
+            (2024, (3, 31), (5, 5), (4, 22)),
+            (2025, (4, 20), (4, 20), (4, 7)),
+            (2038, (4, 25), (4, 25), (4, 12)),
+            (2100, (3, 28), (5, 2), (4, 18)),
+            (2285, (3, 22), (4, 26), (4, 11)),
+        ]
+        .iter()
+        .for_each(|(year, western, orthodox, julian)| {
+            let date = |(month, day): (u32, u32)| NaiveDate::from_ymd_opt(*year, month, day);
+            assert_eq!(
+                easter(*year, EasterMethod::Western),
+                date(*western),
+                "{}",
+                year
+            );
+            assert_eq!(
+                easter(*year, EasterMethod::Orthodox),
+                date(*orthodox),
+                "{}",
+                year
+            );
+            assert_eq!(
+                easter(*year, EasterMethod::Julian),
+                date(*julian),
+                "{}",
+                year
+            );
+        });
+    }
+
+    #[test]
+    fn test_easter_full_range() {
+        (NaiveDate::MIN.year()..=NaiveDate::MAX.year()).for_each(|year| {
+            let in_season = |date: NaiveDate| {
+                date.month() == 3 && date.day() >= 22 || date.month() == 4 && date.day() <= 25
+            };
+
+            let western = easter(year, EasterMethod::Western).unwrap();
+            assert_eq!(western.weekday(), Weekday::Sun, "{}", year);
+            assert!(in_season(western), "{}", year);
+
+            let julian = easter(year, EasterMethod::Julian).unwrap();
+            assert!(in_season(julian), "{}", year);
+
+            // From March onwards, the Julian calendar is this many days behind the Gregorian.
+            // The Orthodox date leaves the range of NaiveDate at the ends of the range.
+            let drift = year.div_euclid(100) - year.div_euclid(400) - 2;
+            let orthodox = easter(year, EasterMethod::Orthodox);
+            assert_eq!(
+                orthodox,
+                julian.checked_add_signed(Duration::days(drift.into())),
+                "{}",
+                year
+            );
+            if let Some(orthodox) = orthodox {
+                assert_eq!(orthodox.weekday(), Weekday::Sun, "{}", year);
+            }
+        });
+    }
 }
@@ -0,0 +1,1344 @@
+//! Fiscal years which start on any day of the year, with their quarters and periods, and
+//! week-based retail calendars.
+use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::boundary::ymd;
+use crate::calendar::naive_date;
+use crate::delta::{nearest_weekday, previous_weekday, shift_months_opt, with_date};
+
+/// How fiscal years are numbered.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FiscalYearNaming {
+    /// Fiscal years are named after the calendar year they start in, so FY24 starts in 2024.
+    ///
+    /// A [`RetailCalendar`] whose years end near the end of December names each year after the
+    /// calendar year it ends in, as it may start in the last days of the previous December.
+    StartYear,
+    /// Fiscal years are named after the calendar year they end in, so FY24 ends in 2024.
+    EndYear,
+}
+
+/// A fiscal year, quarter or period (fiscal month), as labelled by a [`FiscalCalendar`].
+///
+/// Labels are formatted as `FY24`, `FY24 Q3` and `FY24 P7`. Fiscal years from 2000 to 2099 are
+/// written with two digits, and other years in full, e.g. `FY1999 Q4`.
+///
+/// # Example
+///
+/// ```
+/// use chronoutil::fiscal::FiscalPeriod;
+///
+/// let quarter: FiscalPeriod = "FY24 Q3".parse().unwrap();
+/// assert_eq!(quarter, FiscalPeriod::Quarter(2024, 3));
+/// assert_eq!(quarter.shift(2).unwrap().to_string(), "FY25 Q1");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FiscalPeriod {
+    /// A whole fiscal year.
+    Year(i32),
+    /// A quarter of a fiscal year, numbered from 1 to 4.
+    Quarter(i32, u32),
+    /// A period of a fiscal year, numbered from 1 to 12, where each period is one month long.
+    Period(i32, u32),
+}
+
+impl FiscalPeriod {
+    /// Parses a fiscal period label, ignoring case. See [`FromStr`].
+    ///
+    /// # Errors
+    ///
+    /// - Labels which do not start with `FY`
+    /// - Years which are not numbers
+    /// - Quarters or periods which are out of range
+    pub fn parse(label: &str) -> Result<FiscalPeriod, String> {
+        let upper = label.trim().to_ascii_uppercase();
+        let rest = upper
+            .strip_prefix("FY")
+            .ok_or_else(|| format!("fiscal period {} does not start with FY", label))?
+            .trim_start();
+
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+            .unwrap_or(rest.len());
+        let digits = &rest[..split];
+        let year = digits
+            .parse::<i32>()
+            .map_err(|_| format!("invalid year in fiscal period {}", label))?;
+        // Only unsigned two-digit years are in the 2000s, so FY-1 is the year -1
+        let year = if digits.len() == 2 && digits.bytes().all(|b| b.is_ascii_digit()) {
+            2000 + year
+        } else {
+            year
+        };
+
+        let suffix = rest[split..].trim_start();
+        if suffix.is_empty() {
+            return Ok(FiscalPeriod::Year(year));
+        }
+        let mut chars = suffix.chars();
+        let unit = chars.next();
+        let number = chars
+            .as_str()
+            .parse::<u32>()
+            .map_err(|_| format!("invalid number in fiscal period {}", label))?;
+        match unit {
+            Some('Q') if (1..=4).contains(&number) => Ok(FiscalPeriod::Quarter(year, number)),
+            Some('P') if (1..=12).contains(&number) => Ok(FiscalPeriod::Period(year, number)),
+            Some('Q') | Some('P') => Err(format!(
+                "{} out of range in fiscal period {}",
+                suffix, label
+            )),
+            _ => Err(format!(
+                "unknown unit {} in fiscal period {}",
+                suffix, label
+            )),
+        }
+    }
+
+    /// The fiscal year the period belongs to.
+    #[inline]
+    pub fn year(&self) -> i32 {
+        match self {
+            FiscalPeriod::Year(year)
+            | FiscalPeriod::Quarter(year, _)
+            | FiscalPeriod::Period(year, _) => *year,
+        }
+    }
+
+    /// Returns the period `n` years, quarters or periods later, depending on the kind of
+    /// period. Negative values of `n` move to earlier periods.
+    ///
+    /// Returns None if the year overflows.
+    pub fn shift(&self, n: i32) -> Option<FiscalPeriod> {
+        let shift = |year: i32, number: u32, length: i32| -> Option<(i32, u32)> {
+            let index = i64::from(number) - 1 + i64::from(n);
+            let year = i64::from(year) + index.div_euclid(i64::from(length));
+            let number = index.rem_euclid(i64::from(length)) as u32 + 1;
+            Some((year.try_into().ok()?, number))
+        };
+        match *self {
+            FiscalPeriod::Year(year) => year.checked_add(n).map(FiscalPeriod::Year),
+            FiscalPeriod::Quarter(year, quarter) => {
+                shift(year, quarter, 4).map(|(year, quarter)| FiscalPeriod::Quarter(year, quarter))
+            }
+            FiscalPeriod::Period(year, period) => {
+                shift(year, period, 12).map(|(year, period)| FiscalPeriod::Period(year, period))
+            }
+        }
+    }
+
+    // The first period and the number of periods covered, or None if the quarter or period
+    // is out of range
+    fn periods(&self) -> Option<(u32, u32)> {
+        match *self {
+            FiscalPeriod::Year(_) => Some((1, 12)),
+            FiscalPeriod::Quarter(_, quarter @ 1..=4) => Some((3 * quarter - 2, 3)),
+            FiscalPeriod::Period(_, period @ 1..=12) => Some((period, 1)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FiscalPeriod {
+    /// Formats the period as a label such as `FY24`, `FY24 Q3` or `FY24 P7`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let year = self.year();
+        if (2000..2100).contains(&year) {
+            write!(f, "FY{:02}", year - 2000)?;
+        } else {
+            write!(f, "FY{:04}", year)?;
+        }
+        match self {
+            FiscalPeriod::Year(_) => Ok(()),
+            FiscalPeriod::Quarter(_, quarter) => write!(f, " Q{}", quarter),
+            FiscalPeriod::Period(_, period) => write!(f, " P{}", period),
+        }
+    }
+}
+
+impl FromStr for FiscalPeriod {
+    type Err = String;
+
+    /// Parses a label such as `FY24`, `FY2024 Q3`, `FY24Q3` or `FY24 P07`, ignoring case.
+    ///
+    /// Two-digit years are taken to be between 2000 and 2099.
+    fn from_str(label: &str) -> Result<Self, Self::Err> {
+        FiscalPeriod::parse(label)
+    }
+}
+
+/// A fiscal year, starting on a given day and month and divided into quarters and periods.
+///
+/// Each fiscal year has four quarters of three periods, and each period runs for one month
+/// from the start day, so the periods of the UK tax year run from the 6th to the 5th of the
+/// following month.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use chronoutil::fiscal::{FiscalCalendar, FiscalPeriod, FiscalYearNaming};
+///
+/// // The US federal government's fiscal year starts on October 1st
+/// let calendar = FiscalCalendar::new(10, 1).unwrap().with_naming(FiscalYearNaming::EndYear);
+/// let date = NaiveDate::from_ymd_opt(2024, 11, 15).unwrap();
+/// assert_eq!(calendar.fiscal_year(date), FiscalPeriod::Year(2025));
+/// assert_eq!(calendar.fiscal_quarter(date).to_string(), "FY25 Q1");
+/// assert_eq!(calendar.fiscal_period(date).to_string(), "FY25 P2");
+///
+/// let quarter = "FY24 Q3".parse().unwrap();
+/// assert_eq!(calendar.start(quarter), NaiveDate::from_ymd_opt(2024, 4, 1));
+/// assert_eq!(calendar.end(quarter), NaiveDate::from_ymd_opt(2024, 6, 30));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FiscalCalendar {
+    start_month: u32,
+    start_day: u32,
+    naming: FiscalYearNaming,
+}
+
+impl FiscalCalendar {
+    /// The calendar year, from January 1st.
+    pub const CALENDAR_YEAR: Self = Self {
+        start_month: 1,
+        start_day: 1,
+        naming: FiscalYearNaming::EndYear,
+    };
+
+    /// The UK tax year, from April 6th, named after the year it ends in.
+    pub const UK_TAX: Self = Self {
+        start_month: 4,
+        start_day: 6,
+        naming: FiscalYearNaming::EndYear,
+    };
+
+    /// The UK government's financial year, from April 1st, named after the year it starts in.
+    pub const UK_GOVERNMENT: Self = Self {
+        start_month: 4,
+        start_day: 1,
+        naming: FiscalYearNaming::StartYear,
+    };
+
+    /// The US federal government's fiscal year, from October 1st, named after the year it
+    /// ends in.
+    pub const US_FEDERAL: Self = Self {
+        start_month: 10,
+        start_day: 1,
+        naming: FiscalYearNaming::EndYear,
+    };
+
+    /// Creates a fiscal calendar whose years start on the given month and day, and are named
+    /// after the year they end in.
+    ///
+    /// # Errors
+    ///
+    /// - Months outside 1-12
+    /// - Days outside 1-28, which would not start a period in every month
+    pub fn new(start_month: u32, start_day: u32) -> Result<Self, String> {
+        if !(1..=12).contains(&start_month) {
+            return Err(format!("Month {} not in range 1-12", start_month));
+        }
+        if !(1..=28).contains(&start_day) {
+            return Err(format!("Day {} not in range 1-28", start_day));
+        }
+        Ok(Self {
+            start_month,
+            start_day,
+            naming: FiscalYearNaming::EndYear,
+        })
+    }
+
+    /// Names fiscal years with the given convention.
+    pub fn with_naming(&self, naming: FiscalYearNaming) -> Self {
+        Self { naming, ..*self }
+    }
+
+    /// The month fiscal years start in.
+    #[inline]
+    pub fn start_month(&self) -> u32 {
+        self.start_month
+    }
+
+    /// The day of the month fiscal years start on.
+    #[inline]
+    pub fn start_day(&self) -> u32 {
+        self.start_day
+    }
+
+    /// How fiscal years are numbered.
+    #[inline]
+    pub fn naming(&self) -> FiscalYearNaming {
+        self.naming
+    }
+
+    // The difference between a fiscal year's name and the calendar year it starts in
+    fn year_offset(&self) -> i32 {
+        let starts_in_january = self.start_month == 1 && self.start_day == 1;
+        (self.naming == FiscalYearNaming::EndYear && !starts_in_january) as i32
+    }
+
+    // The fiscal year of the date, and its period from 1 to 12
+    fn year_and_period<D: Datelike>(&self, date: &D) -> (i32, u32) {
+        let months = i64::from(date.year()) * 12 + i64::from(date.month0())
+            - i64::from(self.start_month - 1)
+            - (date.day() < self.start_day) as i64;
+        let year = months.div_euclid(12) as i32 + self.year_offset();
+        (year, months.rem_euclid(12) as u32 + 1)
+    }
+
+    /// Returns the fiscal year containing the date.
+    pub fn fiscal_year<D: Datelike>(&self, date: D) -> FiscalPeriod {
+        FiscalPeriod::Year(self.year_and_period(&date).0)
+    }
+
+    /// Returns the fiscal quarter containing the date.
+    pub fn fiscal_quarter<D: Datelike>(&self, date: D) -> FiscalPeriod {
+        let (year, period) = self.year_and_period(&date);
+        FiscalPeriod::Quarter(year, (period - 1) / 3 + 1)
+    }
+
+    /// Returns the fiscal period containing the date.
+    pub fn fiscal_period<D: Datelike>(&self, date: D) -> FiscalPeriod {
+        let (year, period) = self.year_and_period(&date);
+        FiscalPeriod::Period(year, period)
+    }
+
+    /// Returns the first day of a fiscal year, quarter or period.
+    ///
+    /// Returns None if the quarter is not in the range 1-4, the period is not in the range
+    /// 1-12, or the date is out of range.
+    pub fn start(&self, period: FiscalPeriod) -> Option<NaiveDate> {
+        let (first, _) = period.periods()?;
+        let year = period.year().checked_sub(self.year_offset())?;
+        let start = NaiveDate::from_ymd_opt(year, self.start_month, self.start_day)?;
+        shift_months_opt(start, first as i32 - 1)
+    }
+
+    /// Returns the last day of a fiscal year, quarter or period.
+    ///
+    /// Returns None if the quarter is not in the range 1-4, the period is not in the range
+    /// 1-12, or the date is out of range.
+    pub fn end(&self, period: FiscalPeriod) -> Option<NaiveDate> {
+        let (_, length) = period.periods()?;
+        shift_months_opt(self.start(period)?, length as i32)?.pred_opt()
+    }
+
+    /// Shift a date by the given number of fiscal quarters, which are three months long.
+    ///
+    /// The date keeps its position within the quarter, and ambiguous month-ends are shifted
+    /// backwards as for [`shift_months`](crate::delta::shift_months). Panics if the result is
+    /// out of range or an ambiguous or non-existing date/time.
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::fiscal::{FiscalCalendar, FiscalPeriod};
+    /// let calendar = FiscalCalendar::UK_TAX;
+    /// let date = NaiveDate::from_ymd_opt(2024, 11, 30).unwrap();
+    /// assert_eq!(calendar.fiscal_quarter(date), FiscalPeriod::Quarter(2025, 3));
+    ///
+    /// let shifted = calendar.shift_quarters(date, 1);
+    /// assert_eq!(shifted, NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
+    /// assert_eq!(calendar.fiscal_quarter(shifted), FiscalPeriod::Quarter(2025, 4));
+    /// ```
+    #[inline]
+    pub fn shift_quarters<D: Datelike>(&self, date: D, quarters: i32) -> D {
+        self.shift_quarters_opt(date, quarters).unwrap()
+    }
+
+    /// Same as [`FiscalCalendar::shift_quarters`] except fallible on unresolvable dates/times.
+    ///
+    /// Returns `None` rather than panicking when the result is out of range, or an ambiguous or
+    /// non-existing date/time (e.g. in a DST transition).
+    #[inline]
+    pub fn shift_quarters_opt<D: Datelike>(&self, date: D, quarters: i32) -> Option<D> {
+        shift_months_opt(date, quarters.checked_mul(3)?)
+    }
+}
+
+/// The number of weeks in each of the three periods of a quarter, for a [`RetailCalendar`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RetailPattern {
+    /// Periods of 4, 4 and 5 weeks.
+    FourFourFive,
+    /// Periods of 4, 5 and 4 weeks.
+    FourFiveFour,
+    /// Periods of 5, 4 and 4 weeks.
+    FiveFourFour,
+}
+
+impl RetailPattern {
+    fn weeks(&self) -> [u32; 3] {
+        match self {
+            RetailPattern::FourFourFive => [4, 4, 5],
+            RetailPattern::FourFiveFour => [4, 5, 4],
+            RetailPattern::FiveFourFour => [5, 4, 4],
+        }
+    }
+}
+
+/// How a [`RetailCalendar`] chooses the last day of its year from the end of a month.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RetailYearEnd {
+    /// The last weekday on or before the end of the month.
+    Last,
+    /// The weekday nearest to the end of the month, which may fall in the following month.
+    Nearest,
+}
+
+/// A week-based fiscal calendar, as used for retail and broadcast reporting.
+///
+/// Each year ends on a given weekday at or near the end of a given month, so most years have
+/// 52 weeks and some have 53. The weeks are grouped into four quarters of three periods with a
+/// [`RetailPattern`] such as 4-4-5, and the 53rd week is added to one of the periods, the last
+/// by default. Years, quarters and periods are labelled with [`FiscalPeriod`]s, named after
+/// the year the month end falls in unless [`FiscalYearNaming::StartYear`] is used.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use chronoutil::fiscal::{FiscalPeriod, RetailCalendar};
+///
+/// // Years end on the Saturday nearest the end of January, with a 4-5-4 pattern
+/// let calendar = RetailCalendar::NRF;
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+/// assert_eq!(calendar.fiscal_period(date).to_string(), "FY24 P2");
+/// assert_eq!(calendar.fiscal_week(date), (2024, 6));
+/// assert_eq!(calendar.weeks_in_year(2023), Some(53));
+///
+/// let period = FiscalPeriod::Period(2024, 2);
+/// assert_eq!(calendar.start(period), NaiveDate::from_ymd_opt(2024, 3, 3));
+/// assert_eq!(calendar.end(period), NaiveDate::from_ymd_opt(2024, 4, 6));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct RetailCalendar {
+    year_end_month: u32,
+    year_end_weekday: Weekday,
+    year_end: RetailYearEnd,
+    pattern: RetailPattern,
+    extra_week_period: u32,
+    naming: FiscalYearNaming,
+}
+
+impl RetailCalendar {
+    /// The US National Retail Federation's calendar, whose years end on the Saturday nearest
+    /// the end of January and are named after the year they start in, with a 4-5-4 pattern.
+    pub const NRF: Self = Self {
+        year_end_month: 1,
+        year_end_weekday: Weekday::Sat,
+        year_end: RetailYearEnd::Nearest,
+        pattern: RetailPattern::FourFiveFour,
+        extra_week_period: 12,
+        naming: FiscalYearNaming::StartYear,
+    };
+
+    /// Creates a retail calendar whose years end on the given weekday at or near the end of
+    /// the given month, with a 4-4-5 pattern and the 53rd week in the last period.
+    ///
+    /// # Errors
+    ///
+    /// - Months outside 1-12
+    pub fn new(
+        year_end_month: u32,
+        year_end_weekday: Weekday,
+        year_end: RetailYearEnd,
+    ) -> Result<Self, String> {
+        if !(1..=12).contains(&year_end_month) {
+            return Err(format!("Month {} not in range 1-12", year_end_month));
+        }
+        Ok(Self {
+            year_end_month,
+            year_end_weekday,
+            year_end,
+            pattern: RetailPattern::FourFourFive,
+            extra_week_period: 12,
+            naming: FiscalYearNaming::EndYear,
+        })
+    }
+
+    /// Groups the weeks of each quarter with the given pattern.
+    pub fn with_pattern(&self, pattern: RetailPattern) -> Self {
+        Self { pattern, ..*self }
+    }
+
+    /// Adds the 53rd week of long years to the given period.
+    ///
+    /// # Errors
+    ///
+    /// - Periods outside 1-12
+    pub fn with_extra_week_period(&self, period: u32) -> Result<Self, String> {
+        if !(1..=12).contains(&period) {
+            return Err(format!("Period {} not in range 1-12", period));
+        }
+        Ok(Self {
+            extra_week_period: period,
+            ..*self
+        })
+    }
+
+    /// Names fiscal years with the given convention.
+    ///
+    /// Years which end near the end of December are named after the calendar year they end in
+    /// with either convention, as they may start in the last days of the previous December.
+    pub fn with_naming(&self, naming: FiscalYearNaming) -> Self {
+        Self { naming, ..*self }
+    }
+
+    /// The month years end at or near the end of.
+    #[inline]
+    pub fn year_end_month(&self) -> u32 {
+        self.year_end_month
+    }
+
+    /// The weekday years end on.
+    #[inline]
+    pub fn year_end_weekday(&self) -> Weekday {
+        self.year_end_weekday
+    }
+
+    /// How the last day of the year is chosen.
+    #[inline]
+    pub fn year_end(&self) -> RetailYearEnd {
+        self.year_end
+    }
+
+    /// The number of weeks in each period of a quarter.
+    #[inline]
+    pub fn pattern(&self) -> RetailPattern {
+        self.pattern
+    }
+
+    /// The period which the 53rd week of long years is added to.
+    #[inline]
+    pub fn extra_week_period(&self) -> u32 {
+        self.extra_week_period
+    }
+
+    /// How fiscal years are numbered.
+    #[inline]
+    pub fn naming(&self) -> FiscalYearNaming {
+        self.naming
+    }
+
+    /// Returns the number of weeks in the given fiscal year, which is either 52 or 53.
+    ///
+    /// Returns None if the year is out of range.
+    pub fn weeks_in_year(&self, year: i32) -> Option<u32> {
+        let (start, end) = self.year_bounds(year)?;
+        Some(((end - start).num_days() / 7 + 1) as u32)
+    }
+
+    // The difference between the calendar year of a fiscal year's month end and its name
+    fn year_offset(&self) -> i32 {
+        (self.naming == FiscalYearNaming::StartYear && self.year_end_month != 12) as i32
+    }
+
+    // The last day of the fiscal year whose month end is in the given calendar year
+    fn year_end_date(&self, year: i32) -> Option<NaiveDate> {
+        let month_end = ymd(year, self.year_end_month, 31)?;
+        match self.year_end {
+            RetailYearEnd::Last => previous_weekday(month_end, self.year_end_weekday, true),
+            RetailYearEnd::Nearest => nearest_weekday(month_end, self.year_end_weekday, true),
+        }
+    }
+
+    // The first and last days of a fiscal year
+    fn year_bounds(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        let year = year.checked_add(self.year_offset())?;
+        let start = self.year_end_date(year.checked_sub(1)?)?.succ_opt()?;
+        Some((start, self.year_end_date(year)?))
+    }
+
+    fn period_weeks(&self, period: u32, weeks_in_year: u32) -> u32 {
+        let extra = weeks_in_year == 53 && period == self.extra_week_period;
+        self.pattern.weeks()[(period as usize - 1) % 3] + extra as u32
+    }
+
+    // The fiscal year of the date, and the number of days since the start of the year
+    fn year_and_day(&self, date: NaiveDate) -> Option<(i32, i64)> {
+        let mut year = date.year();
+        while date > self.year_end_date(year)? {
+            year += 1;
+        }
+        while date <= self.year_end_date(year - 1)? {
+            year -= 1;
+        }
+        let year = year - self.year_offset();
+        Some((year, (date - self.year_bounds(year)?.0).num_days()))
+    }
+
+    // The fiscal year and period of the date, and the number of days since the start of the
+    // period
+    fn year_period_and_day<D: Datelike>(&self, date: &D) -> Option<(i32, u32, i64)> {
+        let (year, mut day) = self.year_and_day(naive_date(date)?)?;
+        let weeks_in_year = self.weeks_in_year(year)?;
+        let mut period = 1;
+        loop {
+            let days = 7 * i64::from(self.period_weeks(period, weeks_in_year));
+            if day < days {
+                return Some((year, period, day));
+            }
+            day -= days;
+            period += 1;
+        }
+    }
+
+    /// Returns the fiscal year containing the date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fiscal year is out of range.
+    pub fn fiscal_year<D: Datelike>(&self, date: D) -> FiscalPeriod {
+        self.fiscal_year_opt(date).expect("date out of range")
+    }
+
+    /// Same as [`RetailCalendar::fiscal_year`] except returns `None` rather than panicking
+    /// when the fiscal year is out of range.
+    pub fn fiscal_year_opt<D: Datelike>(&self, date: D) -> Option<FiscalPeriod> {
+        let (year, _) = self.year_and_day(naive_date(&date)?)?;
+        Some(FiscalPeriod::Year(year))
+    }
+
+    /// Returns the fiscal quarter containing the date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fiscal year is out of range.
+    pub fn fiscal_quarter<D: Datelike>(&self, date: D) -> FiscalPeriod {
+        self.fiscal_quarter_opt(date).expect("date out of range")
+    }
+
+    /// Same as [`RetailCalendar::fiscal_quarter`] except returns `None` rather than panicking
+    /// when the fiscal year is out of range.
+    pub fn fiscal_quarter_opt<D: Datelike>(&self, date: D) -> Option<FiscalPeriod> {
+        let (year, period, _) = self.year_period_and_day(&date)?;
+        Some(FiscalPeriod::Quarter(year, (period - 1) / 3 + 1))
+    }
+
+    /// Returns the fiscal period containing the date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fiscal year is out of range.
+    pub fn fiscal_period<D: Datelike>(&self, date: D) -> FiscalPeriod {
+        self.fiscal_period_opt(date).expect("date out of range")
+    }
+
+    /// Same as [`RetailCalendar::fiscal_period`] except returns `None` rather than panicking
+    /// when the fiscal year is out of range.
+    pub fn fiscal_period_opt<D: Datelike>(&self, date: D) -> Option<FiscalPeriod> {
+        let (year, period, _) = self.year_period_and_day(&date)?;
+        Some(FiscalPeriod::Period(year, period))
+    }
+
+    /// Returns the fiscal year containing the date, and the week of that year from 1 to 53.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fiscal year is out of range.
+    pub fn fiscal_week<D: Datelike>(&self, date: D) -> (i32, u32) {
+        self.fiscal_week_opt(date).expect("date out of range")
+    }
+
+    /// Same as [`RetailCalendar::fiscal_week`] except returns `None` rather than panicking
+    /// when the fiscal year is out of range.
+    pub fn fiscal_week_opt<D: Datelike>(&self, date: D) -> Option<(i32, u32)> {
+        let (year, day) = self.year_and_day(naive_date(&date)?)?;
+        Some((year, day as u32 / 7 + 1))
+    }
+
+    /// Returns the first day of a fiscal year, quarter or period.
+    ///
+    /// Returns None if the quarter is not in the range 1-4, the period is not in the range
+    /// 1-12, or the date is out of range.
+    pub fn start(&self, period: FiscalPeriod) -> Option<NaiveDate> {
+        let (start, _) = self.year_bounds(period.year())?;
+        let weeks_in_year = self.weeks_in_year(period.year())?;
+        let (first, _) = period.periods()?;
+        let weeks: u32 = (1..first)
+            .map(|period| self.period_weeks(period, weeks_in_year))
+            .sum();
+        start.checked_add_signed(Duration::weeks(weeks.into()))
+    }
+
+    /// Returns the last day of a fiscal year, quarter or period.
+    ///
+    /// Returns None if the quarter is not in the range 1-4, the period is not in the range
+    /// 1-12, or the date is out of range.
+    pub fn end(&self, period: FiscalPeriod) -> Option<NaiveDate> {
+        let (first, length) = period.periods()?;
+        let weeks_in_year = self.weeks_in_year(period.year())?;
+        let weeks: u32 = (first..first + length)
+            .map(|period| self.period_weeks(period, weeks_in_year))
+            .sum();
+        self.start(period)?
+            .checked_add_signed(Duration::weeks(weeks.into()))?
+            .pred_opt()
+    }
+
+    /// Shift a date by the given number of fiscal periods.
+    ///
+    /// The date keeps its week and weekday within the period. Dates in the 5th or 6th week of
+    /// a period which is shifted to a shorter period move back to the same weekday of its last
+    /// week.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fiscal year is out of range, or the result is an ambiguous or
+    /// non-existing date/time.
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::fiscal::{FiscalPeriod, RetailCalendar};
+    /// let calendar = RetailCalendar::NRF;
+    /// // The last Friday of the 5-week FY24 P2
+    /// let date = NaiveDate::from_ymd_opt(2024, 4, 5).unwrap();
+    /// let shifted = calendar.shift_periods(date, 1);
+    /// // The last Friday of the 4-week FY24 P3
+    /// assert_eq!(shifted, NaiveDate::from_ymd_opt(2024, 5, 3).unwrap());
+    /// assert_eq!(calendar.fiscal_period(shifted), FiscalPeriod::Period(2024, 3));
+    /// ```
+    pub fn shift_periods<D: Datelike>(&self, date: D, periods: i32) -> D {
+        self.shift_periods_opt(date, periods)
+            .expect("date out of range")
+    }
+
+    /// Same as [`RetailCalendar::shift_periods`] except fallible on unresolvable dates/times.
+    ///
+    /// Returns `None` rather than panicking when the fiscal year is out of range, or the
+    /// result is an ambiguous or non-existing date/time (e.g. in a DST transition).
+    pub fn shift_periods_opt<D: Datelike>(&self, date: D, periods: i32) -> Option<D> {
+        let (year, period, day) = self.year_period_and_day(&date)?;
+        let target = FiscalPeriod::Period(year, period).shift(periods)?;
+        let start = self.start(target)?;
+        let end = self.end(target)?;
+        // Keep the weekday when moving back from a longer period
+        let last_week = (end - start).num_days() + 1 - 7;
+        let day = if day < last_week + 7 {
+            day
+        } else {
+            last_week + day % 7
+        };
+        with_date(date, start.checked_add_signed(Duration::days(day))?)
+    }
+
+    /// Returns the comparable date in the previous fiscal year, which has the same week of the
+    /// year and weekday.
+    ///
+    /// Dates in the 53rd week of a long year move back to the 52nd week of the previous year.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fiscal year is out of range, or the result is an ambiguous or
+    /// non-existing date/time.
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::fiscal::RetailCalendar;
+    /// let calendar = RetailCalendar::NRF;
+    /// // The first Sunday of FY24, and of FY23
+    /// let date = NaiveDate::from_ymd_opt(2024, 2, 4).unwrap();
+    /// assert_eq!(
+    ///     calendar.same_day_last_year(date),
+    ///     NaiveDate::from_ymd_opt(2023, 1, 29).unwrap()
+    /// );
+    /// ```
+    pub fn same_day_last_year<D: Datelike>(&self, date: D) -> D {
+        self.same_day_last_year_opt(date)
+            .expect("date out of range")
+    }
+
+    /// Same as [`RetailCalendar::same_day_last_year`] except fallible on unresolvable
+    /// dates/times.
+    ///
+    /// Returns `None` rather than panicking when the fiscal year is out of range, or the
+    /// result is an ambiguous or non-existing date/time (e.g. in a DST transition).
+    pub fn same_day_last_year_opt<D: Datelike>(&self, date: D) -> Option<D> {
+        let (year, day) = self.year_and_day(naive_date(&date)?)?;
+        let (start, end) = self.year_bounds(year.checked_sub(1)?)?;
+        let day = day.min((end - start).num_days() - 6 + day % 7);
+        with_date(date, start.checked_add_signed(Duration::days(day))?)
+    }
+
+    /// Returns the successive fiscal years, quarters or periods from the given one, depending
+    /// on its kind, along with their first and last days.
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::fiscal::{FiscalPeriod, RetailCalendar};
+    /// let calendar = RetailCalendar::NRF;
+    /// let weeks: Vec<i64> = calendar
+    ///     .periods(FiscalPeriod::Period(2023, 10))
+    ///     .with_count(4)
+    ///     .map(|(_, start, end)| (end - start).num_days() / 7 + 1)
+    ///     .collect();
+    /// // FY23 had 53 weeks, so the extra week was added to P12
+    /// assert_eq!(weeks, vec![4, 5, 5, 4]);
+    /// ```
+    pub fn periods(&self, from: FiscalPeriod) -> RetailPeriods {
+        RetailPeriods {
+            calendar: *self,
+            from,
+            count: None,
+            end: None,
+            _current_count: 0,
+        }
+    }
+}
+
+/// An iterator over the successive periods of a [`RetailCalendar`], yielding each
+/// [`FiscalPeriod`] along with its first and last days.
+///
+/// Like a [`DateRule`](crate::DateRule), it can be limited to a number of periods, or to
+/// periods starting before an end date.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RetailPeriods {
+    calendar: RetailCalendar,
+    from: FiscalPeriod,
+    count: Option<usize>,
+    end: Option<NaiveDate>,
+    _current_count: usize,
+}
+
+impl RetailPeriods {
+    /// Limits the `RetailPeriods` to a given number of periods.
+    pub fn with_count(&self, number: usize) -> Self {
+        Self {
+            count: Some(number),
+            end: None,
+            _current_count: 0,
+            ..*self
+        }
+    }
+
+    /// Limits the `RetailPeriods` to periods starting before an end date (exclusive).
+    pub fn with_end(&self, end: NaiveDate) -> Self {
+        Self {
+            count: None,
+            end: Some(end),
+            _current_count: 0,
+            ..*self
+        }
+    }
+}
+
+impl Iterator for RetailPeriods {
+    type Item = (FiscalPeriod, NaiveDate, NaiveDate);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count.is_some() && self._current_count >= self.count.unwrap() {
+            return None;
+        }
+
+        let period = self.from.shift(self._current_count.try_into().ok()?)?;
+        let start = self.calendar.start(period)?;
+        let end = self.calendar.end(period)?;
+        if let Some(limit) = self.end {
+            if start >= limit {
+                return None;
+            }
+        }
+
+        self._current_count += 1;
+        Some((period, start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_format() {
+        [
+            ("FY24", FiscalPeriod::Year(2024), "FY24"),
+            ("fy2024", FiscalPeriod::Year(2024), "FY24"),
+            ("FY24 Q3", FiscalPeriod::Quarter(2024, 3), "FY24 Q3"),
+            (" fy24q3 ", FiscalPeriod::Quarter(2024, 3), "FY24 Q3"),
+            ("FY 2024 Q1", FiscalPeriod::Quarter(2024, 1), "FY24 Q1"),
+            ("FY24 P07", FiscalPeriod::Period(2024, 7), "FY24 P7"),
+            ("FY99 P12", FiscalPeriod::Period(2099, 12), "FY99 P12"),
+            ("FY1999 Q4", FiscalPeriod::Quarter(1999, 4), "FY1999 Q4"),
+            ("FY2100", FiscalPeriod::Year(2100), "FY2100"),
+            ("FY0024", FiscalPeriod::Year(24), "FY0024"),
+            ("FY-1", FiscalPeriod::Year(-1), "FY-001"),
+            ("FY-12 Q2", FiscalPeriod::Quarter(-12, 2), "FY-012 Q2"),
+        ]
+        .iter()
+        .for_each(|(label, period, formatted)| {
+            assert_eq!(label.parse::<FiscalPeriod>(), Ok(*period), "{}", label);
+            assert_eq!(period.to_string(), *formatted);
+            assert_eq!(formatted.parse::<FiscalPeriod>(), Ok(*period));
+        });
+
+        [
+            ("24 Q3", "does not start with FY"),
+            ("FYQ3", "invalid year"),
+            ("FY24 Q", "invalid number"),
+            ("FY24 Q5", "Q5 out of range"),
+            ("FY24 P0", "P0 out of range"),
+            ("FY24 H1", "unknown unit H1"),
+        ]
+        .iter()
+        .for_each(|(label, error)| {
+            let message = FiscalPeriod::parse(label).unwrap_err();
+            assert!(message.contains(error), "{}", message);
+        });
+    }
+
+    #[test]
+    fn test_shift_periods() {
+        [
+            (FiscalPeriod::Year(2024), -3, FiscalPeriod::Year(2021)),
+            (
+                FiscalPeriod::Quarter(2024, 3),
+                2,
+                FiscalPeriod::Quarter(2025, 1),
+            ),
+            (
+                FiscalPeriod::Quarter(2024, 1),
+                -1,
+                FiscalPeriod::Quarter(2023, 4),
+            ),
+            (
+                FiscalPeriod::Quarter(2024, 2),
+                -9,
+                FiscalPeriod::Quarter(2022, 1),
+            ),
+            (
+                FiscalPeriod::Period(2024, 12),
+                1,
+                FiscalPeriod::Period(2025, 1),
+            ),
+            (
+                FiscalPeriod::Period(2024, 7),
+                30,
+                FiscalPeriod::Period(2027, 1),
+            ),
+        ]
+        .iter()
+        .for_each(|(period, n, expected)| {
+            assert_eq!(period.shift(*n), Some(*expected));
+        });
+        assert_eq!(FiscalPeriod::Year(i32::MAX).shift(1), None);
+        assert_eq!(FiscalPeriod::Quarter(i32::MAX, 4).shift(1), None);
+    }
+
+    #[test]
+    fn test_fiscal_periods_of_dates() {
+        let calendar = FiscalCalendar::UK_TAX;
+        [
+            (date(2024, 4, 5), (2024, 4, 12)),
+            (date(2024, 4, 6), (2025, 1, 1)),
+            (date(2024, 7, 5), (2025, 1, 3)),
+            (date(2024, 7, 6), (2025, 2, 4)),
+            (date(2025, 1, 5), (2025, 3, 9)),
+            (date(2025, 1, 6), (2025, 4, 10)),
+        ]
+        .iter()
+        .for_each(|(date, (year, quarter, period))| {
+            assert_eq!(calendar.fiscal_year(*date), FiscalPeriod::Year(*year));
+            assert_eq!(
+                calendar.fiscal_quarter(*date),
+                FiscalPeriod::Quarter(*year, *quarter)
+            );
+            assert_eq!(
+                calendar.fiscal_period(*date),
+                FiscalPeriod::Period(*year, *period)
+            );
+        });
+
+        // Naming by start or end year
+        let july = FiscalCalendar::new(7, 1).unwrap();
+        let datetime = date(2024, 8, 15).and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(july.fiscal_year(datetime), FiscalPeriod::Year(2025));
+        assert_eq!(
+            july.with_naming(FiscalYearNaming::StartYear)
+                .fiscal_year(datetime),
+            FiscalPeriod::Year(2024)
+        );
+        assert_eq!(
+            FiscalCalendar::UK_GOVERNMENT.fiscal_quarter(date(2025, 3, 31)),
+            FiscalPeriod::Quarter(2024, 4)
+        );
+        assert_eq!(
+            FiscalCalendar::CALENDAR_YEAR.fiscal_quarter(date(2024, 8, 15)),
+            FiscalPeriod::Quarter(2024, 3)
+        );
+        assert_eq!(
+            FiscalCalendar::CALENDAR_YEAR
+                .with_naming(FiscalYearNaming::StartYear)
+                .fiscal_year(date(2024, 1, 1)),
+            FiscalPeriod::Year(2024)
+        );
+    }
+
+    #[test]
+    fn test_boundaries() {
+        [
+            (
+                FiscalCalendar::UK_TAX,
+                "FY25",
+                date(2024, 4, 6),
+                date(2025, 4, 5),
+            ),
+            (
+                FiscalCalendar::UK_TAX,
+                "FY25 Q4",
+                date(2025, 1, 6),
+                date(2025, 4, 5),
+            ),
+            (
+                FiscalCalendar::UK_TAX,
+                "FY25 P11",
+                date(2025, 2, 6),
+                date(2025, 3, 5),
+            ),
+            (
+                FiscalCalendar::US_FEDERAL,
+                "FY24 Q1",
+                date(2023, 10, 1),
+                date(2023, 12, 31),
+            ),
+            (
+                FiscalCalendar::US_FEDERAL,
+                "FY24 P5",
+                date(2024, 2, 1),
+                date(2024, 2, 29),
+            ),
+            (
+                FiscalCalendar::UK_GOVERNMENT,
+                "FY24",
+                date(2024, 4, 1),
+                date(2025, 3, 31),
+            ),
+            (
+                FiscalCalendar::CALENDAR_YEAR,
+                "FY24 Q2",
+                date(2024, 4, 1),
+                date(2024, 6, 30),
+            ),
+        ]
+        .iter()
+        .for_each(|(calendar, label, start, end)| {
+            let period: FiscalPeriod = label.parse().unwrap();
+            assert_eq!(calendar.start(period), Some(*start), "{}", label);
+            assert_eq!(calendar.end(period), Some(*end), "{}", label);
+            // The boundaries lie within the period
+            [*start, *end].iter().for_each(|date| {
+                let containing = match period {
+                    FiscalPeriod::Year(_) => calendar.fiscal_year(*date),
+                    FiscalPeriod::Quarter(_, _) => calendar.fiscal_quarter(*date),
+                    FiscalPeriod::Period(_, _) => calendar.fiscal_period(*date),
+                };
+                assert_eq!(containing, period);
+            });
+            // and the next period starts the following day
+            assert_eq!(calendar.start(period.shift(1).unwrap()), end.succ_opt());
+        });
+        [
+            FiscalPeriod::Year(i32::MIN),
+            FiscalPeriod::Quarter(2024, 0),
+            FiscalPeriod::Quarter(2024, 5),
+            FiscalPeriod::Period(2024, 0),
+            FiscalPeriod::Period(2024, 13),
+        ]
+        .iter()
+        .for_each(|period| {
+            assert_eq!(FiscalCalendar::UK_TAX.start(*period), None, "{:?}", period);
+            assert_eq!(FiscalCalendar::UK_TAX.end(*period), None, "{:?}", period);
+        });
+    }
+
+    #[test]
+    fn test_shift_quarters() {
+        let calendar = FiscalCalendar::US_FEDERAL;
+        let start = date(2023, 10, 31);
+        let quarters: Vec<(NaiveDate, FiscalPeriod)> = (0..5)
+            .map(|n| {
+                let date = calendar.shift_quarters(start, n);
+                (date, calendar.fiscal_quarter(date))
+            })
+            .collect();
+        assert_eq!(
+            quarters,
+            vec![
+                (date(2023, 10, 31), FiscalPeriod::Quarter(2024, 1)),
+                (date(2024, 1, 31), FiscalPeriod::Quarter(2024, 2)),
+                (date(2024, 4, 30), FiscalPeriod::Quarter(2024, 3)),
+                (date(2024, 7, 31), FiscalPeriod::Quarter(2024, 4)),
+                (date(2024, 10, 31), FiscalPeriod::Quarter(2025, 1)),
+            ]
+        );
+        assert_eq!(calendar.shift_quarters(start, -2), date(2023, 4, 30));
+        assert_eq!(
+            calendar.shift_quarters_opt(start, -2),
+            Some(date(2023, 4, 30))
+        );
+        assert_eq!(calendar.shift_quarters_opt(start, i32::MAX / 2), None);
+        assert_eq!(calendar.shift_quarters_opt(start, i32::MIN), None);
+    }
+
+    #[test]
+    fn test_new() {
+        let calendar = FiscalCalendar::new(4, 6).unwrap();
+        assert_eq!(calendar, FiscalCalendar::UK_TAX);
+        assert_eq!(
+            (
+                calendar.start_month(),
+                calendar.start_day(),
+                calendar.naming()
+            ),
+            (4, 6, FiscalYearNaming::EndYear)
+        );
+        assert_eq!(
+            FiscalCalendar::new(13, 1).unwrap_err(),
+            "Month 13 not in range 1-12"
+        );
+        assert_eq!(
+            FiscalCalendar::new(1, 29).unwrap_err(),
+            "Day 29 not in range 1-28"
+        );
+    }
+
+    #[test]
+    fn test_retail_years() {
+        // The NRF calendar, named after the year each starts in
+        [
+            (2021, date(2021, 1, 31), date(2022, 1, 29), 52),
+            (2022, date(2022, 1, 30), date(2023, 1, 28), 52),
+            (2023, date(2023, 1, 29), date(2024, 2, 3), 53),
+            (2024, date(2024, 2, 4), date(2025, 2, 1), 52),
+        ]
+        .iter()
+        .for_each(|(year, start, end, weeks)| {
+            let calendar = RetailCalendar::NRF;
+            assert_eq!(calendar.start(FiscalPeriod::Year(*year)), Some(*start));
+            assert_eq!(calendar.end(FiscalPeriod::Year(*year)), Some(*end));
+            assert_eq!(calendar.weeks_in_year(*year), Some(*weeks));
+            assert_eq!(calendar.fiscal_year(*start), FiscalPeriod::Year(*year));
+            assert_eq!(calendar.fiscal_year(*end), FiscalPeriod::Year(*year));
+        });
+        let long_years: Vec<i32> = (2000..2030)
+            .filter(|year| RetailCalendar::NRF.weeks_in_year(*year) == Some(53))
+            .collect();
+        assert_eq!(long_years, vec![2000, 2006, 2012, 2017, 2023, 2028]);
+
+        // Years ending on the last Saturday of September, named after the year they end in
+        let calendar = RetailCalendar::new(9, Weekday::Sat, RetailYearEnd::Last).unwrap();
+        [
+            (2022, date(2021, 9, 26), date(2022, 9, 24), 52),
+            (2023, date(2022, 9, 25), date(2023, 9, 30), 53),
+            (2024, date(2023, 10, 1), date(2024, 9, 28), 52),
+        ]
+        .iter()
+        .for_each(|(year, start, end, weeks)| {
+            assert_eq!(calendar.start(FiscalPeriod::Year(*year)), Some(*start));
+            assert_eq!(calendar.end(FiscalPeriod::Year(*year)), Some(*end));
+            assert_eq!(calendar.weeks_in_year(*year), Some(*weeks));
+        });
+        assert_eq!(
+            calendar
+                .with_naming(FiscalYearNaming::StartYear)
+                .fiscal_year(date(2024, 1, 1)),
+            FiscalPeriod::Year(2023)
+        );
+        // Years ending near December are named after the year they end in either way
+        let december = RetailCalendar::new(12, Weekday::Sun, RetailYearEnd::Nearest).unwrap();
+        assert_eq!(
+            december
+                .with_naming(FiscalYearNaming::StartYear)
+                .fiscal_year(date(2024, 6, 1)),
+            december.fiscal_year(date(2024, 6, 1))
+        );
+    }
+
+    #[test]
+    fn test_retail_periods() {
+        let base = RetailCalendar::new(1, Weekday::Sat, RetailYearEnd::Nearest)
+            .unwrap()
+            .with_naming(FiscalYearNaming::StartYear);
+        [
+            (RetailPattern::FourFourFive, 12, 2024, [4, 4, 5, 4, 4, 5]),
+            (RetailPattern::FourFiveFour, 12, 2024, [4, 5, 4, 4, 5, 4]),
+            (RetailPattern::FiveFourFour, 12, 2024, [5, 4, 4, 5, 4, 4]),
+            (RetailPattern::FourFourFive, 12, 2023, [4, 4, 5, 4, 4, 6]),
+            (RetailPattern::FourFourFive, 2, 2023, [4, 5, 5, 4, 4, 5]),
+        ]
+        .iter()
+        .for_each(|(pattern, extra_week_period, year, weeks)| {
+            let calendar = base
+                .with_pattern(*pattern)
+                .with_extra_week_period(*extra_week_period)
+                .unwrap();
+            let periods: Vec<(FiscalPeriod, NaiveDate, NaiveDate)> = calendar
+                .periods(FiscalPeriod::Period(*year, 1))
+                .with_count(12)
+                .collect();
+            let lengths: Vec<i64> = periods
+                .iter()
+                .map(|(_, start, end)| ((*end - *start).num_days() + 1) / 7)
+                .collect();
+            assert_eq!(&lengths[..3], &weeks[..3]);
+            assert_eq!(&lengths[9..], &weeks[3..]);
+            assert_eq!(
+                lengths.iter().sum::<i64>(),
+                i64::from(calendar.weeks_in_year(*year).unwrap())
+            );
+
+            // Every day of the year lies within its period, quarter and week
+            let mut day = calendar.start(FiscalPeriod::Year(*year)).unwrap();
+            let end = calendar.end(FiscalPeriod::Year(*year)).unwrap();
+            while day <= end {
+                let period = calendar.fiscal_period(day);
+                let quarter = calendar.fiscal_quarter(day);
+                assert!(calendar.start(period).unwrap() <= day);
+                assert!(calendar.end(period).unwrap() >= day);
+                assert!(calendar.start(quarter).unwrap() <= day);
+                assert!(calendar.end(quarter).unwrap() >= day);
+                let (week_year, week) = calendar.fiscal_week(day);
+                assert_eq!(week_year, *year);
+                assert_eq!(
+                    i64::from(week),
+                    (day - calendar.start(FiscalPeriod::Year(*year)).unwrap()).num_days() / 7 + 1
+                );
+                day = day.succ_opt().unwrap();
+            }
+        });
+
+        // Periods before an end date
+        let quarters: Vec<FiscalPeriod> = RetailCalendar::NRF
+            .periods(FiscalPeriod::Quarter(2024, 3))
+            .with_end(date(2025, 5, 4))
+            .map(|(quarter, _, _)| quarter)
+            .collect();
+        assert_eq!(
+            quarters,
+            vec![
+                FiscalPeriod::Quarter(2024, 3),
+                FiscalPeriod::Quarter(2024, 4),
+                FiscalPeriod::Quarter(2025, 1),
+            ]
+        );
+
+        assert_eq!(
+            RetailCalendar::new(0, Weekday::Sat, RetailYearEnd::Last).unwrap_err(),
+            "Month 0 not in range 1-12"
+        );
+        assert_eq!(
+            RetailCalendar::NRF.with_extra_week_period(13).unwrap_err(),
+            "Period 13 not in range 1-12"
+        );
+    }
+
+    #[test]
+    fn test_retail_shifts() {
+        let calendar = RetailCalendar::NRF;
+        [
+            // From the last week of the 5-week FY24 P2 to the 4-week P3
+            (date(2024, 4, 5), 1, date(2024, 5, 3)),
+            (date(2024, 4, 5), 3, date(2024, 7, 5)),
+            (date(2024, 3, 3), 1, date(2024, 4, 7)),
+            // Across years, and into the 5-week FY23 P12
+            (date(2024, 3, 3), -2, date(2023, 12, 31)),
+            (date(2024, 3, 3), 12, date(2025, 3, 2)),
+            (date(2024, 2, 2), 0, date(2024, 2, 2)),
+        ]
+        .iter()
+        .for_each(|(date, periods, expected)| {
+            assert_eq!(
+                calendar.shift_periods(*date, *periods),
+                *expected,
+                "{} {}",
+                date,
+                periods
+            );
+        });
+
+        [
+            // The same week and weekday of the previous year
+            (date(2025, 1, 31), date(2024, 1, 26)),
+            (date(2024, 2, 4), date(2023, 1, 29)),
+            // The 53rd week moves back to the 52nd
+            (date(2024, 2, 1), date(2023, 1, 26)),
+            (date(2024, 1, 27), date(2023, 1, 28)),
+        ]
+        .iter()
+        .for_each(|(date, expected)| {
+            assert_eq!(calendar.same_day_last_year(*date), *expected, "{}", date);
+            assert_eq!(expected.weekday(), date.weekday());
+        });
+
+        // Times of day are kept
+        let datetime = date(2024, 4, 5).and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(
+            calendar.shift_periods(datetime, 1),
+            date(2024, 5, 3).and_hms_opt(9, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_retail_out_of_range() {
+        use chrono::TimeZone;
+
+        let calendar = RetailCalendar::NRF;
+        assert_eq!(calendar.fiscal_year_opt(NaiveDate::MAX), None);
+        assert_eq!(calendar.fiscal_quarter_opt(NaiveDate::MAX), None);
+        assert_eq!(calendar.fiscal_period_opt(NaiveDate::MAX), None);
+        assert_eq!(calendar.fiscal_week_opt(NaiveDate::MIN), None);
+        assert_eq!(calendar.shift_periods_opt(date(2024, 4, 5), i32::MAX), None);
+        assert_eq!(calendar.same_day_last_year_opt(NaiveDate::MIN), None);
+        [
+            FiscalPeriod::Quarter(2024, 0),
+            FiscalPeriod::Quarter(2024, 5),
+            FiscalPeriod::Period(2024, 0),
+            FiscalPeriod::Period(2024, 13),
+        ]
+        .iter()
+        .for_each(|period| {
+            assert_eq!(calendar.start(*period), None, "{:?}", period);
+            assert_eq!(calendar.end(*period), None, "{:?}", period);
+        });
+
+        let start = date(2024, 4, 5);
+        assert_eq!(
+            calendar.fiscal_year_opt(start),
+            Some(FiscalPeriod::Year(2024))
+        );
+        assert_eq!(
+            calendar.fiscal_quarter_opt(start),
+            Some(FiscalPeriod::Quarter(2024, 1))
+        );
+        assert_eq!(
+            calendar.fiscal_period_opt(start),
+            Some(FiscalPeriod::Period(2024, 2))
+        );
+        assert_eq!(calendar.fiscal_week_opt(start), Some((2024, 9)));
+        assert_eq!(calendar.shift_periods_opt(start, 1), Some(date(2024, 5, 3)));
+        assert_eq!(
+            calendar.same_day_last_year_opt(start),
+            Some(date(2023, 3, 31))
+        );
+
+        // Clocks go forward from 02:00 to 03:00 on 10th March 2024 in New York
+        let tz = &chrono_tz::America::New_York;
+        let start = tz.with_ymd_and_hms(2024, 2, 11, 2, 30, 0).unwrap();
+        assert_eq!(calendar.shift_periods_opt(start, 1), None);
+        assert_eq!(
+            calendar.shift_periods_opt(start, 2),
+            tz.with_ymd_and_hms(2024, 4, 14, 2, 30, 0).single()
+        );
+    }
+}
@@ -4,7 +4,11 @@
 //! ChronoUtil provides the following utilities:
 //! - `RelativeDuration`: extending Chrono's `Duration` to add months and years
 //! - `DateRule`: useful iterators yielding regular (e.g. monthly) dates
+//! - `AnchoredDate`: dates which remember their day of the month across repeated shifts
+//! - `RelativeDate`: parsing of English phrases such as "3 weeks ago" or "next friday"
+//! - `Tenor`: market tenor codes such as `ON`, `1W` or `18M`
 //! - Procedural helper functions for shifting datelike values by months and years
+//! - Helper functions for the start and end of weeks, months, quarters and years
 //!
 //! It is heavily inspired by Python's [dateutil](https://github.com/dateutil/dateutil)
 //! and provides a similar API, but with less of the niche functionality.
@@ -61,15 +65,38 @@
 //! ChronoUtil also exposes useful shift functions which are used internally, namely:
 //!
 //! - [shift_months] to shift a datelike value by a given number of months
+//! - [delta::shift_months_by_weekday] to shift by months keeping the weekday position
 //! - [shift_years] to shift a datelike value by a given number of years
-//! - [with_day] to shift a datelike value to a given day
+//! - [with_day] to shift a datelike value to a given day, counting back from the end of the
+//!   month for negative days
 //! - [with_month] to shift a datelike value to a given month
 //! - [with_year] to shift a datelike value to a given year
+//! - [nth_weekday_of_month] to find e.g. the 3rd Wednesday or last Friday of a month
+//! - [weekday_ordinal_in_month] to find which occurrence of its weekday a date is
+//! - [shift_iso_weeks], [with_iso_week] and [iso_weeks_in_year] for ISO week-date arithmetic
+//!
+//! The [delta] module also has adjusters such as [delta::next_weekday] and
+//! [delta::next_time_of_day] to move to the next, previous or nearest weekday or time of day.
+//!
+//! The date of Easter Sunday, which most moveable feasts are relative to, is given by
+//! [easter] using the Western, Orthodox or Julian method.
+//!
+//! The [boundary] module has functions such as [boundary::start_of_month] and
+//! [boundary::end_of_quarter] to find the first and last instants of calendar periods, and
+//! the [fiscal] module does the same for fiscal years, quarters and periods starting on any
+//! day of the year, and for week-based 4-4-5 retail calendars.
+//!
+//! The [week] module has ISO, US, CDC MMWR and day-of-year week numberings, which convert
+//! dates to and from a week-year, week and weekday, and which a [DateRule] can step through
+//! with [DateRule::numbered_weeks] or, wrapped in [week::Weeks], keep from year to year.
 //!
 //! ## Design decisions and gotchas
 //!
-//! We favour simplicity over complexity: we use only the Gregorian calendar and
-//! make no changes e.g. for dates before the 1500s.
+//! We favour simplicity over complexity: by default we use only the proleptic Gregorian
+//! calendar and make no changes e.g. for dates before the 1500s. The [calendar] module has
+//! Julian and Julian-to-Gregorian calendars for month and year arithmetic on historical
+//! dates, and tabular Hijri and Hebrew calendars, which a [DateRule] can use with
+//! [DateRule::with_calendar].
 //!
 //! For days between the 1st and 28th, shifting by months has an obvious
 //! unambiguous meaning which we always stick to. One month after Jan 28th is
@@ -122,14 +149,38 @@
 //! assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
 //! assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 3, 31).unwrap());
 //! ```
+//!
+//! When shifts are chained one at a time, an [AnchoredDate] remembers the original day of
+//! the month and moves back onto it after every shift.
 
 extern crate chrono;
 
+// Used by the macros in chronoutil-macros to refer to chrono types
+#[doc(hidden)]
+pub mod __private {
+    pub use chrono::{Duration, NaiveDate};
+}
+
+pub mod anchored;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod boundary;
+pub mod calendar;
+#[cfg(feature = "clap")]
+pub mod clap;
 pub mod delta;
+pub mod fiscal;
+pub mod natural;
 pub mod relative_duration;
 pub mod rule;
+pub mod tenor;
+pub mod week;
 
+pub use anchored::AnchoredDate;
 pub use relative_duration::RelativeDuration;
 pub use rule::DateRule;
 // Utility functions may be useful for others
-pub use delta::{is_leap_year, shift_months, shift_years, with_day, with_month, with_year};
+pub use delta::{
+    easter, is_leap_year, iso_weeks_in_year, nth_weekday_of_month, shift_iso_weeks, shift_months,
+    shift_years, weekday_ordinal_in_month, with_day, with_iso_week, with_month, with_year,
+};
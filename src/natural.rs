@@ -0,0 +1,915 @@
+//! Parsing of English relative date phrases such as "3 weeks ago" or "start of next month".
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Weekday};
+
+use crate::boundary::{
+    end_of_day_opt, end_of_month_opt, end_of_quarter_opt, end_of_week_opt, end_of_year_opt,
+    start_of_day_opt, start_of_month_opt, start_of_quarter_opt, start_of_week_opt,
+    start_of_year_opt, DayBounds,
+};
+use crate::delta::{next_weekday, previous_weekday, shift_months_opt};
+use crate::RelativeDuration;
+
+/// A calendar period which a [`RelativeDate`] can be anchored to the start or end of.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Period {
+    /// A calendar day.
+    Day,
+    /// An ISO week, from Monday to Sunday.
+    Week,
+    /// A calendar month.
+    Month,
+    /// A calendar quarter, starting in January, April, July or October.
+    Quarter,
+    /// A calendar year.
+    Year,
+}
+
+/// An adjustment applied to a date after it has been shifted by a [`RelativeDate`]'s offset.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Anchor {
+    /// The first given weekday strictly after the date, as in "next friday".
+    Next(Weekday),
+    /// The last given weekday strictly before the date, as in "last monday".
+    Last(Weekday),
+    /// The given weekday in the same ISO week as the date, as in "this friday" or "friday".
+    This(Weekday),
+    /// Midnight at the start of the period containing the date, as in "start of next month".
+    StartOf(Period),
+    /// The last nanosecond of the period containing the date, as in "end of last year".
+    EndOf(Period),
+}
+
+/// The ways in which parsing a [`RelativeDate`] can fail.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ParseErrorKind {
+    /// The input contained no words.
+    Empty,
+    /// A word was not understood in its position.
+    UnexpectedToken,
+    /// The input ended where more words were needed.
+    UnexpectedEnd,
+    /// The amount of time does not fit in a [`RelativeDuration`].
+    Overflow,
+}
+
+/// An error from parsing a [`RelativeDate`], pointing at the word which could not be parsed.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: usize,
+    token: String,
+    expected: &'static str,
+}
+
+impl ParseError {
+    /// The kind of error.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The byte offset of the offending word in the input, or the length of the input if it
+    /// ended early.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The offending word, which is empty if the input ended early.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// A description of what was expected in place of the offending word.
+    pub fn expected(&self) -> &str {
+        self.expected
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "expected {}, found empty input", self.expected),
+            ParseErrorKind::UnexpectedToken => write!(
+                f,
+                "expected {}, found \"{}\" at position {}",
+                self.expected, self.token, self.position
+            ),
+            ParseErrorKind::UnexpectedEnd => {
+                write!(f, "expected {} at the end of the input", self.expected)
+            }
+            ParseErrorKind::Overflow => write!(
+                f,
+                "\"{}\" at position {} overflows a RelativeDuration",
+                self.token, self.position
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Fortnight,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+}
+
+impl Unit {
+    fn parse(word: &str) -> Option<Unit> {
+        Some(match word {
+            "second" | "seconds" | "sec" | "secs" => Unit::Second,
+            "minute" | "minutes" | "min" | "mins" => Unit::Minute,
+            "hour" | "hours" | "hr" | "hrs" => Unit::Hour,
+            "day" | "days" => Unit::Day,
+            "week" | "weeks" => Unit::Week,
+            "fortnight" | "fortnights" => Unit::Fortnight,
+            "month" | "months" => Unit::Month,
+            "quarter" | "quarters" => Unit::Quarter,
+            "year" | "years" => Unit::Year,
+            "decade" | "decades" => Unit::Decade,
+            _ => return None,
+        })
+    }
+
+    fn period(self) -> Option<Period> {
+        match self {
+            Unit::Day => Some(Period::Day),
+            Unit::Week => Some(Period::Week),
+            Unit::Month => Some(Period::Month),
+            Unit::Quarter => Some(Period::Quarter),
+            Unit::Year => Some(Period::Year),
+            _ => None,
+        }
+    }
+
+    fn times(self, count: i64) -> Option<RelativeDuration> {
+        let months = |factor: i64| {
+            count
+                .checked_mul(factor)?
+                .try_into()
+                .ok()
+                .map(RelativeDuration::months)
+        };
+        let seconds = |factor: i64| {
+            count
+                .checked_mul(factor)
+                .and_then(Duration::try_seconds)
+                .map(RelativeDuration::from)
+        };
+        match self {
+            Unit::Second => seconds(1),
+            Unit::Minute => seconds(60),
+            Unit::Hour => seconds(60 * 60),
+            Unit::Day => seconds(24 * 60 * 60),
+            Unit::Week => seconds(7 * 24 * 60 * 60),
+            Unit::Fortnight => seconds(14 * 24 * 60 * 60),
+            Unit::Month => months(1),
+            Unit::Quarter => months(3),
+            Unit::Year => months(12),
+            Unit::Decade => months(120),
+        }
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    Some(match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+// An unsigned count in digits, or None if the word is not one or it overflows
+fn parse_digits(word: &str) -> Option<i64> {
+    if !word.is_empty() && word.bytes().all(|b| b.is_ascii_digit()) {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_count(word: &str) -> Option<i64> {
+    const WORDS: [&str; 12] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+        "twelve",
+    ];
+    match word {
+        "a" | "an" => Some(1),
+        _ => WORDS.iter().position(|w| *w == word).map(|i| i as i64 + 1),
+    }
+}
+
+// A lowercased word of the input, along with its byte range
+struct Token {
+    word: String,
+    position: usize,
+    end: usize,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        let mut tokens = vec![];
+        let mut start = None;
+        for (i, c) in input
+            .char_indices()
+            .chain(std::iter::once((input.len(), ' ')))
+        {
+            let separator = c.is_whitespace() || c == ',';
+            match (start, separator) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    tokens.push(Token {
+                        word: input[s..i].to_lowercase(),
+                        position: s,
+                        end: i,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        Parser {
+            input,
+            tokens,
+            index: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.index).map(|t| t.word.as_str())
+    }
+
+    fn next(&mut self) -> Option<&str> {
+        self.index += 1;
+        self.tokens.get(self.index - 1).map(|t| t.word.as_str())
+    }
+
+    // An error at the given token index, or at the end of the input
+    fn error_at(&self, index: usize, kind: ParseErrorKind, expected: &'static str) -> ParseError {
+        match self.tokens.get(index) {
+            Some(token) => ParseError {
+                kind,
+                position: token.position,
+                token: self.input[token.position..token.end].to_string(),
+                expected,
+            },
+            None => ParseError {
+                kind: if self.tokens.is_empty() {
+                    ParseErrorKind::Empty
+                } else {
+                    ParseErrorKind::UnexpectedEnd
+                },
+                position: self.input.len(),
+                token: String::new(),
+                expected,
+            },
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        self.error_at(self.index, ParseErrorKind::UnexpectedToken, expected)
+    }
+
+    fn expect_end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("the end of the input")),
+        }
+    }
+
+    fn unit(&mut self) -> Result<Unit, ParseError> {
+        match self.peek().and_then(Unit::parse) {
+            Some(unit) => {
+                self.next();
+                Ok(unit)
+            }
+            None => Err(self.unexpected("a unit of time")),
+        }
+    }
+
+    // Parses one or more amounts, such as "1 year, 2 months and 3 days"
+    fn amounts(&mut self) -> Result<RelativeDuration, ParseError> {
+        let mut total = RelativeDuration::zero();
+        loop {
+            let start = self.index;
+            let count = match self.peek() {
+                Some(word) => match parse_digits(word).or_else(|| parse_count(word)) {
+                    Some(count) => count,
+                    None if !word.is_empty() && word.bytes().all(|b| b.is_ascii_digit()) => {
+                        return Err(self.error_at(start, ParseErrorKind::Overflow, "a number"));
+                    }
+                    None => return Err(self.unexpected("a number")),
+                },
+                None => return Err(self.unexpected("a number")),
+            };
+            self.next();
+            let amount = self
+                .unit()?
+                .times(count)
+                .ok_or_else(|| self.error_at(start, ParseErrorKind::Overflow, "a number"))?;
+            total = checked_add(total, amount)
+                .ok_or_else(|| self.error_at(start, ParseErrorKind::Overflow, "a number"))?;
+
+            if self.peek() == Some("and") {
+                self.next();
+            } else if !matches!(
+                self.peek(),
+                Some(word) if parse_digits(word).is_some() || parse_count(word).is_some()
+            ) {
+                return Ok(total);
+            }
+        }
+    }
+
+    fn parse(&mut self) -> Result<RelativeDate, ParseError> {
+        let first = match self.peek() {
+            Some(word) => word.to_string(),
+            None => return Err(self.unexpected("a relative date")),
+        };
+
+        let date = match first.as_str() {
+            "now" | "today" => {
+                self.next();
+                RelativeDate::new(RelativeDuration::zero())
+            }
+            "tomorrow" => {
+                self.next();
+                RelativeDate::new(RelativeDuration::days(1))
+            }
+            "yesterday" => {
+                self.next();
+                RelativeDate::new(RelativeDuration::days(-1))
+            }
+            "start" | "beginning" | "end" => {
+                self.next();
+                if self.peek() == Some("of") {
+                    self.next();
+                }
+                let sign = match self.peek() {
+                    Some("next") => 1,
+                    Some("last") => -1,
+                    Some("this") | Some("the") => 0,
+                    _ => return Err(self.unexpected("\"next\", \"last\", \"this\" or \"the\"")),
+                };
+                self.next();
+                let position = self.index;
+                let period = self.unit()?.period().ok_or_else(|| {
+                    self.error_at(
+                        position,
+                        ParseErrorKind::UnexpectedToken,
+                        "a day, week, month, quarter or year",
+                    )
+                })?;
+                let offset = Unit::parse(&self.tokens[position].word)
+                    .and_then(|unit| unit.times(sign))
+                    .unwrap();
+                RelativeDate {
+                    offset,
+                    anchor: Some(if first == "end" {
+                        Anchor::EndOf(period)
+                    } else {
+                        Anchor::StartOf(period)
+                    }),
+                }
+            }
+            "next" | "last" | "this" => {
+                self.next();
+                let word = self.peek().map(str::to_string);
+                match word.as_deref().and_then(parse_weekday) {
+                    Some(weekday) => {
+                        self.next();
+                        RelativeDate {
+                            offset: RelativeDuration::zero(),
+                            anchor: Some(match first.as_str() {
+                                "next" => Anchor::Next(weekday),
+                                "last" => Anchor::Last(weekday),
+                                _ => Anchor::This(weekday),
+                            }),
+                        }
+                    }
+                    None => {
+                        let sign = match first.as_str() {
+                            "next" => 1,
+                            "last" => -1,
+                            _ => 0,
+                        };
+                        // A single unit never overflows
+                        RelativeDate::new(self.unit()?.times(sign).unwrap())
+                    }
+                }
+            }
+            "in" => {
+                self.next();
+                RelativeDate::new(self.amounts()?)
+            }
+            word => match parse_weekday(word) {
+                Some(weekday) => {
+                    self.next();
+                    RelativeDate {
+                        offset: RelativeDuration::zero(),
+                        anchor: Some(Anchor::This(weekday)),
+                    }
+                }
+                None => {
+                    let amounts = self.amounts()?;
+                    let direction = self.index;
+                    let offset = match self.next() {
+                        Some("ago") | Some("earlier") => -amounts,
+                        Some("later") | Some("hence") => amounts,
+                        Some("from") => match self.next() {
+                            Some("now") | Some("today") => amounts,
+                            _ => {
+                                return Err(self.error_at(
+                                    direction + 1,
+                                    ParseErrorKind::UnexpectedToken,
+                                    "\"now\" or \"today\"",
+                                ))
+                            }
+                        },
+                        _ => {
+                            return Err(self.error_at(
+                                direction,
+                                ParseErrorKind::UnexpectedToken,
+                                "\"ago\", \"from now\", \"later\" or another unit of time",
+                            ))
+                        }
+                    };
+                    RelativeDate::new(offset)
+                }
+            },
+        };
+
+        self.expect_end()?;
+        Ok(date)
+    }
+}
+
+fn checked_add(lhs: RelativeDuration, rhs: RelativeDuration) -> Option<RelativeDuration> {
+    Some(
+        RelativeDuration::months(lhs.months.checked_add(rhs.months)?)
+            .with_duration(lhs.duration.checked_add(&rhs.duration)?),
+    )
+}
+
+fn start_of<D: DayBounds>(datetime: D, period: Period) -> Option<D> {
+    match period {
+        Period::Day => start_of_day_opt(datetime),
+        Period::Week => start_of_week_opt(datetime, Weekday::Mon),
+        Period::Month => start_of_month_opt(datetime),
+        Period::Quarter => start_of_quarter_opt(datetime),
+        Period::Year => start_of_year_opt(datetime),
+    }
+}
+
+fn end_of<D: DayBounds>(datetime: D, period: Period) -> Option<D> {
+    match period {
+        Period::Day => end_of_day_opt(datetime),
+        Period::Week => end_of_week_opt(datetime, Weekday::Mon),
+        Period::Month => end_of_month_opt(datetime),
+        Period::Quarter => end_of_quarter_opt(datetime),
+        Period::Year => end_of_year_opt(datetime),
+    }
+}
+
+fn apply_anchor<D: DayBounds>(datetime: D, anchor: Anchor) -> Option<D> {
+    match anchor {
+        Anchor::Next(weekday) => next_weekday(datetime, weekday, false),
+        Anchor::Last(weekday) => previous_weekday(datetime, weekday, false),
+        Anchor::This(weekday) => {
+            let monday = previous_weekday(datetime, Weekday::Mon, true)?;
+            next_weekday(monday, weekday, true)
+        }
+        Anchor::StartOf(period) => start_of(datetime, period),
+        Anchor::EndOf(period) => end_of(datetime, period),
+    }
+}
+
+/// A date relative to some reference point in time, as parsed from an English phrase.
+///
+/// A `RelativeDate` is a [`RelativeDuration`] offset, optionally followed by an [`Anchor`] to a
+/// weekday or to the start or end of a calendar period. The following phrases are understood,
+/// case-insensitively:
+///
+/// - `now`, `today`, `tomorrow` and `yesterday`
+/// - amounts of time in the future or past, such as `in 10 days`, `2 months ago`,
+///   `a fortnight from now`, `1 year and 2 months later` or `three weeks earlier`
+/// - `next`, `last` or `this` followed by a unit of time, such as `next quarter` or `last year`
+/// - `next`, `last` or `this` followed by a weekday, such as `next friday`, or a bare weekday
+/// - `start of`, `beginning of` or `end of` followed by `next`, `last`, `this` or `the` and a day,
+///   week, month, quarter or year, such as `start of next month` or `end of the year`
+///
+/// Amounts may be unsigned numbers, `a` or `an`, or the words `one` to `twelve`, and the units
+/// are seconds, minutes, hours, days, weeks, fortnights, months, quarters, years and decades.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use chronoutil::natural::RelativeDate;
+///
+/// let reference = NaiveDate::from_ymd_opt(2024, 1, 31)
+///     .unwrap()
+///     .and_hms_opt(9, 30, 0)
+///     .unwrap();
+///
+/// let in_a_month: RelativeDate = "in a month".parse().unwrap();
+/// assert_eq!(
+///     in_a_month.resolve(reference),
+///     NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_opt(9, 30, 0)
+/// );
+///
+/// let next_quarter = RelativeDate::parse("start of next quarter").unwrap();
+/// assert_eq!(
+///     next_quarter.resolve(reference),
+///     NaiveDate::from_ymd_opt(2024, 4, 1).unwrap().and_hms_opt(0, 0, 0)
+/// );
+///
+/// let error = RelativeDate::parse("3 weks ago").unwrap_err();
+/// assert_eq!(error.token(), "weks");
+/// assert_eq!(error.position(), 2);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct RelativeDate {
+    offset: RelativeDuration,
+    anchor: Option<Anchor>,
+}
+
+impl RelativeDate {
+    /// Creates a `RelativeDate` from an offset, with no anchor.
+    pub fn new(offset: RelativeDuration) -> Self {
+        RelativeDate {
+            offset,
+            anchor: None,
+        }
+    }
+
+    /// Sets the anchor applied after shifting by the offset.
+    pub fn with_anchor(self, anchor: Anchor) -> Self {
+        RelativeDate {
+            anchor: Some(anchor),
+            ..self
+        }
+    }
+
+    /// Parses an English phrase into a `RelativeDate`. See [`RelativeDate`] for the phrases
+    /// understood.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] pointing at the first word which could not be understood.
+    pub fn parse(input: &str) -> Result<RelativeDate, ParseError> {
+        Parser::new(input).parse()
+    }
+
+    /// The offset from the reference point in time.
+    pub fn offset(&self) -> RelativeDuration {
+        self.offset
+    }
+
+    /// The anchor applied after shifting by the offset, if any.
+    pub fn anchor(&self) -> Option<Anchor> {
+        self.anchor
+    }
+
+    /// Resolves the date against a reference point in time.
+    ///
+    /// The reference is shifted by the offset with the same rules as adding a
+    /// [`RelativeDuration`], so "in a month" from the 31st of January is the 29th of February in
+    /// a leap year. Weekday anchors keep the time of day. Returns `None` if the result is out of
+    /// range.
+    pub fn resolve(&self, reference: NaiveDateTime) -> Option<NaiveDateTime> {
+        let shifted = shift_months_opt(reference, self.offset.months)?
+            .checked_add_signed(self.offset.duration)?;
+        match self.anchor {
+            Some(anchor) => apply_anchor(shifted, anchor),
+            None => Some(shifted),
+        }
+    }
+
+    /// As [`RelativeDate::resolve`], for a time-zone aware reference.
+    ///
+    /// Anchors are applied to the local time. The start and end of a period are its first and
+    /// last instants, as in the [`boundary`](crate::boundary) module, even when local midnight
+    /// is skipped by a daylight saving transition. Returns `None` if the result is out of range,
+    /// or if a weekday anchor moves onto a local time which is ambiguous or does not exist in
+    /// the time zone.
+    pub fn resolve_datetime<Tz: TimeZone>(&self, reference: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let shifted = shift_months_opt(reference.clone(), self.offset.months)?
+            .checked_add_signed(self.offset.duration)?;
+        match self.anchor {
+            Some(anchor) => apply_anchor(shifted, anchor),
+            None => Some(shifted),
+        }
+    }
+}
+
+impl From<RelativeDuration> for RelativeDate {
+    fn from(offset: RelativeDuration) -> Self {
+        RelativeDate::new(offset)
+    }
+}
+
+impl FromStr for RelativeDate {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        RelativeDate::parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn reference() -> NaiveDateTime {
+        // A Wednesday
+        NaiveDate::from_ymd_opt(2024, 1, 31)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap()
+    }
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, mi: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, mi, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_offsets() {
+        [
+            ("now", RelativeDuration::zero()),
+            ("Today", RelativeDuration::zero()),
+            ("tomorrow", RelativeDuration::days(1)),
+            ("yesterday", RelativeDuration::days(-1)),
+            ("2 months ago", RelativeDuration::months(-2)),
+            ("in 10 days", RelativeDuration::days(10)),
+            ("last year", RelativeDuration::years(-1)),
+            ("next quarter", RelativeDuration::months(3)),
+            ("this week", RelativeDuration::zero()),
+            ("a fortnight from now", RelativeDuration::weeks(2)),
+            ("an hour later", RelativeDuration::hours(1)),
+            ("three weeks earlier", RelativeDuration::weeks(-3)),
+            ("2 decades hence", RelativeDuration::years(20)),
+            (
+                "in 1 year, 2 months and 3 days",
+                RelativeDuration::months(14) + RelativeDuration::days(3),
+            ),
+            (
+                "  1 HOUR 30 mins ago ",
+                -(RelativeDuration::hours(1) + RelativeDuration::minutes(30)),
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            assert_eq!(
+                RelativeDate::parse(input).unwrap(),
+                RelativeDate::new(*expected),
+                "{}",
+                input
+            )
+        })
+    }
+
+    #[test]
+    fn test_parse_anchors() {
+        [
+            (
+                "next friday",
+                RelativeDuration::zero(),
+                Anchor::Next(Weekday::Fri),
+            ),
+            (
+                "last mon",
+                RelativeDuration::zero(),
+                Anchor::Last(Weekday::Mon),
+            ),
+            (
+                "this sunday",
+                RelativeDuration::zero(),
+                Anchor::This(Weekday::Sun),
+            ),
+            (
+                "Tuesday",
+                RelativeDuration::zero(),
+                Anchor::This(Weekday::Tue),
+            ),
+            (
+                "start of next month",
+                RelativeDuration::months(1),
+                Anchor::StartOf(Period::Month),
+            ),
+            (
+                "end of last quarter",
+                RelativeDuration::months(-3),
+                Anchor::EndOf(Period::Quarter),
+            ),
+            (
+                "beginning of the week",
+                RelativeDuration::zero(),
+                Anchor::StartOf(Period::Week),
+            ),
+            (
+                "end this year",
+                RelativeDuration::zero(),
+                Anchor::EndOf(Period::Year),
+            ),
+        ]
+        .iter()
+        .for_each(|(input, offset, anchor)| {
+            assert_eq!(
+                RelativeDate::parse(input).unwrap(),
+                RelativeDate::new(*offset).with_anchor(*anchor),
+                "{}",
+                input
+            )
+        })
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        [
+            ("", ParseErrorKind::Empty, 0, ""),
+            ("   ", ParseErrorKind::Empty, 3, ""),
+            ("3 weks ago", ParseErrorKind::UnexpectedToken, 2, "weks"),
+            ("in 3 days ago", ParseErrorKind::UnexpectedToken, 10, "ago"),
+            ("3 days", ParseErrorKind::UnexpectedEnd, 6, ""),
+            (
+                "3 days from Monday",
+                ParseErrorKind::UnexpectedToken,
+                12,
+                "Monday",
+            ),
+            ("soon", ParseErrorKind::UnexpectedToken, 0, "soon"),
+            ("next", ParseErrorKind::UnexpectedEnd, 4, ""),
+            (
+                "end of next hour",
+                ParseErrorKind::UnexpectedToken,
+                12,
+                "hour",
+            ),
+            (
+                "start of month",
+                ParseErrorKind::UnexpectedToken,
+                9,
+                "month",
+            ),
+            (
+                "in 3000000000 months",
+                ParseErrorKind::Overflow,
+                3,
+                "3000000000",
+            ),
+            (
+                "in 99999999999999999999 days",
+                ParseErrorKind::Overflow,
+                3,
+                "99999999999999999999",
+            ),
+            ("in -3 days", ParseErrorKind::UnexpectedToken, 3, "-3"),
+            ("+3 days ago", ParseErrorKind::UnexpectedToken, 0, "+3"),
+            (
+                "3 days and -2 hours ago",
+                ParseErrorKind::UnexpectedToken,
+                11,
+                "-2",
+            ),
+            (
+                "tomorrow morning",
+                ParseErrorKind::UnexpectedToken,
+                9,
+                "morning",
+            ),
+        ]
+        .iter()
+        .for_each(|(input, kind, position, token)| {
+            let error = RelativeDate::parse(input).unwrap_err();
+            assert_eq!(error.kind(), *kind, "{}", input);
+            assert_eq!(error.position(), *position, "{}", input);
+            assert_eq!(error.token(), *token, "{}", input);
+        });
+
+        assert_eq!(
+            RelativeDate::parse("3 weks ago").unwrap_err().to_string(),
+            "expected a unit of time, found \"weks\" at position 2"
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        [
+            ("now", datetime(2024, 1, 31, 9, 30, 0)),
+            ("in a month", datetime(2024, 2, 29, 9, 30, 0)),
+            ("2 months ago", datetime(2023, 11, 30, 9, 30, 0)),
+            ("tomorrow", datetime(2024, 2, 1, 9, 30, 0)),
+            ("next wednesday", datetime(2024, 2, 7, 9, 30, 0)),
+            ("next friday", datetime(2024, 2, 2, 9, 30, 0)),
+            ("last wednesday", datetime(2024, 1, 24, 9, 30, 0)),
+            ("last monday", datetime(2024, 1, 29, 9, 30, 0)),
+            ("this sunday", datetime(2024, 2, 4, 9, 30, 0)),
+            ("start of the week", datetime(2024, 1, 29, 0, 0, 0)),
+            ("start of next month", datetime(2024, 2, 1, 0, 0, 0)),
+            ("start of next quarter", datetime(2024, 4, 1, 0, 0, 0)),
+            ("start of last year", datetime(2023, 1, 1, 0, 0, 0)),
+            ("start of the day", datetime(2024, 1, 31, 0, 0, 0)),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            assert_eq!(
+                RelativeDate::parse(input).unwrap().resolve(reference()),
+                Some(*expected),
+                "{}",
+                input
+            )
+        });
+
+        assert_eq!(
+            RelativeDate::parse("end of next month")
+                .unwrap()
+                .resolve(reference()),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_nano_opt(23, 59, 59, 999_999_999)
+        );
+        assert_eq!(
+            RelativeDate::parse("in 10 decades")
+                .unwrap()
+                .resolve(NaiveDateTime::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_datetime() {
+        use chrono::FixedOffset;
+
+        let tz = FixedOffset::east_opt(5 * 3600).unwrap();
+        let reference = tz.from_local_datetime(&reference()).unwrap();
+        assert_eq!(
+            RelativeDate::parse("start of next month")
+                .unwrap()
+                .resolve_datetime(&reference),
+            tz.from_local_datetime(&datetime(2024, 2, 1, 0, 0, 0))
+                .single()
+        );
+        assert_eq!(
+            RelativeDate::parse("3 hours ago")
+                .unwrap()
+                .resolve_datetime(&reference),
+            tz.from_local_datetime(&datetime(2024, 1, 31, 6, 30, 0))
+                .single()
+        );
+
+        // Clocks go forward from 00:00 to 01:00 on 4th November 2018 in Sao Paulo
+        let tz = &chrono_tz::America::Sao_Paulo;
+        let reference = tz.with_ymd_and_hms(2018, 11, 4, 10, 0, 0).unwrap();
+        assert_eq!(
+            RelativeDate::parse("start of the day")
+                .unwrap()
+                .resolve_datetime(&reference),
+            tz.with_ymd_and_hms(2018, 11, 4, 1, 0, 0).single()
+        );
+        assert_eq!(
+            RelativeDate::parse("end of last day")
+                .unwrap()
+                .resolve_datetime(&reference),
+            Some(crate::boundary::end_of_day(
+                tz.with_ymd_and_hms(2018, 11, 3, 10, 0, 0).unwrap()
+            ))
+        );
+        // Weekday anchors keep the time of day, which is skipped a week later
+        let reference = tz.with_ymd_and_hms(2018, 10, 28, 0, 30, 0).unwrap();
+        assert_eq!(
+            RelativeDate::parse("next sunday")
+                .unwrap()
+                .resolve_datetime(&reference),
+            None
+        );
+    }
+}
@@ -6,13 +6,31 @@ use chrono::{Date, DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone};
 
 use super::delta::shift_months;
 
+#[cfg(feature = "apache-avro")]
+pub use avro::serde_avro_duration;
+pub use humanize::{Humanizer, Locale};
+pub use pattern::DurationFormat;
+#[cfg(feature = "serde")]
+pub use serde_impls::{serde_compact, serde_structured};
+
+mod avro;
+mod humanize;
 mod parse;
+mod pattern;
+#[cfg(any(feature = "postgres-types", feature = "sqlx"))]
+mod postgres;
+mod rfc5545;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod xsd;
 
 /// Relative time duration extending Chrono's Duration.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub struct RelativeDuration {
-    months: i32, // Sorry, cosmologists..
-    duration: Duration,
+    pub(crate) months: i32, // Sorry, cosmologists..
+    pub(crate) duration: Duration,
 }
 
 impl From<Duration> for RelativeDuration {
@@ -52,7 +70,7 @@ impl RelativeDuration {
     /// Makes a new `RelativeDuration` with given number of months.
     /// Panics when the duration is out of bounds.
     #[inline]
-    pub fn months(months: i32) -> RelativeDuration {
+    pub const fn months(months: i32) -> RelativeDuration {
         RelativeDuration {
             months,
             duration: Duration::zero(),
@@ -138,7 +156,7 @@ impl RelativeDuration {
 
     /// Update the `Duration` part of the current `RelativeDuration`.
     #[inline]
-    pub fn with_duration(self, duration: Duration) -> RelativeDuration {
+    pub const fn with_duration(self, duration: Duration) -> RelativeDuration {
         RelativeDuration {
             months: self.months,
             duration,
@@ -147,13 +165,25 @@ impl RelativeDuration {
 
     /// A `RelativeDuration` representing zero.
     #[inline]
-    pub fn zero() -> RelativeDuration {
+    pub const fn zero() -> RelativeDuration {
         RelativeDuration {
             months: 0,
             duration: Duration::zero(),
         }
     }
 
+    /// Returns the number of months in the `RelativeDuration`.
+    #[inline]
+    pub const fn num_months(&self) -> i32 {
+        self.months
+    }
+
+    /// Returns the `Duration` part of the `RelativeDuration`.
+    #[inline]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
     /// Returns true if the duration equals RelativeDuration::zero().
     #[inline]
     pub fn is_zero(&self) -> bool {
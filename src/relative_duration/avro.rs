@@ -0,0 +1,234 @@
+//! Conversions between `RelativeDuration` and the 12 byte `duration` of Avro and `INTERVAL` of
+//! Parquet.
+//!
+//! Both store unsigned months, days and milliseconds. Days are read as exactly 24 hours, and
+//! encoding fails for negative components or precision finer than a millisecond. With the
+//! `apache-avro` feature, a serde adapter stores `RelativeDuration` fields as Avro `duration`s.
+use super::RelativeDuration;
+use chrono::Duration;
+use std::convert::TryInto;
+
+impl RelativeDuration {
+    /// Decodes a [`RelativeDuration`] from the 12 byte layout shared by the Avro `duration`
+    /// logical type and the legacy Parquet `INTERVAL` type.
+    ///
+    /// The layout is three little-endian unsigned 32-bit integers: months, days and milliseconds.
+    /// Days are treated as exactly 24 hours.
+    ///
+    /// # Errors
+    ///
+    /// - The number of months does not fit in a [`RelativeDuration`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     RelativeDuration::from_avro_duration([1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]).unwrap(),
+    ///     RelativeDuration::months(1)
+    ///         + RelativeDuration::days(2)
+    ///         + RelativeDuration::milliseconds(3),
+    /// );
+    /// ```
+    pub fn from_avro_duration(bytes: [u8; 12]) -> Result<RelativeDuration, String> {
+        let months = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let days = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let millis = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+
+        let months = months
+            .try_into()
+            .map_err(|_| format!("{} months overflows a RelativeDuration", months))?;
+
+        Ok(RelativeDuration::months(months)
+            .with_duration(Duration::days(days.into()) + Duration::milliseconds(millis.into())))
+    }
+
+    /// Encodes a [`RelativeDuration`] into the 12 byte layout shared by the Avro `duration`
+    /// logical type and the legacy Parquet `INTERVAL` type.
+    ///
+    /// Every whole multiple of 24 hours is written into the days field, and the remainder into the
+    /// milliseconds field.
+    ///
+    /// # Errors
+    ///
+    /// - Negative months or `Duration`, as the layout is unsigned
+    /// - Sub-millisecond precision, which would be lost
+    /// - More days than fit in the days field
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     (RelativeDuration::months(1) + RelativeDuration::hours(49)).to_avro_duration(),
+    ///     Ok([1, 0, 0, 0, 2, 0, 0, 0, 128, 238, 54, 0]),
+    /// );
+    /// assert!(RelativeDuration::months(-1).to_avro_duration().is_err());
+    /// ```
+    pub fn to_avro_duration(&self) -> Result<[u8; 12], String> {
+        if self.months < 0 || self.duration < Duration::zero() {
+            return Err(format!(
+                "{} has negative components, which avro durations do not support",
+                self.format_to_iso8601()
+            ));
+        }
+
+        let days = self.duration.num_days();
+        let remainder = self.duration - Duration::days(days);
+        if remainder.subsec_nanos() % 1_000_000 != 0 {
+            return Err(format!(
+                "{} has sub-millisecond precision, which avro durations do not support",
+                self.format_to_iso8601()
+            ));
+        }
+
+        let days: u32 = days.try_into().map_err(|_| {
+            format!(
+                "{} overflows the days of an avro duration",
+                self.format_to_iso8601()
+            )
+        })?;
+        // The remainder is less than a day, so always fits
+        let millis = remainder.num_milliseconds() as u32;
+
+        let mut out = [0; 12];
+        out[0..4].copy_from_slice(&(self.months as u32).to_le_bytes());
+        out[4..8].copy_from_slice(&days.to_le_bytes());
+        out[8..12].copy_from_slice(&millis.to_le_bytes());
+        Ok(out)
+    }
+}
+
+/// Serde adapter storing a [`RelativeDuration`] as an Avro `duration`.
+///
+/// This module is intended to be used through the Serde `with` attribute, on a field whose
+/// schema is a 12 byte `fixed` with the `duration` logical type:
+///
+/// ```rust
+/// # use chronoutil::RelativeDuration;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Subscription {
+///     #[serde(with = "chronoutil::relative_duration::serde_avro_duration")]
+///     renewal: RelativeDuration,
+/// }
+/// ```
+#[cfg(feature = "apache-avro")]
+pub mod serde_avro_duration {
+    use serde::{de::Error as _, ser::Error as _, Deserializer, Serializer};
+
+    use super::RelativeDuration;
+
+    /// Serializes a [`RelativeDuration`] as a 12 byte Avro `fixed`.
+    pub fn serialize<S>(duration: &RelativeDuration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = duration.to_avro_duration().map_err(S::Error::custom)?;
+        apache_avro::serde_avro_fixed::serialize(&bytes, serializer)
+    }
+
+    /// Deserializes a [`RelativeDuration`] from a 12 byte Avro `fixed`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RelativeDuration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = apache_avro::serde_avro_fixed::deserialize::<_, 12>(deserializer)?;
+        RelativeDuration::from_avro_duration(bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avro_duration_round_trip() {
+        [
+            (RelativeDuration::zero(), [0; 12]),
+            (
+                RelativeDuration::years(1) + RelativeDuration::days(3) + RelativeDuration::hours(1),
+                [12, 0, 0, 0, 3, 0, 0, 0, 128, 238, 54, 0],
+            ),
+            (
+                RelativeDuration::months(i32::MAX)
+                    + RelativeDuration::days(u32::MAX.into())
+                    + RelativeDuration::milliseconds(24 * 60 * 60 * 1000 - 1),
+                [255, 255, 255, 127, 255, 255, 255, 255, 255, 91, 38, 5],
+            ),
+        ]
+        .iter()
+        .for_each(|(duration, bytes)| {
+            assert_eq!(duration.to_avro_duration().unwrap(), *bytes);
+            assert_eq!(
+                RelativeDuration::from_avro_duration(*bytes).unwrap(),
+                *duration
+            );
+        })
+    }
+
+    #[test]
+    fn test_avro_duration_errors() {
+        [
+            RelativeDuration::months(-1),
+            RelativeDuration::seconds(-1),
+            RelativeDuration::months(1) - RelativeDuration::days(1),
+            RelativeDuration::microseconds(1),
+            RelativeDuration::days(i64::from(u32::MAX) + 1),
+        ]
+        .iter()
+        .for_each(|duration| assert!(duration.to_avro_duration().is_err()));
+
+        assert!(
+            RelativeDuration::from_avro_duration([0, 0, 0, 128, 0, 0, 0, 0, 0, 0, 0, 0]).is_err()
+        );
+    }
+
+    #[cfg(feature = "apache-avro")]
+    #[test]
+    fn test_serde_avro_duration() {
+        use apache_avro::{from_value, to_value, types::Value, Schema};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Record {
+            #[serde(with = "super::serde_avro_duration")]
+            duration: RelativeDuration,
+        }
+
+        let schema = Schema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "Record",
+                "fields": [{
+                    "name": "duration",
+                    "type": {"type": "fixed", "name": "d", "size": 12, "logicalType": "duration"}
+                }]
+            }"#,
+        )
+        .unwrap();
+        let record = Record {
+            duration: RelativeDuration::months(2) + RelativeDuration::milliseconds(5),
+        };
+
+        let value = to_value(&record).unwrap();
+        let resolved = value.clone().resolve(&schema).unwrap();
+        assert_eq!(
+            resolved,
+            Value::Record(vec![(
+                "duration".to_string(),
+                Value::Duration(apache_avro::Duration::from([
+                    2, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0
+                ]))
+            )])
+        );
+        assert_eq!(from_value::<Record>(&value).unwrap(), record);
+
+        let invalid = Record {
+            duration: RelativeDuration::months(-2),
+        };
+        assert!(to_value(invalid).is_err());
+    }
+}
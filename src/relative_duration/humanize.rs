@@ -0,0 +1,607 @@
+use super::parse::Components;
+use super::RelativeDuration;
+
+const SECOND_NANOS: i128 = 1_000_000_000;
+const MINUTE_NANOS: i128 = 60 * SECOND_NANOS;
+const HOUR_NANOS: i128 = 60 * MINUTE_NANOS;
+const DAY_NANOS: i128 = 24 * HOUR_NANOS;
+const WEEK_NANOS: i128 = 7 * DAY_NANOS;
+// The mean Gregorian month, used only when rounding days into months
+const MONTH_NANOS: f64 = 146_097.0 / 4_800.0 * DAY_NANOS as f64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Size {
+    Months(i64),
+    Nanos(i128),
+}
+
+// Indices into the locale tables, along with the size of each unit
+const UNITS: [(usize, Size); 7] = [
+    (0, Size::Months(12)),
+    (1, Size::Months(1)),
+    (2, Size::Nanos(WEEK_NANOS)),
+    (3, Size::Nanos(DAY_NANOS)),
+    (4, Size::Nanos(HOUR_NANOS)),
+    (5, Size::Nanos(MINUTE_NANOS)),
+    (6, Size::Nanos(SECOND_NANOS)),
+];
+
+/// A table of words used to render a [`RelativeDuration`] in a given language.
+///
+/// English, French, German and Spanish are built in, and other languages can be supported by
+/// filling in a table of their own. Every field is rendered in some output, so a custom locale
+/// should fill in all of them rather than taking some from a built-in locale, which would mix
+/// languages.
+#[derive(Clone, Copy, Debug)]
+pub struct Locale {
+    /// Singular and plural names of years, months, weeks, days, hours, minutes and seconds.
+    pub units: [(&'static str, &'static str); 7],
+    /// As `units`, but for use inside the `future` and `past` phrases (which differ in languages
+    /// with grammatical case).
+    pub relative_units: [(&'static str, &'static str); 7],
+    /// Returns true if the given count takes the plural form.
+    pub plural: fn(u64) -> bool,
+    /// Placed between all but the last two items of a list, e.g. `", "`.
+    pub separator: &'static str,
+    /// Placed between the last two items of a list, e.g. `" and "`.
+    pub conjunction: &'static str,
+    /// The prefix and suffix for a positive duration relative to now, e.g. `("in ", "")`.
+    pub future: (&'static str, &'static str),
+    /// The prefix and suffix for a negative duration relative to now, e.g. `("", " ago")`.
+    pub past: (&'static str, &'static str),
+    /// Rendering of a zero duration relative to now.
+    pub now: &'static str,
+}
+
+const ENGLISH_UNITS: [(&str, &str); 7] = [
+    ("year", "years"),
+    ("month", "months"),
+    ("week", "weeks"),
+    ("day", "days"),
+    ("hour", "hours"),
+    ("minute", "minutes"),
+    ("second", "seconds"),
+];
+
+const FRENCH_UNITS: [(&str, &str); 7] = [
+    ("an", "ans"),
+    ("mois", "mois"),
+    ("semaine", "semaines"),
+    ("jour", "jours"),
+    ("heure", "heures"),
+    ("minute", "minutes"),
+    ("seconde", "secondes"),
+];
+
+const SPANISH_UNITS: [(&str, &str); 7] = [
+    ("año", "años"),
+    ("mes", "meses"),
+    ("semana", "semanas"),
+    ("día", "días"),
+    ("hora", "horas"),
+    ("minuto", "minutos"),
+    ("segundo", "segundos"),
+];
+
+impl Locale {
+    /// English, e.g. "1 year, 2 months and 3 days", "in 3 weeks" or "2 months ago".
+    pub const ENGLISH: Locale = Locale {
+        units: ENGLISH_UNITS,
+        relative_units: ENGLISH_UNITS,
+        plural: |n| n != 1,
+        separator: ", ",
+        conjunction: " and ",
+        future: ("in ", ""),
+        past: ("", " ago"),
+        now: "now",
+    };
+
+    /// French, e.g. "1 an, 2 mois et 3 jours", "dans 3 semaines" or "il y a 2 mois".
+    pub const FRENCH: Locale = Locale {
+        units: FRENCH_UNITS,
+        relative_units: FRENCH_UNITS,
+        plural: |n| n > 1,
+        separator: ", ",
+        conjunction: " et ",
+        future: ("dans ", ""),
+        past: ("il y a ", ""),
+        now: "maintenant",
+    };
+
+    /// German, e.g. "1 Jahr, 2 Monate und 3 Tage", "in 3 Wochen" or "vor 2 Monaten".
+    pub const GERMAN: Locale = Locale {
+        units: [
+            ("Jahr", "Jahre"),
+            ("Monat", "Monate"),
+            ("Woche", "Wochen"),
+            ("Tag", "Tage"),
+            ("Stunde", "Stunden"),
+            ("Minute", "Minuten"),
+            ("Sekunde", "Sekunden"),
+        ],
+        // "in" and "vor" both take the dative
+        relative_units: [
+            ("Jahr", "Jahren"),
+            ("Monat", "Monaten"),
+            ("Woche", "Wochen"),
+            ("Tag", "Tagen"),
+            ("Stunde", "Stunden"),
+            ("Minute", "Minuten"),
+            ("Sekunde", "Sekunden"),
+        ],
+        plural: |n| n != 1,
+        separator: ", ",
+        conjunction: " und ",
+        future: ("in ", ""),
+        past: ("vor ", ""),
+        now: "jetzt",
+    };
+
+    /// Spanish, e.g. "1 año, 2 meses y 3 días", "dentro de 3 semanas" or "hace 2 meses".
+    pub const SPANISH: Locale = Locale {
+        units: SPANISH_UNITS,
+        relative_units: SPANISH_UNITS,
+        plural: |n| n != 1,
+        separator: ", ",
+        conjunction: " y ",
+        future: ("dentro de ", ""),
+        past: ("hace ", ""),
+        now: "ahora",
+    };
+}
+
+/// Renders [`RelativeDuration`]s as human readable text, such as "1 year, 2 months and 3 days",
+/// "in 3 weeks" or "2 months ago".
+///
+/// Months are broken down into years and months, and the `Duration` into weeks, days, hours,
+/// minutes and seconds. Sub-second parts are rounded to the nearest second.
+///
+/// For example:
+/// ```rust
+/// # use chronoutil::RelativeDuration;
+/// # use chronoutil::relative_duration::{Humanizer, Locale};
+/// let delta = RelativeDuration::months(14) + RelativeDuration::days(3);
+/// let humanizer = Humanizer::new(&Locale::ENGLISH);
+///
+/// assert_eq!(humanizer.format(&delta), "1 year, 2 months and 3 days");
+/// assert_eq!(humanizer.format_relative(&-delta), "1 year, 2 months and 3 days ago");
+/// assert_eq!(
+///     humanizer.with_max_units(2).format_relative(&delta),
+///     "in 1 year and 2 months"
+/// );
+/// assert_eq!(
+///     Humanizer::new(&Locale::FRENCH).format_relative(&RelativeDuration::weeks(3)),
+///     "dans 3 semaines"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Humanizer<'a> {
+    locale: &'a Locale,
+    max_units: Option<usize>,
+    weeks: bool,
+}
+
+impl<'a> Humanizer<'a> {
+    /// Creates a new `Humanizer` rendering text in the given locale.
+    #[inline]
+    pub fn new(locale: &'a Locale) -> Self {
+        Self {
+            locale,
+            max_units: None,
+            weeks: true,
+        }
+    }
+
+    /// Limits the rendering to the given number of most significant non-zero units. The last
+    /// unit shown is rounded to the nearest whole value, and a limit of zero is treated as one.
+    ///
+    /// When rounding days into months, a month is taken to be the mean Gregorian month of
+    /// 30.436875 days.
+    pub fn with_max_units(&self, max_units: usize) -> Self {
+        Self {
+            max_units: Some(max_units.max(1)),
+            ..*self
+        }
+    }
+
+    /// Whether to break days down into weeks (the default) or to render them as days only.
+    pub fn with_weeks(&self, weeks: bool) -> Self {
+        Self { weeks, ..*self }
+    }
+
+    /// Renders the duration as a list of units, e.g. "1 year, 2 months and 3 days".
+    ///
+    /// Negative components are rendered as negative numbers, e.g. "1 month and -1 day".
+    pub fn format(&self, duration: &RelativeDuration) -> String {
+        let counts = self.counts(duration);
+        if counts.is_empty() {
+            let (singular, plural) = self.locale.units[6];
+            let unit = if (self.locale.plural)(0) {
+                plural
+            } else {
+                singular
+            };
+            return format!("0 {}", unit);
+        }
+        self.render(&counts, &self.locale.units)
+    }
+
+    /// Renders the duration relative to now, e.g. "in 3 weeks" or "2 months ago".
+    ///
+    /// The sign of the largest non-zero unit decides between the future and past forms, and any
+    /// other components with the opposite sign are rendered as negative numbers.
+    pub fn format_relative(&self, duration: &RelativeDuration) -> String {
+        let mut counts = self.counts(duration);
+        let negative = match counts.first() {
+            None => return self.locale.now.to_string(),
+            Some((_, count)) => *count < 0,
+        };
+        if negative {
+            counts.iter_mut().for_each(|(_, count)| *count = -*count);
+        }
+
+        let (prefix, suffix) = if negative {
+            self.locale.past
+        } else {
+            self.locale.future
+        };
+        format!(
+            "{}{}{}",
+            prefix,
+            self.render(&counts, &self.locale.relative_units),
+            suffix
+        )
+    }
+
+    fn units(&self) -> impl Iterator<Item = &'static (usize, Size)> {
+        let weeks = self.weeks;
+        UNITS.iter().filter(move |(index, _)| weeks || *index != 2)
+    }
+
+    // Greedily breaks down the months and nanoseconds into the enabled units
+    fn decompose(&self, mut months: i64, mut nanos: i128) -> Vec<(usize, i64)> {
+        self.units()
+            .map(|(index, size)| match size {
+                Size::Months(size) => {
+                    let count = months / size;
+                    months -= count * size;
+                    (*index, count)
+                }
+                Size::Nanos(size) => {
+                    let count = nanos / size;
+                    nanos -= count * size;
+                    (*index, count as i64)
+                }
+            })
+            .collect()
+    }
+
+    // Returns the non-zero counts of each unit to render, rounded as necessary
+    fn counts(&self, duration: &RelativeDuration) -> Vec<(usize, i64)> {
+        let Components {
+            years,
+            months,
+            days,
+            hours,
+            minutes,
+            seconds,
+            nanos,
+        } = duration.components();
+        let months = years * 12 + months;
+        let seconds = (((days * 24 + hours) * 60 + minutes) * 60 + seconds) as i128;
+        // Round half away from zero to the nearest second
+        let nanos = seconds * SECOND_NANOS
+            + match nanos {
+                n if n >= 500_000_000 => SECOND_NANOS,
+                n if n <= -500_000_000 => -SECOND_NANOS,
+                _ => 0,
+            };
+
+        let mut counts = self.decompose(months, nanos);
+        let last = self.max_units.and_then(|max_units| {
+            counts
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, count))| *count != 0)
+                .map(|(position, _)| position)
+                .nth(max_units - 1)
+        });
+
+        if let Some(last) = last {
+            if last + 1 < counts.len() {
+                let kept = &counts[..=last];
+                let kept_months: i64 = kept
+                    .iter()
+                    .zip(self.units())
+                    .map(|((_, count), (_, size))| match size {
+                        Size::Months(size) => count * size,
+                        Size::Nanos(_) => 0,
+                    })
+                    .sum();
+                let kept_nanos: i128 = kept
+                    .iter()
+                    .zip(self.units())
+                    .map(|((_, count), (_, size))| match size {
+                        Size::Months(_) => 0,
+                        Size::Nanos(size) => *count as i128 * size,
+                    })
+                    .sum();
+
+                let fraction = match self.units().nth(last).unwrap().1 {
+                    Size::Months(size) => {
+                        ((months - kept_months) as f64 + nanos as f64 / MONTH_NANOS) / size as f64
+                    }
+                    Size::Nanos(size) => (nanos - kept_nanos) as f64 / size as f64,
+                };
+                counts[last].1 += fraction.round() as i64;
+
+                // Re-normalise, carrying into larger units where the rounding overflowed
+                let (months, nanos) = match self.units().nth(last).unwrap().1 {
+                    Size::Months(size) => (kept_months + fraction.round() as i64 * size, 0),
+                    Size::Nanos(size) => (months, kept_nanos + fraction.round() as i128 * size),
+                };
+                counts = self.decompose(months, nanos);
+                counts.truncate(last + 1);
+            }
+        }
+
+        counts.retain(|(_, count)| *count != 0);
+        counts
+    }
+
+    fn render(&self, counts: &[(usize, i64)], units: &[(&str, &str); 7]) -> String {
+        let parts: Vec<String> = counts
+            .iter()
+            .map(|(index, count)| {
+                let (singular, plural) = units[*index];
+                let unit = if (self.locale.plural)(count.unsigned_abs()) {
+                    plural
+                } else {
+                    singular
+                };
+                format!("{} {}", count, unit)
+            })
+            .collect();
+
+        match parts.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => {
+                format!(
+                    "{}{}{}",
+                    rest.join(self.locale.separator),
+                    self.locale.conjunction,
+                    last
+                )
+            }
+            None => String::new(),
+        }
+    }
+}
+
+impl RelativeDuration {
+    /// Renders the duration as English text, e.g. "1 year, 2 months and 3 days".
+    ///
+    /// See [`Humanizer`] for other languages, rounding and rendering relative to now.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     (RelativeDuration::years(1) + RelativeDuration::hours(25)).humanize(),
+    ///     "1 year, 1 day and 1 hour",
+    /// );
+    /// ```
+    pub fn humanize(&self) -> String {
+        Humanizer::new(&Locale::ENGLISH).format(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RelativeDuration {
+        RelativeDuration::months(14) + RelativeDuration::days(3)
+    }
+
+    #[test]
+    fn test_format() {
+        let humanizer = Humanizer::new(&Locale::ENGLISH);
+
+        [
+            (RelativeDuration::zero(), "0 seconds"),
+            (sample(), "1 year, 2 months and 3 days"),
+            (RelativeDuration::days(1), "1 day"),
+            (RelativeDuration::days(15), "2 weeks and 1 day"),
+            (
+                RelativeDuration::seconds(3661),
+                "1 hour, 1 minute and 1 second",
+            ),
+            (RelativeDuration::milliseconds(1500), "2 seconds"),
+            (RelativeDuration::milliseconds(499), "0 seconds"),
+            (-sample(), "-1 year, -2 months and -3 days"),
+            (
+                RelativeDuration::months(1) - RelativeDuration::days(1),
+                "1 month and -1 day",
+            ),
+        ]
+        .iter()
+        .for_each(|(duration, expected)| assert_eq!(humanizer.format(duration), *expected));
+
+        assert_eq!(
+            humanizer
+                .with_weeks(false)
+                .format(&RelativeDuration::days(15)),
+            "15 days"
+        );
+    }
+
+    #[test]
+    fn test_format_relative() {
+        let humanizer = Humanizer::new(&Locale::ENGLISH);
+
+        assert_eq!(humanizer.format_relative(&RelativeDuration::zero()), "now");
+        assert_eq!(
+            humanizer.format_relative(&RelativeDuration::weeks(3)),
+            "in 3 weeks"
+        );
+        assert_eq!(
+            humanizer.format_relative(&RelativeDuration::months(-2)),
+            "2 months ago"
+        );
+        assert_eq!(
+            humanizer.format_relative(&(RelativeDuration::days(1) - RelativeDuration::months(1))),
+            "1 month and -1 day ago"
+        );
+    }
+
+    #[test]
+    fn test_max_units() {
+        let humanizer = Humanizer::new(&Locale::ENGLISH);
+
+        [
+            (1, sample(), "1 year"),
+            (2, sample(), "1 year and 2 months"),
+            (3, sample(), "1 year, 2 months and 3 days"),
+            (0, sample(), "1 year"),
+            (1, RelativeDuration::months(18), "2 years"),
+            (
+                1,
+                RelativeDuration::months(11) + RelativeDuration::days(20),
+                "1 year",
+            ),
+            (
+                2,
+                RelativeDuration::months(11) + RelativeDuration::days(20),
+                "11 months and 3 weeks",
+            ),
+            (
+                2,
+                RelativeDuration::years(1) + RelativeDuration::hours(30),
+                "1 year and 1 day",
+            ),
+            (
+                1,
+                RelativeDuration::months(11) + RelativeDuration::days(14),
+                "11 months",
+            ),
+            (1, RelativeDuration::seconds(59 * 60 + 40), "1 hour"),
+            (
+                2,
+                RelativeDuration::seconds(23 * 3600 + 59 * 60 + 40),
+                "1 day",
+            ),
+            (2, RelativeDuration::days(13), "1 week and 6 days"),
+            (1, RelativeDuration::days(13), "2 weeks"),
+            (1, RelativeDuration::days(-13), "-2 weeks"),
+            (1, RelativeDuration::seconds(1), "1 second"),
+        ]
+        .iter()
+        .for_each(|(max_units, duration, expected)| {
+            assert_eq!(
+                humanizer.with_max_units(*max_units).format(duration),
+                *expected,
+                "{}",
+                duration.format_to_iso8601()
+            )
+        });
+    }
+
+    #[test]
+    fn test_locales() {
+        let in_three_weeks = RelativeDuration::weeks(3);
+        let two_months_ago = RelativeDuration::months(-2);
+
+        [
+            (
+                Locale::ENGLISH,
+                "1 year, 2 months and 3 days",
+                "in 3 weeks",
+                "2 months ago",
+                "0 seconds",
+            ),
+            (
+                Locale::FRENCH,
+                "1 an, 2 mois et 3 jours",
+                "dans 3 semaines",
+                "il y a 2 mois",
+                "0 seconde",
+            ),
+            (
+                Locale::GERMAN,
+                "1 Jahr, 2 Monate und 3 Tage",
+                "in 3 Wochen",
+                "vor 2 Monaten",
+                "0 Sekunden",
+            ),
+            (
+                Locale::SPANISH,
+                "1 año, 2 meses y 3 días",
+                "dentro de 3 semanas",
+                "hace 2 meses",
+                "0 segundos",
+            ),
+        ]
+        .iter()
+        .for_each(|(locale, plain, future, past, zero)| {
+            let humanizer = Humanizer::new(locale);
+            assert_eq!(humanizer.format(&sample()), *plain);
+            assert_eq!(humanizer.format_relative(&in_three_weeks), *future);
+            assert_eq!(humanizer.format_relative(&two_months_ago), *past);
+            assert_eq!(humanizer.format(&RelativeDuration::zero()), *zero);
+        });
+
+        assert_eq!(
+            Humanizer::new(&Locale::GERMAN).format_relative(&RelativeDuration::days(-1)),
+            "vor 1 Tag"
+        );
+        assert_eq!(
+            Humanizer::new(&Locale::FRENCH).format(&RelativeDuration::days(1)),
+            "1 jour"
+        );
+    }
+
+    #[test]
+    fn test_custom_locale() {
+        const TERSE_UNITS: [(&str, &str); 7] = [
+            ("y", "y"),
+            ("mo", "mo"),
+            ("w", "w"),
+            ("d", "d"),
+            ("h", "h"),
+            ("m", "m"),
+            ("s", "s"),
+        ];
+        let terse = Locale {
+            units: TERSE_UNITS,
+            relative_units: TERSE_UNITS,
+            plural: |_| false,
+            separator: " ",
+            conjunction: " ",
+            future: ("+", ""),
+            past: ("-", ""),
+            now: "0",
+        };
+        let humanizer = Humanizer::new(&terse);
+
+        [
+            (humanizer.format(&sample()), "1 y 2 mo 3 d"),
+            (humanizer.format_relative(&sample()), "+1 y 2 mo 3 d"),
+            (
+                humanizer.format_relative(&RelativeDuration::hours(-5)),
+                "-5 h",
+            ),
+            (
+                humanizer.format_relative(
+                    &(RelativeDuration::minutes(-2) + RelativeDuration::seconds(-1)),
+                ),
+                "-2 m 1 s",
+            ),
+            (humanizer.format_relative(&RelativeDuration::zero()), "0"),
+        ]
+        .iter()
+        .for_each(|(actual, expected)| assert_eq!(actual, expected));
+    }
+}
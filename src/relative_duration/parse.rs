@@ -54,6 +54,8 @@ fn get_terminated_decimal(input: &str, terminator: char) -> Result<(&str, i64, u
 
         let fraction = if fraction_string.is_empty() {
             0
+        } else if !fraction_string.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("{} is not a valid fraction", fraction_string));
         } else {
             fraction_string
                 .chars()
@@ -119,7 +121,34 @@ fn parse_timespec(timespec: &str) -> Result<(i64, i64, i64, u32), String> {
     }
 }
 
+// The signed components of a duration, each truncated towards zero.
+pub(crate) struct Components {
+    pub(crate) years: i64,
+    pub(crate) months: i64,
+    pub(crate) days: i64,
+    pub(crate) hours: i64,
+    pub(crate) minutes: i64,
+    pub(crate) seconds: i64,
+    pub(crate) nanos: i32,
+}
+
 impl RelativeDuration {
+    // Breaks the duration down into years, months, days, hours, minutes, seconds and nanos.
+    pub(crate) fn components(&self) -> Components {
+        let duration_seconds = self.duration.num_seconds();
+        let remaining_seconds = duration_seconds % (24 * 60 * 60);
+
+        Components {
+            years: self.months as i64 / 12,
+            months: self.months as i64 % 12,
+            days: duration_seconds / (24 * 60 * 60),
+            hours: remaining_seconds / (60 * 60),
+            minutes: remaining_seconds % (60 * 60) / 60,
+            seconds: remaining_seconds % 60,
+            nanos: self.duration.subsec_nanos(),
+        }
+    }
+
     /// Parses an [ISO 8601 duration string](https://en.wikipedia.org/wiki/ISO_8601#Durations) into
     /// a [`RelativeDuration`] value.
     ///
@@ -179,21 +208,15 @@ impl RelativeDuration {
     /// );
     /// ```
     pub fn format_to_iso8601(&self) -> String {
-        let years = self.months as i64 / 12;
-        let months = self.months as i64 % 12;
-
-        let duration_seconds = self.duration.num_seconds();
-
-        let days = duration_seconds / (24 * 60 * 60);
-        let mut remaining_seconds = duration_seconds % (24 * 60 * 60);
-
-        let hours = remaining_seconds / (60 * 60);
-        remaining_seconds %= 60 * 60;
-
-        let minutes = remaining_seconds / 60;
-        remaining_seconds %= 60;
-
-        let subsec_nanos = self.duration.subsec_nanos();
+        let Components {
+            years,
+            months,
+            days,
+            hours,
+            minutes,
+            seconds: remaining_seconds,
+            nanos: subsec_nanos,
+        } = self.components();
 
         // This awkward handling is needed to represent nanoseconds as a fraction of seconds,
         // instead of independently, since it must have no sign, and will affect the sign for
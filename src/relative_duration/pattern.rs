@@ -0,0 +1,458 @@
+use super::parse::Components;
+use super::RelativeDuration;
+use chrono::Duration;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Field {
+    Years,
+    Months,
+    TotalMonths,
+    Weeks,
+    WeekDays,
+    TotalDays,
+    Hours,
+    TotalHours,
+    Minutes,
+    TotalMinutes,
+    Seconds,
+    TotalSeconds,
+    Fraction,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Pad {
+    Zero,
+    Space,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Item {
+    Literal(String),
+    Field {
+        field: Field,
+        pad: Pad,
+        width: usize,
+    },
+    Sign {
+        always: bool,
+    },
+    Section(Vec<Item>),
+}
+
+// The value of every field for one duration
+struct Values {
+    negative: bool,
+    // -1 when rendering fields relative to a negative sign, else 1
+    sign: i64,
+    components: Components,
+}
+
+impl Values {
+    fn new(duration: &RelativeDuration, signed: bool) -> Self {
+        let negative = if duration.months != 0 {
+            duration.months < 0
+        } else {
+            duration.duration < Duration::zero()
+        };
+        Values {
+            negative,
+            sign: if signed && negative { -1 } else { 1 },
+            components: duration.components(),
+        }
+    }
+
+    fn get(&self, field: Field) -> i64 {
+        let c = &self.components;
+        let total_hours = c.days * 24 + c.hours;
+        let total_minutes = total_hours * 60 + c.minutes;
+        self.sign
+            * match field {
+                Field::Years => c.years,
+                Field::Months => c.months,
+                Field::TotalMonths => c.years * 12 + c.months,
+                Field::Weeks => c.days / 7,
+                Field::WeekDays => c.days % 7,
+                Field::TotalDays => c.days,
+                Field::Hours => c.hours,
+                Field::TotalHours => total_hours,
+                Field::Minutes => c.minutes,
+                Field::TotalMinutes => total_minutes,
+                Field::Seconds => c.seconds,
+                Field::TotalSeconds => total_minutes * 60 + c.seconds,
+                Field::Fraction => c.nanos.into(),
+            }
+    }
+}
+
+/// A compiled format string for rendering [`RelativeDuration`]s, in the spirit of `strftime`.
+///
+/// The format string is parsed once, and the resulting `DurationFormat` can then be used to
+/// render any number of durations cheaply. The supported directives are:
+///
+/// | Directive | Meaning                                         |
+/// |-----------|-------------------------------------------------|
+/// | `%Y`      | Whole years                                     |
+/// | `%m`      | Months after the whole years (`0..12`)          |
+/// | `%O`      | Total months                                    |
+/// | `%W`      | Whole weeks                                     |
+/// | `%w`      | Days after the whole weeks (`0..7`)             |
+/// | `%D`      | Total days                                      |
+/// | `%H`      | Hours after the whole days (`0..24`)            |
+/// | `%h`      | Total hours                                     |
+/// | `%M`      | Minutes after the whole hours (`0..60`)         |
+/// | `%i`      | Total minutes                                   |
+/// | `%S`      | Seconds after the whole minutes (`0..60`)       |
+/// | `%s`      | Total seconds                                   |
+/// | `%f`      | Fractional seconds, as 9 digits                 |
+/// | `%-`      | `-` if the duration is negative, else nothing   |
+/// | `%+`      | `-` if the duration is negative, else `+`       |
+/// | `%[`      | Start of a section which is omitted when all of its fields are zero |
+/// | `%]`      | End of a section                                |
+/// | `%%`      | A literal `%`                                   |
+///
+/// Months (and years) are counted from the months of the duration, and everything else from its
+/// `Duration`, where days are taken to be 24 hours.
+///
+/// Numeric directives accept a width, padded with spaces (`%2H`) or zeros (`%02H`). For `%f` the
+/// width is instead the number of digits to show, from 1 to 9, so `%3f` renders milliseconds.
+///
+/// A duration is negative if its months are negative, or if it has no months and a negative
+/// `Duration`. Without a sign directive, each field is rendered as a signed number, and the first
+/// field of a negative duration is given a `-` even when it is zero. When the
+/// format contains `%-` or `%+`, fields are instead rendered relative to that sign, so that
+/// `-PT1H30M` renders as `-1:30` rather than `-1:-30` with `%-%H:%02M`.
+///
+/// # Example
+///
+/// ```
+/// use chronoutil::RelativeDuration;
+/// use chronoutil::relative_duration::DurationFormat;
+///
+/// let format = DurationFormat::parse("%[%Y years %]%[%m months %]%02H:%02M:%02S").unwrap();
+/// let delta = RelativeDuration::months(2) + RelativeDuration::seconds(3723);
+/// assert_eq!(format.format(&delta), "2 months 01:02:03");
+///
+/// let format: DurationFormat = "%-%D days total".parse().unwrap();
+/// assert_eq!(format.format(&RelativeDuration::weeks(-2)), "-14 days total");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DurationFormat {
+    items: Vec<Item>,
+    signed: bool,
+}
+
+// Parses a single directive following a %, other than the section markers
+fn parse_directive(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    pattern: &str,
+) -> Result<Item, String> {
+    let pad = if chars.peek() == Some(&'0') {
+        chars.next();
+        Pad::Zero
+    } else {
+        Pad::Space
+    };
+    let mut width = String::new();
+    while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+        width.push(*c);
+        chars.next();
+    }
+    let width: Option<usize> = if width.is_empty() {
+        None
+    } else {
+        Some(
+            width
+                .parse()
+                .map_err(|_| format!("invalid width {} in {}", width, pattern))?,
+        )
+    };
+
+    let directive = chars
+        .next()
+        .ok_or_else(|| format!("incomplete directive at the end of {}", pattern))?;
+    let field = match directive {
+        'Y' => Field::Years,
+        'm' => Field::Months,
+        'O' => Field::TotalMonths,
+        'W' => Field::Weeks,
+        'w' => Field::WeekDays,
+        'D' => Field::TotalDays,
+        'H' => Field::Hours,
+        'h' => Field::TotalHours,
+        'M' => Field::Minutes,
+        'i' => Field::TotalMinutes,
+        'S' => Field::Seconds,
+        's' => Field::TotalSeconds,
+        'f' => {
+            let digits = width.unwrap_or(9);
+            if !(1..=9).contains(&digits) {
+                return Err(format!(
+                    "%f takes between 1 and 9 digits, not {}, in {}",
+                    digits, pattern
+                ));
+            }
+            return Ok(Item::Field {
+                field: Field::Fraction,
+                pad: Pad::Zero,
+                width: digits,
+            });
+        }
+        _ if width.is_some() || pad == Pad::Zero => {
+            return Err(format!(
+                "%{} does not accept a width in {}",
+                directive, pattern
+            ));
+        }
+        '%' => return Ok(Item::Literal("%".to_string())),
+        '-' => return Ok(Item::Sign { always: false }),
+        '+' => return Ok(Item::Sign { always: true }),
+        _ => return Err(format!("unknown directive %{} in {}", directive, pattern)),
+    };
+    Ok(Item::Field {
+        field,
+        pad,
+        width: width.unwrap_or(0),
+    })
+}
+
+fn push_literal(items: &mut Vec<Item>, c: char) {
+    if let Some(Item::Literal(literal)) = items.last_mut() {
+        literal.push(c);
+    } else {
+        items.push(Item::Literal(c.to_string()));
+    }
+}
+
+impl DurationFormat {
+    /// Compiles a format string into a `DurationFormat`.
+    ///
+    /// # Errors
+    ///
+    /// - Unknown directives, or a `%` at the end of the format string
+    /// - Widths on directives which do not accept them, or a `%f` width outside `1..=9`
+    /// - Nested, unopened or unclosed sections
+    pub fn parse(pattern: &str) -> Result<DurationFormat, String> {
+        let mut items = vec![];
+        let mut section: Option<Vec<Item>> = None;
+        let mut signed = false;
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let current = section.as_mut().unwrap_or(&mut items);
+            if c != '%' {
+                push_literal(current, c);
+                continue;
+            }
+            match chars.peek() {
+                Some('[') => {
+                    chars.next();
+                    if section.is_some() {
+                        return Err(format!("nested section in {}", pattern));
+                    }
+                    section = Some(vec![]);
+                }
+                Some(']') => {
+                    chars.next();
+                    match section.take() {
+                        Some(inner) => items.push(Item::Section(inner)),
+                        None => return Err(format!("unopened section in {}", pattern)),
+                    }
+                }
+                _ => match parse_directive(&mut chars, pattern)? {
+                    Item::Literal(literal) => {
+                        literal.chars().for_each(|c| push_literal(current, c))
+                    }
+                    item => {
+                        signed |= matches!(item, Item::Sign { .. });
+                        current.push(item);
+                    }
+                },
+            }
+        }
+
+        if section.is_some() {
+            return Err(format!("unclosed section in {}", pattern));
+        }
+        Ok(DurationFormat { items, signed })
+    }
+
+    /// Renders the duration according to this format.
+    pub fn format(&self, duration: &RelativeDuration) -> String {
+        let mut out = String::new();
+        // Writing to a String never fails
+        let _ = self.write(&mut out, duration);
+        out
+    }
+
+    /// Renders the duration according to this format into the given writer, avoiding an
+    /// intermediate allocation.
+    pub fn write<W: Write>(&self, out: &mut W, duration: &RelativeDuration) -> fmt::Result {
+        let values = Values::new(duration, self.signed);
+        // Without a sign directive, a negative duration whose first field is zero (e.g. the
+        // seconds of -0.5s) would otherwise lose its sign
+        let mut pending_sign = !self.signed && values.negative;
+        write_items(out, &self.items, &values, &mut pending_sign)
+    }
+}
+
+fn write_items<W: Write>(
+    out: &mut W,
+    items: &[Item],
+    values: &Values,
+    pending_sign: &mut bool,
+) -> fmt::Result {
+    items.iter().try_for_each(|item| match item {
+        Item::Literal(literal) => out.write_str(literal),
+        Item::Field {
+            field: Field::Fraction,
+            width,
+            ..
+        } => {
+            if std::mem::take(pending_sign) {
+                out.write_char('-')?;
+            }
+            let nanos = values.get(Field::Fraction).unsigned_abs();
+            write!(
+                out,
+                "{:0width$}",
+                nanos / 10u64.pow(9 - *width as u32),
+                width = width
+            )
+        }
+        Item::Field { field, pad, width } => {
+            let value = values.get(*field);
+            let width = *width;
+            match (std::mem::take(pending_sign) && value == 0, pad) {
+                (true, Pad::Zero) => write!(out, "-{:0width$}", 0, width = width.max(1) - 1),
+                (true, Pad::Space) => write!(out, "{:>width$}", "-0", width = width),
+                (false, Pad::Zero) => write!(out, "{:0width$}", value, width = width),
+                (false, Pad::Space) => write!(out, "{:width$}", value, width = width),
+            }
+        }
+        Item::Sign { always } => match (values.negative, always) {
+            (true, _) => out.write_char('-'),
+            (false, true) => out.write_char('+'),
+            (false, false) => Ok(()),
+        },
+        Item::Section(inner) => {
+            let mut fields = inner.iter().filter_map(|item| match item {
+                Item::Field { field, .. } => Some(values.get(*field)),
+                _ => None,
+            });
+            let has_fields = fields.clone().next().is_some();
+            if has_fields && fields.all(|value| value == 0) {
+                Ok(())
+            } else {
+                write_items(out, inner, values, pending_sign)
+            }
+        }
+    })
+}
+
+impl FromStr for DurationFormat {
+    type Err = String;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        DurationFormat::parse(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RelativeDuration {
+        RelativeDuration::months(14)
+            + RelativeDuration::days(10)
+            + RelativeDuration::seconds(3723)
+            + RelativeDuration::milliseconds(45)
+    }
+
+    #[test]
+    fn test_format() {
+        [
+            ("%Y years %m months", sample(), "1 years 2 months"),
+            ("%O months", sample(), "14 months"),
+            ("%W weeks %w days", sample(), "1 weeks 3 days"),
+            ("%D days total", sample(), "10 days total"),
+            ("%H:%02M:%02S.%3f", sample(), "1:02:03.045"),
+            ("%02H:%02M:%02S.%f", sample(), "01:02:03.045000000"),
+            ("%h hours", sample(), "241 hours"),
+            ("%i minutes", sample(), "14462 minutes"),
+            ("%s seconds", sample(), "867723 seconds"),
+            ("[%3H]", sample(), "[  1]"),
+            ("100%%", sample(), "100%"),
+            (
+                "%[%Y years %]%[%m months %]%D days",
+                sample(),
+                "1 years 2 months 10 days",
+            ),
+            (
+                "%[%Y years %]%[%m months %]%D days",
+                RelativeDuration::days(3),
+                "3 days",
+            ),
+            ("%[%Y years %]%[%m months %]", RelativeDuration::days(3), ""),
+            ("%[no fields%]", RelativeDuration::zero(), "no fields"),
+            ("%-%D", RelativeDuration::days(3), "3"),
+            ("%+%D", RelativeDuration::days(3), "+3"),
+            ("%+%D", RelativeDuration::zero(), "+0"),
+            ("%H:%M", RelativeDuration::minutes(-90), "-1:-30"),
+            ("%-%H:%02M", RelativeDuration::minutes(-90), "-1:30"),
+            (
+                "%+%O %D",
+                RelativeDuration::months(-1) + RelativeDuration::days(1),
+                "-1 -1",
+            ),
+            (
+                "%+%O %D",
+                RelativeDuration::months(1) - RelativeDuration::days(1),
+                "+1 -1",
+            ),
+            ("%-%S.%3f", RelativeDuration::milliseconds(-1500), "-1.500"),
+            ("%S.%3f", RelativeDuration::milliseconds(-500), "-0.500"),
+            ("%S.%3f", RelativeDuration::milliseconds(-1500), "-1.500"),
+            ("%02S.%3f", RelativeDuration::milliseconds(-500), "-0.500"),
+            ("%03S.%3f", RelativeDuration::milliseconds(-500), "-00.500"),
+            ("%3S.%3f", RelativeDuration::milliseconds(-500), " -0.500"),
+            ("%H:%02M:%02S", RelativeDuration::seconds(-30), "-0:00:-30"),
+            ("%3f ms", RelativeDuration::milliseconds(-5), "-005 ms"),
+            (
+                "%[%D days %]%S.%3f",
+                RelativeDuration::milliseconds(-250),
+                "-0.250",
+            ),
+            ("%S.%3f", RelativeDuration::milliseconds(500), "0.500"),
+        ]
+        .iter()
+        .for_each(|(pattern, duration, expected)| {
+            assert_eq!(
+                DurationFormat::parse(pattern).unwrap().format(duration),
+                *expected,
+                "{}",
+                pattern
+            )
+        })
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        [
+            "%", "%x", "%5", "%2%", "%0-", "%10f", "%[", "%]", "%[%[%]%]", "%[%Y",
+        ]
+        .iter()
+        .for_each(|pattern| assert!(DurationFormat::parse(pattern).is_err(), "{}", pattern))
+    }
+
+    #[test]
+    fn test_write() {
+        let format: DurationFormat = "%D/%H".parse().unwrap();
+        let mut out = String::from("> ");
+        format
+            .write(&mut out, &RelativeDuration::hours(49))
+            .unwrap();
+        assert_eq!(out, "> 2/1");
+    }
+}
@@ -0,0 +1,297 @@
+//! Conversions between `RelativeDuration` and the Postgres `interval` type.
+//!
+//! On the wire a Postgres `interval` is a big-endian `(i64 microseconds, i32 days, i32 months)`
+//! triple. Postgres keeps days separate from microseconds (so that `1 day` and `24 hours`
+//! differ across DST changes), whereas a `RelativeDuration` only distinguishes months from an
+//! absolute `Duration`. We therefore:
+//!
+//! - decode the days field as exactly 24 hours each, and
+//! - encode every whole multiple of 24 hours into the days field, leaving the remainder (with the
+//!   same sign) in the microseconds field.
+//!
+//! Postgres only has microsecond resolution, so encoding a `RelativeDuration` with a non-zero
+//! sub-microsecond part is an error rather than a silent truncation.
+use std::convert::TryInto;
+
+use chrono::Duration;
+
+use super::RelativeDuration;
+
+const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+// The largest possible days and microseconds fields always fit in a chrono `Duration`.
+fn from_pg_interval(months: i32, days: i32, microseconds: i64) -> RelativeDuration {
+    RelativeDuration::months(months)
+        .with_duration(Duration::days(days.into()) + Duration::microseconds(microseconds))
+}
+
+fn to_pg_interval(duration: &RelativeDuration) -> Result<(i32, i32, i64), String> {
+    if duration.duration.subsec_nanos() % 1_000 != 0 {
+        return Err(format!(
+            "{} has sub-microsecond precision, which postgres intervals do not support",
+            duration.format_to_iso8601()
+        ));
+    }
+
+    let microseconds = duration.duration.num_microseconds().ok_or_else(|| {
+        format!(
+            "{} overflows the microseconds of a postgres interval",
+            duration.format_to_iso8601()
+        )
+    })?;
+    let days = (microseconds / MICROS_PER_DAY).try_into().map_err(|_| {
+        format!(
+            "{} overflows the days of a postgres interval",
+            duration.format_to_iso8601()
+        )
+    })?;
+
+    Ok((duration.months, days, microseconds % MICROS_PER_DAY))
+}
+
+#[cfg(feature = "postgres-types")]
+fn decode_pg_interval(raw: &[u8]) -> Result<RelativeDuration, String> {
+    if raw.len() != 16 {
+        return Err(format!(
+            "postgres interval should be 16 bytes, got {}",
+            raw.len()
+        ));
+    }
+
+    let microseconds = i64::from_be_bytes(raw[0..8].try_into().unwrap());
+    let days = i32::from_be_bytes(raw[8..12].try_into().unwrap());
+    let months = i32::from_be_bytes(raw[12..16].try_into().unwrap());
+
+    Ok(from_pg_interval(months, days, microseconds))
+}
+
+#[cfg(feature = "postgres-types")]
+fn encode_pg_interval(duration: &RelativeDuration) -> Result<[u8; 16], String> {
+    let (months, days, microseconds) = to_pg_interval(duration)?;
+
+    let mut out = [0; 16];
+    out[0..8].copy_from_slice(&microseconds.to_be_bytes());
+    out[8..12].copy_from_slice(&days.to_be_bytes());
+    out[12..16].copy_from_slice(&months.to_be_bytes());
+    Ok(out)
+}
+
+#[cfg(feature = "postgres-types")]
+mod postgres_types_impls {
+    use std::error::Error;
+
+    use bytes::BytesMut;
+    use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+    use super::{decode_pg_interval, encode_pg_interval, RelativeDuration};
+
+    impl<'a> FromSql<'a> for RelativeDuration {
+        fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            Ok(decode_pg_interval(raw)?)
+        }
+
+        accepts!(INTERVAL);
+    }
+
+    impl ToSql for RelativeDuration {
+        fn to_sql(
+            &self,
+            _: &Type,
+            out: &mut BytesMut,
+        ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+            out.extend_from_slice(&encode_pg_interval(self)?);
+            Ok(IsNull::No)
+        }
+
+        accepts!(INTERVAL);
+        to_sql_checked!();
+    }
+}
+
+#[cfg(feature = "sqlx")]
+mod sqlx_impls {
+    use std::convert::TryFrom;
+
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::types::PgInterval;
+    use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+    use sqlx::{Decode, Encode, Type};
+
+    use super::{from_pg_interval, to_pg_interval, RelativeDuration};
+
+    impl From<PgInterval> for RelativeDuration {
+        /// Makes a new `RelativeDuration` from a `PgInterval`, treating each day as 24 hours.
+        #[inline]
+        fn from(item: PgInterval) -> Self {
+            from_pg_interval(item.months, item.days, item.microseconds)
+        }
+    }
+
+    impl TryFrom<RelativeDuration> for PgInterval {
+        type Error = String;
+
+        /// Converts a `RelativeDuration` into a `PgInterval`.
+        ///
+        /// Returns Err if the duration has sub-microsecond precision or does not fit.
+        fn try_from(item: RelativeDuration) -> Result<Self, Self::Error> {
+            let (months, days, microseconds) = to_pg_interval(&item)?;
+            Ok(PgInterval {
+                months,
+                days,
+                microseconds,
+            })
+        }
+    }
+
+    impl Type<Postgres> for RelativeDuration {
+        fn type_info() -> PgTypeInfo {
+            <PgInterval as Type<Postgres>>::type_info()
+        }
+    }
+
+    impl PgHasArrayType for RelativeDuration {
+        fn array_type_info() -> PgTypeInfo {
+            PgInterval::array_type_info()
+        }
+    }
+
+    impl<'r> Decode<'r, Postgres> for RelativeDuration {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+            Ok(<PgInterval as Decode<Postgres>>::decode(value)?.into())
+        }
+    }
+
+    impl Encode<'_, Postgres> for RelativeDuration {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            PgInterval::try_from(*self)?.encode_by_ref(buf)
+        }
+
+        fn size_hint(&self) -> usize {
+            16
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // P1M2DT3H4.000005S
+    const FIXTURE: [u8; 16] = [0, 0, 0, 2, 131, 247, 245, 5, 0, 0, 0, 2, 0, 0, 0, 1];
+    // P-1Y-2M-3DT-0.000001S
+    #[cfg(feature = "postgres-types")]
+    const NEGATIVE_FIXTURE: [u8; 16] = [
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 253, 255, 255, 255, 242,
+    ];
+
+    fn fixture() -> RelativeDuration {
+        RelativeDuration::months(1)
+            + RelativeDuration::days(2)
+            + RelativeDuration::hours(3)
+            + RelativeDuration::seconds(4)
+            + RelativeDuration::microseconds(5)
+    }
+
+    fn negative_fixture() -> RelativeDuration {
+        RelativeDuration::months(-14)
+            + RelativeDuration::days(-3)
+            + RelativeDuration::microseconds(-1)
+    }
+
+    #[cfg(feature = "postgres-types")]
+    #[test]
+    fn test_decode_interval() {
+        assert_eq!(decode_pg_interval(&FIXTURE).unwrap(), fixture());
+        assert_eq!(
+            decode_pg_interval(&NEGATIVE_FIXTURE).unwrap(),
+            negative_fixture()
+        );
+        assert_eq!(
+            decode_pg_interval(&[0; 16]).unwrap(),
+            RelativeDuration::zero()
+        );
+        assert!(decode_pg_interval(&FIXTURE[..12]).is_err());
+    }
+
+    #[cfg(feature = "postgres-types")]
+    #[test]
+    fn test_encode_interval() {
+        assert_eq!(encode_pg_interval(&fixture()).unwrap(), FIXTURE);
+        assert_eq!(
+            encode_pg_interval(&negative_fixture()).unwrap(),
+            NEGATIVE_FIXTURE
+        );
+        assert_eq!(
+            encode_pg_interval(&RelativeDuration::zero()).unwrap(),
+            [0; 16]
+        );
+    }
+
+    #[test]
+    fn test_encode_interval_days() {
+        assert_eq!(
+            to_pg_interval(&RelativeDuration::hours(49)).unwrap(),
+            (0, 2, 60 * 60 * 1_000_000)
+        );
+        assert_eq!(
+            to_pg_interval(&RelativeDuration::hours(-49)).unwrap(),
+            (0, -2, -60 * 60 * 1_000_000)
+        );
+        assert_eq!(
+            to_pg_interval(&(RelativeDuration::days(1) - RelativeDuration::hours(1))).unwrap(),
+            (0, 0, 23 * 60 * 60 * 1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_encode_interval_errors() {
+        assert!(to_pg_interval(&RelativeDuration::nanoseconds(1_001)).is_err());
+        assert!(to_pg_interval(&RelativeDuration::nanoseconds(-1)).is_err());
+        assert!(to_pg_interval(&RelativeDuration::seconds(i64::MAX / 1_000)).is_err());
+    }
+
+    #[cfg(feature = "postgres-types")]
+    #[test]
+    fn test_postgres_types() {
+        use bytes::BytesMut;
+        use postgres_types::{FromSql, ToSql, Type};
+
+        let mut out = BytesMut::new();
+        fixture().to_sql_checked(&Type::INTERVAL, &mut out).unwrap();
+        assert_eq!(&out[..], FIXTURE);
+        assert!(fixture().to_sql_checked(&Type::INT8, &mut out).is_err());
+
+        assert_eq!(
+            RelativeDuration::from_sql(&Type::INTERVAL, &FIXTURE).unwrap(),
+            fixture()
+        );
+        assert!(<RelativeDuration as FromSql>::accepts(&Type::INTERVAL));
+        assert!(!<RelativeDuration as FromSql>::accepts(&Type::TEXT));
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn test_sqlx() {
+        use sqlx::encode::IsNull;
+        use sqlx::postgres::{types::PgInterval, PgArgumentBuffer, Postgres};
+        use sqlx::Encode;
+        use std::convert::TryFrom;
+
+        let mut buf = PgArgumentBuffer::default();
+        assert!(matches!(
+            Encode::<Postgres>::encode(fixture(), &mut buf),
+            Ok(IsNull::No)
+        ));
+        assert_eq!(&**buf, FIXTURE);
+        assert!(Encode::<Postgres>::encode(RelativeDuration::nanoseconds(1), &mut buf).is_err());
+
+        let interval = PgInterval {
+            months: -14,
+            days: -3,
+            microseconds: -1,
+        };
+        assert_eq!(RelativeDuration::from(interval), negative_fixture());
+        assert_eq!(PgInterval::try_from(negative_fixture()).unwrap(), interval);
+    }
+}
@@ -0,0 +1,297 @@
+use super::RelativeDuration;
+use chrono::Duration;
+use std::fmt::Write;
+
+// Splits a leading run of digits terminated by the given designator off the input.
+fn take_designated(input: &str, designator: char) -> Option<(&str, &str)> {
+    let end = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    if end == 0 {
+        return None;
+    }
+    input[end..]
+        .strip_prefix(designator)
+        .map(|remainder| (&input[..end], remainder))
+}
+
+fn parse_value(value: &str, input: &str) -> Result<i64, String> {
+    value
+        .parse::<i64>()
+        .map_err(|_| format!("{} overflows in {}", value, input))
+}
+
+// Parses dur-time without the leading T, returning the number of seconds
+fn parse_dur_time(timespec: &str, input: &str) -> Result<i64, String> {
+    let mut remainder = timespec;
+    let mut seconds: i64 = 0;
+    let mut seen_any = false;
+
+    // Each of dur-hour and dur-minute may only be followed by the next smaller unit
+    for (designator, multiplier) in [('H', 60 * 60), ('M', 60), ('S', 1)].iter() {
+        match take_designated(remainder, *designator) {
+            Some((value, tail)) => {
+                seconds = parse_value(value, input)?
+                    .checked_mul(*multiplier)
+                    .and_then(|x| x.checked_add(seconds))
+                    .ok_or_else(|| format!("integer overflow on constructing {}", input))?;
+                remainder = tail;
+                seen_any = true;
+            }
+            None if seen_any => break,
+            None => continue,
+        }
+    }
+
+    if !seen_any || !remainder.is_empty() {
+        Err(format!("invalid time in RFC 5545 duration {}", input))
+    } else {
+        Ok(seconds)
+    }
+}
+
+impl RelativeDuration {
+    /// Parses an [RFC 5545](https://www.rfc-editor.org/rfc/rfc5545#section-3.3.6) (iCalendar)
+    /// `DURATION` value into a [`RelativeDuration`].
+    ///
+    /// This dialect only has weeks, days, hours, minutes and whole seconds, with an optional
+    /// leading sign. Weeks cannot be combined with any other unit, and hours and minutes may only
+    /// be followed by the next smaller unit (so `PT1H0M1S` rather than `PT1H1S`).
+    ///
+    /// # Errors
+    ///
+    /// - Input which is not a valid RFC 5545 duration
+    /// - Values which overflow a [`RelativeDuration`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     RelativeDuration::parse_from_rfc5545("-PT15M").unwrap(),
+    ///     RelativeDuration::minutes(-15),
+    /// );
+    /// assert_eq!(
+    ///     RelativeDuration::parse_from_rfc5545("P7W").unwrap(),
+    ///     RelativeDuration::weeks(7),
+    /// );
+    /// assert!(RelativeDuration::parse_from_rfc5545("P1M").is_err());
+    /// ```
+    pub fn parse_from_rfc5545(input: &str) -> Result<RelativeDuration, String> {
+        let (negative, remainder) = if let Some(remainder) = input.strip_prefix('-') {
+            (true, remainder)
+        } else {
+            (false, input.strip_prefix('+').unwrap_or(input))
+        };
+        let remainder = remainder
+            .strip_prefix('P')
+            .ok_or_else(|| format!("RFC 5545 duration {} was not prefixed with P", input))?;
+
+        let seconds = if let Some((weeks, "")) = take_designated(remainder, 'W') {
+            parse_value(weeks, input)?.checked_mul(7 * 24 * 60 * 60)
+        } else if let Some((days, timespec)) = take_designated(remainder, 'D') {
+            let days = parse_value(days, input)?;
+            let time = match timespec {
+                "" => 0,
+                _ => match timespec.strip_prefix('T') {
+                    Some(timespec) => parse_dur_time(timespec, input)?,
+                    None => {
+                        return Err(format!("invalid time in RFC 5545 duration {}", input));
+                    }
+                },
+            };
+            days.checked_mul(24 * 60 * 60)
+                .and_then(|x| x.checked_add(time))
+        } else if let Some(timespec) = remainder.strip_prefix('T') {
+            Some(parse_dur_time(timespec, input)?)
+        } else {
+            return Err(format!("invalid RFC 5545 duration {}", input));
+        };
+
+        let duration = seconds
+            .and_then(Duration::try_seconds)
+            .ok_or_else(|| format!("integer overflow on constructing {}", input))?;
+        Ok(RelativeDuration::from(if negative {
+            -duration
+        } else {
+            duration
+        }))
+    }
+
+    /// Formats a [`RelativeDuration`] into an
+    /// [RFC 5545](https://www.rfc-editor.org/rfc/rfc5545#section-3.3.6) (iCalendar) `DURATION`
+    /// value.
+    ///
+    /// Whole numbers of weeks are written as weeks, and negative durations use a leading sign.
+    ///
+    /// # Errors
+    ///
+    /// - Non-zero months, which RFC 5545 cannot represent
+    /// - Sub-second precision, which RFC 5545 cannot represent
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     (RelativeDuration::days(15) + RelativeDuration::hours(5) + RelativeDuration::seconds(20))
+    ///         .format_to_rfc5545(),
+    ///     Ok("P15DT5H0M20S".to_string()),
+    /// );
+    /// assert_eq!(RelativeDuration::days(-14).format_to_rfc5545(), Ok("-P2W".to_string()));
+    /// assert!(RelativeDuration::months(1).format_to_rfc5545().is_err());
+    /// ```
+    pub fn format_to_rfc5545(&self) -> Result<String, String> {
+        if self.months != 0 {
+            return Err(format!(
+                "{} has months, which RFC 5545 durations do not support",
+                self.format_to_iso8601()
+            ));
+        }
+        if self.duration.subsec_nanos() != 0 {
+            return Err(format!(
+                "{} has sub-second precision, which RFC 5545 durations do not support",
+                self.format_to_iso8601()
+            ));
+        }
+
+        let mut out = String::new();
+        let seconds = self.duration.num_seconds();
+        if seconds < 0 {
+            out.push('-');
+        }
+        out.push('P');
+
+        let seconds = seconds.unsigned_abs();
+        let days = seconds / (24 * 60 * 60);
+        let (hours, minutes, seconds) = (seconds / (60 * 60) % 24, seconds / 60 % 60, seconds % 60);
+
+        let (weeks, weekdays) = (days / 7, days % 7);
+        if weeks != 0 && weekdays == 0 && hours == 0 && minutes == 0 && seconds == 0 {
+            let _ = write!(out, "{}W", weeks);
+            return Ok(out);
+        }
+        if days != 0 {
+            let _ = write!(out, "{}D", days);
+        }
+        if hours != 0 || minutes != 0 || seconds != 0 || days == 0 {
+            out.push('T');
+            if hours != 0 {
+                let _ = write!(out, "{}H", hours);
+            }
+            if minutes != 0 || (hours != 0 && seconds != 0) {
+                let _ = write!(out, "{}M", minutes);
+            }
+            if seconds != 0 || (hours == 0 && minutes == 0) {
+                let _ = write!(out, "{}S", seconds);
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc5545() {
+        [
+            (
+                "P15DT5H0M20S",
+                RelativeDuration::days(15)
+                    + RelativeDuration::hours(5)
+                    + RelativeDuration::seconds(20),
+            ),
+            ("-PT15M", RelativeDuration::minutes(-15)),
+            ("+PT15M", RelativeDuration::minutes(15)),
+            ("P7W", RelativeDuration::weeks(7)),
+            ("P1D", RelativeDuration::days(1)),
+            ("PT1H", RelativeDuration::hours(1)),
+            ("PT1M1S", RelativeDuration::seconds(61)),
+            ("PT0S", RelativeDuration::zero()),
+            ("P0W", RelativeDuration::zero()),
+            ("-P1DT1H", -RelativeDuration::hours(25)),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            assert_eq!(
+                RelativeDuration::parse_from_rfc5545(input).unwrap(),
+                *expected,
+                "{}",
+                input
+            )
+        })
+    }
+
+    #[test]
+    fn test_parse_rfc5545_errors() {
+        [
+            "",
+            "P",
+            "PT",
+            "P1DT",
+            "P1Y",
+            "P1M",
+            "P1W1D",
+            "P1D1W",
+            "P1WT1H",
+            "PT1H1S",
+            "PT1S1M",
+            "PT1.5S",
+            "P-1D",
+            "+-P1D",
+            "1D",
+            "P1D1H",
+            "PT1H1M1S1S",
+            "P9999999999999999999W",
+            "P99999999999999W",
+        ]
+        .iter()
+        .for_each(|input| {
+            assert!(
+                RelativeDuration::parse_from_rfc5545(input).is_err(),
+                "{}",
+                input
+            )
+        })
+    }
+
+    #[test]
+    fn test_format_rfc5545() {
+        [
+            (RelativeDuration::zero(), "PT0S"),
+            (RelativeDuration::weeks(7), "P7W"),
+            (RelativeDuration::days(-15), "-P15D"),
+            (RelativeDuration::hours(-1), "-PT1H"),
+            (RelativeDuration::seconds(3601), "PT1H0M1S"),
+            (RelativeDuration::seconds(61), "PT1M1S"),
+            (
+                RelativeDuration::days(7) + RelativeDuration::seconds(1),
+                "P7DT1S",
+            ),
+            (
+                RelativeDuration::days(15)
+                    + RelativeDuration::hours(5)
+                    + RelativeDuration::seconds(20),
+                "P15DT5H0M20S",
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            assert_eq!(input.format_to_rfc5545().unwrap(), *expected);
+            assert_eq!(
+                RelativeDuration::parse_from_rfc5545(expected).unwrap(),
+                *input
+            );
+        });
+
+        assert!(RelativeDuration::months(1).format_to_rfc5545().is_err());
+        assert!(RelativeDuration::milliseconds(1500)
+            .format_to_rfc5545()
+            .is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use super::RelativeDuration;
+
+// The grammar accepted by `RelativeDuration::parse_from_iso8601`
+const ISO8601_PATTERN: &str = r"^P([-+]?\d+Y)?([-+]?\d+M)?([-+]?\d+W)?([-+]?\d+D)?(T([-+]?\d+H)?([-+]?\d+M)?([-+]?\d+([.,]\d*)?S)?)?$";
+
+impl JsonSchema for RelativeDuration {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "RelativeDuration".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "chronoutil::RelativeDuration".into()
+    }
+
+    /// Describes a `RelativeDuration` as serialized by default: an ISO 8601 duration string.
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": ISO8601_PATTERN,
+            "description": "An ISO 8601 duration, such as P1Y2M3DT4H5M6.7S. Components may be negative.",
+            "examples": ["P1M", "P1Y2M3DT4H5M6.7S", "P-1MT12H"],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::*;
+
+    #[test]
+    fn test_json_schema() {
+        let schema = schemars::schema_for!(RelativeDuration);
+        assert_eq!(schema.get("type"), Some(&"string".into()));
+
+        // The pattern agrees with the parser and formatter
+        let pattern = Regex::new(schema.get("pattern").unwrap().as_str().unwrap()).unwrap();
+        [
+            RelativeDuration::zero(),
+            RelativeDuration::months(14) + RelativeDuration::days(3),
+            RelativeDuration::months(-1) + RelativeDuration::hours(12),
+            RelativeDuration::milliseconds(-1500),
+            RelativeDuration::seconds(3661) + RelativeDuration::nanoseconds(1),
+        ]
+        .iter()
+        .for_each(|duration| {
+            let formatted = duration.format_to_iso8601();
+            assert!(pattern.is_match(&formatted), "{}", formatted);
+        });
+        [
+            "P",
+            "PT",
+            "P+1Y",
+            "P1W",
+            "P1Y2M3W4DT5H6M7,8S",
+            "PT-0.5S",
+            "PT+1.S",
+        ]
+        .iter()
+        .for_each(|input| {
+            assert!(pattern.is_match(input), "{}", input);
+            assert!(
+                RelativeDuration::parse_from_iso8601(input).is_ok(),
+                "{}",
+                input
+            );
+        });
+        [
+            "",
+            "1M",
+            "P1.5M",
+            "P1D2Y",
+            "PT1S1M",
+            "P1MT1S ",
+            "P+-1Y",
+            "PT.5S",
+            "PT1.+5S",
+            "PT1.123456789xS",
+        ]
+        .iter()
+        .for_each(|input| {
+            assert!(!pattern.is_match(input), "{}", input);
+            assert!(
+                RelativeDuration::parse_from_iso8601(input).is_err(),
+                "{}",
+                input
+            );
+        });
+    }
+}
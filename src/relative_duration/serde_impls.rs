@@ -0,0 +1,211 @@
+use std::fmt;
+
+use chrono::Duration;
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::RelativeDuration;
+
+// Splits the duration into whole seconds (rounded down) and non-negative nanoseconds.
+fn to_seconds_nanos(duration: Duration) -> (i64, u32) {
+    let seconds = duration.num_seconds();
+    let nanos = duration.subsec_nanos();
+    if nanos < 0 {
+        (seconds - 1, (nanos + 1_000_000_000) as u32)
+    } else {
+        (seconds, nanos as u32)
+    }
+}
+
+fn from_parts<E: Error>(months: i32, seconds: i64, nanos: u32) -> Result<RelativeDuration, E> {
+    if nanos >= 1_000_000_000 {
+        return Err(E::custom(format!("{} nanoseconds is out of range", nanos)));
+    }
+    Duration::new(seconds, nanos)
+        .map(|duration| RelativeDuration::months(months).with_duration(duration))
+        .ok_or_else(|| E::custom(format!("{} seconds is out of range", seconds)))
+}
+
+impl Serialize for RelativeDuration {
+    /// Serializes a `RelativeDuration` as an ISO 8601 duration string.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.format_to_iso8601())
+    }
+}
+
+struct Iso8601Visitor;
+
+impl<'de> Visitor<'de> for Iso8601Visitor {
+    type Value = RelativeDuration;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an ISO 8601 duration string")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        RelativeDuration::parse_from_iso8601(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for RelativeDuration {
+    /// Deserializes a `RelativeDuration` from an ISO 8601 duration string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Iso8601Visitor)
+    }
+}
+
+/// Serde adapter storing a [`RelativeDuration`] as a `{months, seconds, nanos}` struct.
+///
+/// The seconds are rounded down, so that the nanoseconds are always in the range
+/// `0..1_000_000_000`. This module is intended to be used through the Serde `with` attribute:
+///
+/// ```rust
+/// # use chronoutil::RelativeDuration;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "chronoutil::relative_duration::serde_structured")]
+///     interval: RelativeDuration,
+/// }
+/// ```
+pub mod serde_structured {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{from_parts, to_seconds_nanos, RelativeDuration};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "RelativeDuration", deny_unknown_fields)]
+    struct Structured {
+        months: i32,
+        seconds: i64,
+        nanos: u32,
+    }
+
+    /// Serializes a [`RelativeDuration`] as a `{months, seconds, nanos}` struct.
+    pub fn serialize<S: Serializer>(
+        duration: &RelativeDuration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let (seconds, nanos) = to_seconds_nanos(duration.duration);
+        Structured {
+            months: duration.months,
+            seconds,
+            nanos,
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserializes a [`RelativeDuration`] from a `{months, seconds, nanos}` struct.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RelativeDuration, D::Error> {
+        let s = Structured::deserialize(deserializer)?;
+        from_parts(s.months, s.seconds, s.nanos)
+    }
+}
+
+/// Serde adapter storing a [`RelativeDuration`] as a compact `(months, seconds, nanos)` tuple.
+///
+/// As [`serde_structured`], but without field names, which keeps binary encodings small. This
+/// module is intended to be used through the Serde `with` attribute:
+///
+/// ```rust
+/// # use chronoutil::RelativeDuration;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "chronoutil::relative_duration::serde_compact")]
+///     interval: RelativeDuration,
+/// }
+/// ```
+pub mod serde_compact {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{from_parts, to_seconds_nanos, RelativeDuration};
+
+    /// Serializes a [`RelativeDuration`] as a `(months, seconds, nanos)` tuple.
+    pub fn serialize<S: Serializer>(
+        duration: &RelativeDuration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let (seconds, nanos) = to_seconds_nanos(duration.duration);
+        (duration.months, seconds, nanos).serialize(serializer)
+    }
+
+    /// Deserializes a [`RelativeDuration`] from a `(months, seconds, nanos)` tuple.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RelativeDuration, D::Error> {
+        let (months, seconds, nanos) = <(i32, i64, u32)>::deserialize(deserializer)?;
+        from_parts(months, seconds, nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        iso: RelativeDuration,
+        #[serde(with = "serde_structured")]
+        structured: RelativeDuration,
+        #[serde(with = "serde_compact")]
+        compact: RelativeDuration,
+    }
+
+    fn record(duration: RelativeDuration) -> Record {
+        Record {
+            iso: duration,
+            structured: duration,
+            compact: duration,
+        }
+    }
+
+    #[test]
+    fn test_serde_json() {
+        let duration = RelativeDuration::months(14) - RelativeDuration::milliseconds(1500);
+        let json = serde_json::to_string(&record(duration)).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"iso":"P1Y2MT-1.5S","structured":{"months":14,"seconds":-2,"nanos":500000000},"compact":[14,-2,500000000]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Record>(&json).unwrap(),
+            record(duration)
+        );
+    }
+
+    #[test]
+    fn test_serde_json_errors() {
+        [
+            r#"{"iso":"1Y","structured":{"months":0,"seconds":0,"nanos":0},"compact":[0,0,0]}"#,
+            r#"{"iso":"P1Y","structured":{"months":0,"seconds":0,"nanos":1000000000},"compact":[0,0,0]}"#,
+            r#"{"iso":"P1Y","structured":{"months":0,"seconds":0},"compact":[0,0,0]}"#,
+            r#"{"iso":"P1Y","structured":{"months":0,"seconds":0,"nanos":0},"compact":[0,9223372036854775807,0]}"#,
+        ]
+        .iter()
+        .for_each(|json| assert!(serde_json::from_str::<Record>(json).is_err(), "{}", json));
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_serde_round_trip(
+            months in prop::num::i32::ANY,
+            secs in (i64::MIN/1000)..(i64::MAX/1000),
+            nanos in 0u32..1_000_000_000
+        ) {
+            let d = RelativeDuration::months(months).with_duration(Duration::new(secs, nanos).unwrap());
+
+            let json = serde_json::to_string(&record(d)).unwrap();
+            prop_assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record(d));
+
+            let bytes = bincode::serialize(&record(d)).unwrap();
+            prop_assert_eq!(bincode::deserialize::<Record>(&bytes).unwrap(), record(d));
+        }
+    }
+}
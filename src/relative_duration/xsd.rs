@@ -0,0 +1,570 @@
+//! Parsing, formatting and the partial order of
+//! [XML Schema durations](https://www.w3.org/TR/xmlschema11-2/#duration).
+//!
+//! An `xs:duration` keeps a month count separate from a seconds count, exactly as a
+//! `RelativeDuration` keeps its months separate from its `Duration`, so the two convert without
+//! loss. Both components must share one sign, and seconds are limited to nanosecond precision.
+//! The `xs:yearMonthDuration` and `xs:dayTimeDuration` subtypes allow only one of the components.
+use super::RelativeDuration;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::cmp::Ordering;
+use std::convert::TryInto;
+use std::fmt::Write;
+
+// The reference dateTimes from the XML Schema partial order on durations, as (year, month).
+// All of them fall on the first of the month, so shifting them never clamps the day.
+const XSD_REFERENCE_MONTHS: [(i64, i64); 4] = [(1696, 9), (1697, 2), (1903, 3), (1903, 7)];
+
+// The Gregorian calendar repeats every 400 years, which have this many days
+const DAYS_PER_400_YEARS: i64 = 146_097;
+
+// Days since the common era of the first of the given month, for any year
+fn days_from_ce(year: i64, month: u32) -> i64 {
+    let first = NaiveDate::from_ymd_opt(year.rem_euclid(400) as i32, month, 1).unwrap();
+    year.div_euclid(400) * DAYS_PER_400_YEARS + i64::from(first.num_days_from_ce())
+}
+
+fn total_nanos(duration: Duration) -> i128 {
+    duration.num_seconds() as i128 * 1_000_000_000 + duration.subsec_nanos() as i128
+}
+
+// Splits a datespec or timespec into the values preceding each allowed designator, which must
+// appear in the given order.
+fn split_fields<'a>(
+    spec: &'a str,
+    designators: &[char],
+    input: &str,
+) -> Result<Vec<Option<&'a str>>, String> {
+    let mut fields = vec![None; designators.len()];
+    let mut remainder = spec;
+    let mut next = 0;
+
+    while !remainder.is_empty() {
+        let end = remainder
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(|| format!("missing designator after {} in {}", remainder, input))?;
+        let (value, tail) = remainder.split_at(end);
+        let designator = tail.chars().next().unwrap();
+
+        let index = designators[next..]
+            .iter()
+            .position(|d| *d == designator)
+            .ok_or_else(|| format!("unexpected designator {} in {}", designator, input))?
+            + next;
+        if value.is_empty() {
+            return Err(format!("missing value before {} in {}", designator, input));
+        }
+
+        fields[index] = Some(value);
+        next = index + 1;
+        remainder = &tail[designator.len_utf8()..];
+    }
+
+    Ok(fields)
+}
+
+fn parse_integer(value: Option<&str>, input: &str) -> Result<i64, String> {
+    match value {
+        None => Ok(0),
+        Some(value) if value.bytes().all(|b| b.is_ascii_digit()) => value
+            .parse::<i64>()
+            .map_err(|_| format!("{} overflows in {}", value, input)),
+        Some(value) => Err(format!("{} is not an integer in {}", value, input)),
+    }
+}
+
+fn parse_seconds(value: Option<&str>, input: &str) -> Result<(i64, u32), String> {
+    let value = match value {
+        None => return Ok((0, 0)),
+        Some(value) => value,
+    };
+    // Either side of the decimal point may be empty, as in 1. or .5, but not both
+    let (int_string, fraction_string) = match value.split_once('.') {
+        Some(("", "")) => return Err(format!("{} is not a decimal in {}", value, input)),
+        Some(split) => split,
+        None => (value, ""),
+    };
+
+    let seconds = match int_string {
+        "" => 0,
+        _ => parse_integer(Some(int_string), input)?,
+    };
+    if !fraction_string.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("{} is not a decimal in {}", value, input));
+    }
+    if fraction_string.len() > 9 && fraction_string[9..].bytes().any(|b| b != b'0') {
+        return Err(format!(
+            "{} has more than nanosecond precision in {}",
+            value, input
+        ));
+    }
+    let nanos = fraction_string
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(9)
+        .collect::<String>()
+        .parse::<u32>()
+        .unwrap();
+
+    Ok((seconds, nanos))
+}
+
+fn parse_xsd(
+    input: &str,
+    allow_year_month: bool,
+    allow_day_time: bool,
+) -> Result<RelativeDuration, String> {
+    let overflow = || format!("integer overflow on constructing duration from {}", input);
+
+    let (negative, remainder) = match input.strip_prefix('-') {
+        Some(remainder) => (true, remainder),
+        None => (false, input),
+    };
+    let remainder = remainder
+        .strip_prefix('P')
+        .ok_or_else(|| format!("{} was not prefixed with P or -P", input))?;
+    let (datespec, timespec) = match remainder.split_once('T') {
+        Some((_, "")) => return Err(format!("{} has no fields after T", input)),
+        Some((datespec, timespec)) => (datespec, Some(timespec)),
+        None => (remainder, None),
+    };
+    if datespec.is_empty() && timespec.is_none() {
+        return Err(format!("{} has no fields", input));
+    }
+
+    let date_designators: &[char] = match (allow_year_month, allow_day_time) {
+        (true, true) => &['Y', 'M', 'D'],
+        (true, false) => &['Y', 'M'],
+        _ => &['D'],
+    };
+    let date_fields = split_fields(datespec, date_designators, input)?;
+    let time_fields = match timespec {
+        Some(_) if !allow_day_time => return Err(format!("{} has a time component", input)),
+        Some(timespec) => split_fields(timespec, &['H', 'M', 'S'], input)?,
+        None => vec![None; 3],
+    };
+
+    let (years, months, days) = if allow_year_month {
+        (
+            parse_integer(date_fields[0], input)?,
+            parse_integer(date_fields[1], input)?,
+            parse_integer(date_fields.get(2).copied().flatten(), input)?,
+        )
+    } else {
+        (0, 0, parse_integer(date_fields[0], input)?)
+    };
+    let hours = parse_integer(time_fields[0], input)?;
+    let minutes = parse_integer(time_fields[1], input)?;
+    let (seconds, nanos) = parse_seconds(time_fields[2], input)?;
+
+    let months: i32 = years
+        .checked_mul(12)
+        .and_then(|x| x.checked_add(months))
+        .and_then(|x| x.try_into().ok())
+        .ok_or_else(overflow)?;
+    let seconds = days
+        .checked_mul(24)
+        .and_then(|x| x.checked_add(hours))
+        .and_then(|x| x.checked_mul(60))
+        .and_then(|x| x.checked_add(minutes))
+        .and_then(|x| x.checked_mul(60))
+        .and_then(|x| x.checked_add(seconds))
+        .ok_or_else(overflow)?;
+    let duration = RelativeDuration::months(months)
+        .with_duration(Duration::new(seconds, nanos).ok_or_else(overflow)?);
+
+    Ok(if negative { -duration } else { duration })
+}
+
+impl RelativeDuration {
+    /// Parses an [XML Schema `xs:duration`](https://www.w3.org/TR/xmlschema11-2/#duration) into a
+    /// [`RelativeDuration`] value.
+    ///
+    /// This is stricter than [`RelativeDuration::parse_from_iso8601`]: only a single leading sign
+    /// is allowed, weeks are not allowed, all fields but the seconds must be unsigned integers and
+    /// there must be at least one field (and at least one after any `T`). Fractional seconds
+    /// beyond nanosecond precision must be zero.
+    ///
+    /// # Errors
+    ///
+    /// - Input which is not a valid `xs:duration`
+    /// - Values which overflow a [`RelativeDuration`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     RelativeDuration::parse_from_xsd_duration("-P1Y2DT3.5S").unwrap(),
+    ///     -(RelativeDuration::years(1)
+    ///         + RelativeDuration::days(2)
+    ///         + RelativeDuration::milliseconds(3500)),
+    /// );
+    /// assert!(RelativeDuration::parse_from_xsd_duration("P1W").is_err());
+    /// assert!(RelativeDuration::parse_from_xsd_duration("P-1Y").is_err());
+    /// ```
+    pub fn parse_from_xsd_duration(input: &str) -> Result<RelativeDuration, String> {
+        parse_xsd(input, true, true)
+    }
+
+    /// Parses an [XML Schema `xs:yearMonthDuration`](https://www.w3.org/TR/xmlschema11-2/#yearMonthDuration)
+    /// into a [`RelativeDuration`] value.
+    ///
+    /// As [`RelativeDuration::parse_from_xsd_duration`], but only years and months are allowed.
+    pub fn parse_from_xsd_year_month_duration(input: &str) -> Result<RelativeDuration, String> {
+        parse_xsd(input, true, false)
+    }
+
+    /// Parses an [XML Schema `xs:dayTimeDuration`](https://www.w3.org/TR/xmlschema11-2/#dayTimeDuration)
+    /// into a [`RelativeDuration`] value.
+    ///
+    /// As [`RelativeDuration::parse_from_xsd_duration`], but years and months are not allowed.
+    pub fn parse_from_xsd_day_time_duration(input: &str) -> Result<RelativeDuration, String> {
+        parse_xsd(input, false, true)
+    }
+
+    /// Returns true if the duration is a valid `xs:yearMonthDuration`, i.e. it has no
+    /// `Duration` component.
+    #[inline]
+    pub fn is_xsd_year_month_duration(&self) -> bool {
+        self.duration.is_zero()
+    }
+
+    /// Returns true if the duration is a valid `xs:dayTimeDuration`, i.e. it has no months.
+    #[inline]
+    pub fn is_xsd_day_time_duration(&self) -> bool {
+        self.months == 0
+    }
+
+    /// Formats a [`RelativeDuration`] value into the canonical
+    /// [XML Schema `xs:duration`](https://www.w3.org/TR/xmlschema11-2/#duration) form.
+    ///
+    /// # Errors
+    ///
+    /// - The months and `Duration` have opposite signs, which XML Schema cannot represent
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     (-RelativeDuration::months(14) - RelativeDuration::hours(25)).format_to_xsd_duration(),
+    ///     Ok("-P1Y2M1DT1H".to_string()),
+    /// );
+    /// assert_eq!(RelativeDuration::zero().format_to_xsd_duration(), Ok("PT0S".to_string()));
+    /// assert!((RelativeDuration::months(1) - RelativeDuration::days(1))
+    ///     .format_to_xsd_duration()
+    ///     .is_err());
+    /// ```
+    pub fn format_to_xsd_duration(&self) -> Result<String, String> {
+        let (negative, months, duration) = self.xsd_components()?;
+        let mut out = String::new();
+        if negative {
+            out.push('-');
+        }
+        out.push('P');
+
+        let seconds = duration.num_seconds();
+        let nanos = duration.subsec_nanos();
+        let fields = [
+            (months / 12, 'Y'),
+            (months % 12, 'M'),
+            (seconds / (24 * 60 * 60), 'D'),
+        ];
+        fields.iter().filter(|x| x.0 != 0).for_each(|x| {
+            let _ = write!(out, "{}{}", x.0, x.1);
+        });
+
+        let seconds = seconds % (24 * 60 * 60);
+        if seconds != 0 || nanos != 0 || (months == 0 && duration.is_zero()) {
+            out.push('T');
+            let fields = [(seconds / (60 * 60), 'H'), (seconds / 60 % 60, 'M')];
+            fields.iter().filter(|x| x.0 != 0).for_each(|x| {
+                let _ = write!(out, "{}{}", x.0, x.1);
+            });
+            if seconds % 60 != 0 || nanos != 0 || duration.is_zero() {
+                write_seconds(&mut out, seconds % 60, nanos);
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Formats a [`RelativeDuration`] value into the canonical
+    /// [XML Schema `xs:yearMonthDuration`](https://www.w3.org/TR/xmlschema11-2/#yearMonthDuration)
+    /// form.
+    ///
+    /// # Errors
+    ///
+    /// - The duration has a non-zero `Duration` component
+    pub fn format_to_xsd_year_month_duration(&self) -> Result<String, String> {
+        if !self.is_xsd_year_month_duration() {
+            return Err(format!(
+                "{} is not an xs:yearMonthDuration",
+                self.format_to_iso8601()
+            ));
+        }
+        if self.months == 0 {
+            Ok("P0M".to_string())
+        } else {
+            self.format_to_xsd_duration()
+        }
+    }
+
+    /// Formats a [`RelativeDuration`] value into the canonical
+    /// [XML Schema `xs:dayTimeDuration`](https://www.w3.org/TR/xmlschema11-2/#dayTimeDuration)
+    /// form.
+    ///
+    /// # Errors
+    ///
+    /// - The duration has non-zero months
+    pub fn format_to_xsd_day_time_duration(&self) -> Result<String, String> {
+        if !self.is_xsd_day_time_duration() {
+            return Err(format!(
+                "{} is not an xs:dayTimeDuration",
+                self.format_to_iso8601()
+            ));
+        }
+        self.format_to_xsd_duration()
+    }
+
+    /// Compares two durations under the XML Schema partial order.
+    ///
+    /// Each duration is added to the reference dateTimes 1696-09-01T00:00:00Z,
+    /// 1697-02-01T00:00:00Z, 1903-03-01T00:00:00Z and 1903-07-01T00:00:00Z. The durations are
+    /// ordered if the results are ordered the same way at all four, and are otherwise
+    /// incomparable (`None`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use chronoutil::RelativeDuration;
+    ///
+    /// let year = RelativeDuration::years(1);
+    /// assert_eq!(year.xsd_partial_cmp(&RelativeDuration::months(12)), Some(Ordering::Equal));
+    /// assert_eq!(year.xsd_partial_cmp(&RelativeDuration::days(364)), Some(Ordering::Greater));
+    /// assert_eq!(year.xsd_partial_cmp(&RelativeDuration::days(365)), None);
+    /// ```
+    pub fn xsd_partial_cmp(&self, other: &RelativeDuration) -> Option<Ordering> {
+        let mut orderings = XSD_REFERENCE_MONTHS.iter().map(|(year, month)| {
+            let instant = |d: &RelativeDuration| {
+                let months = year * 12 + (month - 1) + d.months as i64;
+                let days = days_from_ce(months.div_euclid(12), months.rem_euclid(12) as u32 + 1);
+                days as i128 * 86_400_000_000_000 + total_nanos(d.duration)
+            };
+            instant(self).cmp(&instant(other))
+        });
+
+        let first = orderings.next().unwrap();
+        if orderings.all(|o| o == first) {
+            Some(first)
+        } else {
+            None
+        }
+    }
+
+    // Returns the sign and magnitudes of the months and duration
+    fn xsd_components(&self) -> Result<(bool, i64, Duration), String> {
+        let negative_months = self.months < 0;
+        let negative_duration = self.duration < Duration::zero();
+        if (negative_months && self.duration > Duration::zero())
+            || (negative_duration && self.months > 0)
+        {
+            return Err(format!(
+                "{} has components of mixed sign, which xs:duration does not support",
+                self.format_to_iso8601()
+            ));
+        }
+
+        Ok((
+            negative_months || negative_duration,
+            (self.months as i64).abs(),
+            self.duration.abs(),
+        ))
+    }
+}
+
+fn write_seconds(out: &mut String, seconds: i64, nanos: i32) {
+    let _ = write!(out, "{}", seconds);
+    if nanos != 0 {
+        let nanos_str_raw = format!("{:0>9}", nanos);
+        out.push('.');
+        out.push_str(nanos_str_raw.trim_end_matches('0'));
+    }
+    out.push('S');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xsd_duration() {
+        [
+            ("P1Y", RelativeDuration::years(1)),
+            ("P1Y2M3DT4H5M6S", {
+                RelativeDuration::months(14)
+                    + RelativeDuration::days(3)
+                    + RelativeDuration::hours(4)
+                    + RelativeDuration::minutes(5)
+                    + RelativeDuration::seconds(6)
+            }),
+            ("-P13M", RelativeDuration::months(-13)),
+            ("PT36H", RelativeDuration::hours(36)),
+            ("P0D", RelativeDuration::zero()),
+            ("-PT0.5S", RelativeDuration::milliseconds(-500)),
+            ("PT1.0000000000S", RelativeDuration::seconds(1)),
+            ("PT1.S", RelativeDuration::seconds(1)),
+            ("PT.5S", RelativeDuration::milliseconds(500)),
+            ("-PT.000001S", RelativeDuration::microseconds(-1)),
+            ("P1DT0.000000001S", {
+                RelativeDuration::days(1) + RelativeDuration::nanoseconds(1)
+            }),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            assert_eq!(
+                RelativeDuration::parse_from_xsd_duration(input).unwrap(),
+                *expected,
+                "{}",
+                input
+            )
+        })
+    }
+
+    #[test]
+    fn test_parse_xsd_duration_errors() {
+        [
+            "",
+            "P",
+            "-P",
+            "+P1Y",
+            "1Y",
+            "PT",
+            "P1YT",
+            "P1W",
+            "P-1Y",
+            "P1Y-2M",
+            "P1.5Y",
+            "PT.S",
+            "PT1..5S",
+            "PT1.5.S",
+            "P1M1Y",
+            "P1Y1Y",
+            "PT1H1D",
+            "P1H",
+            "PT1Y",
+            "P1",
+            "PY",
+            "PT0.0000000001S",
+            "P99999999999Y",
+        ]
+        .iter()
+        .for_each(|input| {
+            assert!(
+                RelativeDuration::parse_from_xsd_duration(input).is_err(),
+                "{}",
+                input
+            )
+        })
+    }
+
+    #[test]
+    fn test_parse_xsd_subtypes() {
+        assert_eq!(
+            RelativeDuration::parse_from_xsd_year_month_duration("-P1Y2M").unwrap(),
+            RelativeDuration::months(-14)
+        );
+        assert!(RelativeDuration::parse_from_xsd_year_month_duration("P1D").is_err());
+        assert!(RelativeDuration::parse_from_xsd_year_month_duration("P1YT1H").is_err());
+
+        assert_eq!(
+            RelativeDuration::parse_from_xsd_day_time_duration("P1DT2M").unwrap(),
+            RelativeDuration::days(1) + RelativeDuration::minutes(2)
+        );
+        assert!(RelativeDuration::parse_from_xsd_day_time_duration("P1Y").is_err());
+        assert!(RelativeDuration::parse_from_xsd_day_time_duration("P1MT1H").is_err());
+
+        assert!(RelativeDuration::years(1).is_xsd_year_month_duration());
+        assert!(!RelativeDuration::years(1).is_xsd_day_time_duration());
+        assert!(RelativeDuration::zero().is_xsd_year_month_duration());
+        assert!(RelativeDuration::zero().is_xsd_day_time_duration());
+    }
+
+    #[test]
+    fn test_format_xsd_duration() {
+        [
+            (RelativeDuration::zero(), "PT0S"),
+            (RelativeDuration::years(1), "P1Y"),
+            (RelativeDuration::months(-14), "-P1Y2M"),
+            (RelativeDuration::hours(36), "P1DT12H"),
+            (RelativeDuration::minutes(-61), "-PT1H1M"),
+            (RelativeDuration::nanoseconds(-1), "-PT0.000000001S"),
+            (
+                RelativeDuration::months(1) + RelativeDuration::milliseconds(60_500),
+                "P1MT1M0.5S",
+            ),
+        ]
+        .iter()
+        .for_each(|(input, expected)| {
+            assert_eq!(input.format_to_xsd_duration().unwrap(), *expected);
+            assert_eq!(
+                RelativeDuration::parse_from_xsd_duration(expected).unwrap(),
+                *input
+            );
+        });
+
+        assert_eq!(
+            RelativeDuration::zero().format_to_xsd_year_month_duration(),
+            Ok("P0M".to_string())
+        );
+        assert_eq!(
+            RelativeDuration::zero().format_to_xsd_day_time_duration(),
+            Ok("PT0S".to_string())
+        );
+        assert!(RelativeDuration::days(1)
+            .format_to_xsd_year_month_duration()
+            .is_err());
+        assert!(RelativeDuration::months(1)
+            .format_to_xsd_day_time_duration()
+            .is_err());
+        assert!((RelativeDuration::days(1) - RelativeDuration::months(1))
+            .format_to_xsd_duration()
+            .is_err());
+    }
+
+    #[test]
+    fn test_xsd_partial_cmp() {
+        let cmp = |a: &str, b: &str| {
+            RelativeDuration::parse_from_xsd_duration(a)
+                .unwrap()
+                .xsd_partial_cmp(&RelativeDuration::parse_from_xsd_duration(b).unwrap())
+        };
+
+        // The examples from the XML Schema specification
+        assert_eq!(cmp("P1Y", "P364D"), Some(Ordering::Greater));
+        assert_eq!(cmp("P1Y", "P365D"), None);
+        assert_eq!(cmp("P1Y", "P366D"), None);
+        assert_eq!(cmp("P1Y", "P367D"), Some(Ordering::Less));
+        assert_eq!(cmp("P1M", "P27D"), Some(Ordering::Greater));
+        assert_eq!(cmp("P1M", "P28D"), None);
+        assert_eq!(cmp("P1M", "P29D"), None);
+        assert_eq!(cmp("P1M", "P30D"), None);
+        assert_eq!(cmp("P1M", "P31D"), None);
+        assert_eq!(cmp("P1M", "P32D"), Some(Ordering::Less));
+        assert_eq!(cmp("P5M", "P149D"), Some(Ordering::Greater));
+        assert_eq!(cmp("P5M", "P150D"), None);
+        assert_eq!(cmp("P5M", "P153D"), None);
+        assert_eq!(cmp("P5M", "P154D"), Some(Ordering::Less));
+
+        assert_eq!(cmp("P1D", "PT24H"), Some(Ordering::Equal));
+        assert_eq!(cmp("-P1M", "P0D"), Some(Ordering::Less));
+        assert_eq!(
+            RelativeDuration::months(i32::MAX).xsd_partial_cmp(&RelativeDuration::months(i32::MIN)),
+            Some(Ordering::Greater)
+        );
+    }
+}
@@ -1,21 +1,34 @@
 //! Implements `DateRule` - an iterator yielding evenly spaced dates.
+use std::fmt::Display;
 use std::iter::Iterator;
+use std::str::FromStr;
 
-use super::delta::with_day;
+use core::ops::Add;
+
+use super::calendar::{Calendar, Gregorian};
+use super::delta::{resolve_day, resolve_weekday, DayOutOfRange, WeekdayOrdinalOutOfRange};
 use super::relative_duration::RelativeDuration;
-use chrono::{Date, DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone};
+use super::week::{WeekNumbering, Weeks};
+use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Weekday};
 
 /// DateRule is an iterator for yielding evenly spaced dates
 /// according to a given RelativeDuration. It avoids some
 /// of the pitfalls that naive usage of RelativeDuration
 /// can incur.
+///
+/// Months and years are counted in the Gregorian calendar, unless another
+/// [`Calendar`] is given with [`DateRule::with_calendar`]. Rules whose frequency is a whole
+/// number of years step with [`Calendar::shift_years`], and other rules with
+/// [`Calendar::shift_months`].
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct DateRule<D: Datelike> {
+pub struct DateRule<D: Datelike, C = Gregorian> {
     freq: RelativeDuration,
     start: D,
     end: Option<D>,
     count: Option<usize>,
-    rolling_day: Option<u32>,
+    rolling_day: Option<i32>,
+    rolling_weekday: Option<(Weekday, i32)>,
+    calendar: C,
     _current_count: usize,
 }
 
@@ -32,6 +45,8 @@ where
             end: None,
             count: None,
             rolling_day: None,
+            rolling_weekday: None,
+            calendar: Gregorian,
             _current_count: 0,
         }
     }
@@ -80,6 +95,69 @@ where
         Self::new(from, RelativeDuration::years(1))
     }
 
+    /// Creates a `DateRule` yielding the same weekday in each numbered week of a
+    /// [`WeekNumbering`], such as ISO or MMWR weeks.
+    ///
+    /// In week numberings with partial weeks at the ends of a year, weekdays which are missing
+    /// from a week move onto its nearest day, as in [`WeekNumbering::date_from_week`].
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::week::Us;
+    /// # use chronoutil::DateRule;
+    /// let start = NaiveDate::from_ymd_opt(2021, 12, 27).unwrap();
+    /// let rule = DateRule::numbered_weeks(start, Us).with_count(3);
+    ///
+    /// // US week 1 of 2022 is only Saturday 1 January
+    /// assert_eq!(
+    ///     rule.collect::<Vec<_>>(),
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2021, 12, 27).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    #[inline]
+    pub fn numbered_weeks<W: WeekNumbering + Copy>(from: D, numbering: W) -> DateRule<D, Weeks<W>> {
+        Self::monthly(from).with_calendar(Weeks(numbering))
+    }
+}
+
+impl<D, C> DateRule<D, C>
+where
+    D: Datelike + Copy,
+    C: Calendar + Copy,
+{
+    /// Counts months and years in the given calendar, rather than the Gregorian calendar.
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::NaiveDate;
+    /// # use chronoutil::calendar::{Calendar, Julian};
+    /// # use chronoutil::DateRule;
+    /// // Julian 1699-12-31
+    /// let start = NaiveDate::from_ymd_opt(1700, 1, 10).unwrap();
+    /// let rule = DateRule::monthly(start).with_calendar(Julian).with_count(3);
+    ///
+    /// assert_eq!(
+    ///     rule.map(|date| Julian.to_calendar_date(date)).collect::<Vec<_>>(),
+    ///     vec![(1699, 12, 31), (1700, 1, 31), (1700, 2, 29)]
+    /// );
+    /// ```
+    pub fn with_calendar<C2: Calendar>(&self, calendar: C2) -> DateRule<D, C2> {
+        DateRule {
+            freq: self.freq,
+            start: self.start,
+            end: self.end,
+            count: self.count,
+            rolling_day: self.rolling_day,
+            rolling_weekday: self.rolling_weekday,
+            calendar,
+            _current_count: self._current_count,
+        }
+    }
+
     /// Limits the `DateRule` to a given number of dates.
     pub fn with_count(&self, number: usize) -> Self {
         Self {
@@ -88,6 +166,8 @@ where
             end: None,
             count: Some(number),
             rolling_day: self.rolling_day,
+            rolling_weekday: self.rolling_weekday,
+            calendar: self.calendar,
             _current_count: 0,
         }
     }
@@ -106,13 +186,17 @@ where
             end: Some(end),
             count: None,
             rolling_day: self.rolling_day,
+            rolling_weekday: self.rolling_weekday,
+            calendar: self.calendar,
             _current_count: 0,
         }
     }
 
     /// Ensure the `DateRule` yields new dates which are *always* fall on the
-    /// given rolling day (modulo backwards shifting for month ends). Returns
-    /// Err if the rolling day is not in the range 1-31.
+    /// given rolling day (modulo backwards shifting for month ends). Negative
+    /// rolling days count back from the end of the month, so -1 is always the
+    /// last day. Returns Err if the rolling day is not in the range 1-31 or
+    /// -31 to -1. Replaces any rolling weekday.
     ///
     /// For example:
     /// ```rust
@@ -126,31 +210,185 @@ where
     /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap());
     /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 5, 31).unwrap());
     /// // etc.
+    ///
+    /// // Three days before the end of each month
+    /// let mut rule = DateRule::monthly(start).with_rolling_day(-4).unwrap();
+    /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 2, 26).unwrap());
+    /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 3, 28).unwrap());
+    /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2020, 4, 27).unwrap());
     /// ```
     ///
     /// It produces values equivalent to
     /// ```ignore
     /// rule.map(|d| with_day(d, rolling_day).unwrap())
     /// ```
-    pub fn with_rolling_day(&self, rolling_day: u32) -> Result<Self, String> {
-        if rolling_day == 0 || rolling_day > 31 {
-            Err(format!("Rolling day {} not in range 1-31", rolling_day))
+    pub fn with_rolling_day(&self, rolling_day: i32) -> Result<Self, DayOutOfRange> {
+        // Any month will do for validation
+        resolve_day(2000, 1, rolling_day)?;
+        Ok(Self {
+            freq: self.freq,
+            start: self.start,
+            end: self.end,
+            count: self.count,
+            rolling_day: Some(rolling_day),
+            rolling_weekday: None,
+            calendar: self.calendar,
+            _current_count: self._current_count,
+        })
+    }
+
+    /// Ensure the `DateRule` yields new dates which *always* fall on the `n`th
+    /// given weekday of their month, e.g. the 3rd Wednesday. Negative ordinals
+    /// count back from the end of the month, so -1 is always the last such
+    /// weekday. A 5th weekday in a month with only four is shifted back to the
+    /// 4th, and likewise for -5. Returns Err if the ordinal is not in the range
+    /// 1-5 or -5 to -1. Replaces any rolling day.
+    ///
+    /// For example:
+    /// ```rust
+    /// # use chrono::{NaiveDate, Weekday};
+    /// # use chronoutil::DateRule;
+    /// let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    /// let mut rule = DateRule::monthly(start).with_rolling_weekday(Weekday::Fri, -1).unwrap();
+    ///
+    /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2024, 3, 29).unwrap());
+    /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2024, 4, 26).unwrap());
+    /// assert_eq!(rule.next().unwrap(), NaiveDate::from_ymd_opt(2024, 5, 31).unwrap());
+    /// // etc.
+    /// ```
+    ///
+    /// Unlike repeatedly applying
+    /// [`shift_months_by_weekday`](crate::delta::shift_months_by_weekday), a 5th weekday is
+    /// not forgotten after passing through a month with only four.
+    pub fn with_rolling_weekday(
+        &self,
+        weekday: Weekday,
+        n: i32,
+    ) -> Result<Self, WeekdayOrdinalOutOfRange> {
+        resolve_weekday(2000, 1, weekday, n)?;
+        Ok(Self {
+            freq: self.freq,
+            start: self.start,
+            end: self.end,
+            count: self.count,
+            rolling_day: None,
+            rolling_weekday: Some((weekday, n)),
+            calendar: self.calendar,
+            _current_count: self._current_count,
+        })
+    }
+}
+
+impl<D, C> DateRule<D, C>
+where
+    D: Datelike + Clone + Add<Duration, Output = D>,
+    C: Calendar,
+{
+    // Returns the nth date, moved onto the rolling day or weekday if there is one
+    fn nth_date(&self, n: usize) -> D {
+        let shift = self.freq * n as i32;
+        // Whole years are shifted as years, which differs from months in some calendars
+        let date = if shift.months != 0 && self.freq.months % 12 == 0 {
+            self.calendar
+                .shift_years(self.start.clone(), shift.months / 12)
         } else {
-            Ok(Self {
-                freq: self.freq,
-                start: self.start,
-                end: self.end,
-                count: self.count,
-                rolling_day: Some(rolling_day),
-                _current_count: self._current_count,
-            })
+            self.calendar.shift_months(self.start.clone(), shift.months)
+        } + shift.duration;
+        if let Some(rolling_day) = self.rolling_day {
+            self.calendar.with_day_opt(date, rolling_day).unwrap()
+        } else if let Some((weekday, n)) = self.rolling_weekday {
+            self.calendar
+                .with_weekday_of_month_opt(date, weekday, n)
+                .unwrap()
+        } else {
+            date
         }
     }
 }
 
+impl<D> DateRule<D>
+where
+    D: Datelike + Copy + FromStr,
+    D::Err: Display,
+{
+    /// Parses an [ISO 8601 repeating interval](https://en.wikipedia.org/wiki/ISO_8601#Repeating_intervals)
+    /// of the form `R<count>/<start>/<duration>` into a `DateRule`.
+    ///
+    /// The count may be omitted, as in `R/<start>/<duration>`, for a rule with no limit. The
+    /// start is parsed with the `FromStr` implementation of the date type, and the duration with
+    /// [`RelativeDuration::parse_from_iso8601`].
+    ///
+    /// # Errors
+    ///
+    /// - Specs which are not of the above form
+    /// - An invalid count, start date or duration
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use chronoutil::DateRule;
+    ///
+    /// let rule = DateRule::<NaiveDate>::parse_from_iso8601("R3/2025-01-31/P1M").unwrap();
+    /// assert_eq!(
+    ///     rule.collect::<Vec<_>>(),
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn parse_from_iso8601(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split('/');
+        let (repetitions, start, freq) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(repetitions), Some(start), Some(freq)) if parts.next().is_none() => {
+                (repetitions, start, freq)
+            }
+            _ => {
+                return Err(format!(
+                    "{} is not of the form R<count>/<start>/<duration>",
+                    spec
+                ))
+            }
+        };
+
+        let count =
+            match repetitions.strip_prefix('R') {
+                Some("") => None,
+                Some(count) => Some(count.parse::<usize>().map_err(|_| {
+                    format!("{} is not a valid repetition count in {}", count, spec)
+                })?),
+                None => return Err(format!("{} was not prefixed with R", spec)),
+            };
+        let start = start
+            .parse::<D>()
+            .map_err(|e| format!("{} is not a valid start in {}: {}", start, spec, e))?;
+        let rule = Self::new(start, RelativeDuration::parse_from_iso8601(freq)?);
+
+        Ok(match count {
+            Some(count) => rule.with_count(count),
+            None => rule,
+        })
+    }
+}
+
+impl<D> FromStr for DateRule<D>
+where
+    D: Datelike + Copy + FromStr,
+    D::Err: Display,
+{
+    type Err = String;
+
+    /// Parses an ISO 8601 repeating interval. See [`DateRule::parse_from_iso8601`].
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        Self::parse_from_iso8601(spec)
+    }
+}
+
 // The following is just copy-pasta, mostly because we
 // can't impl<T> Add<RelativeDuration> for T with T: Datelike
-impl Iterator for DateRule<NaiveDate> {
+impl<C: Calendar> Iterator for DateRule<NaiveDate, C> {
     type Item = NaiveDate;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -158,10 +396,7 @@ impl Iterator for DateRule<NaiveDate> {
             return None;
         }
 
-        let mut current_date = self.start + self.freq * self._current_count as i32;
-        if let Some(rolling_day) = self.rolling_day {
-            current_date = with_day(current_date, rolling_day).unwrap();
-        }
+        let current_date = self.nth_date(self._current_count);
 
         if let Some(end) = &self.end {
             if (*end >= self.start && current_date >= *end)
@@ -176,7 +411,7 @@ impl Iterator for DateRule<NaiveDate> {
     }
 }
 
-impl Iterator for DateRule<NaiveDateTime> {
+impl<C: Calendar> Iterator for DateRule<NaiveDateTime, C> {
     type Item = NaiveDateTime;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -184,10 +419,7 @@ impl Iterator for DateRule<NaiveDateTime> {
             return None;
         }
 
-        let mut current_date = self.start + self.freq * self._current_count as i32;
-        if let Some(rolling_day) = self.rolling_day {
-            current_date = with_day(current_date, rolling_day).unwrap();
-        }
+        let current_date = self.nth_date(self._current_count);
 
         if let Some(end) = &self.end {
             if (*end >= self.start && current_date >= *end)
@@ -202,9 +434,10 @@ impl Iterator for DateRule<NaiveDateTime> {
     }
 }
 
-impl<Tz> Iterator for DateRule<Date<Tz>>
+impl<Tz, C> Iterator for DateRule<Date<Tz>, C>
 where
     Tz: TimeZone,
+    C: Calendar,
 {
     type Item = Date<Tz>;
 
@@ -213,10 +446,7 @@ where
             return None;
         }
 
-        let mut current_date = self.start.clone() + self.freq * self._current_count as i32;
-        if let Some(rolling_day) = self.rolling_day {
-            current_date = with_day(current_date, rolling_day).unwrap();
-        }
+        let current_date = self.nth_date(self._current_count);
 
         if let Some(end) = &self.end {
             if (*end >= self.start && current_date >= *end)
@@ -231,9 +461,10 @@ where
     }
 }
 
-impl<Tz> Iterator for DateRule<DateTime<Tz>>
+impl<Tz, C> Iterator for DateRule<DateTime<Tz>, C>
 where
     Tz: TimeZone,
+    C: Calendar,
 {
     type Item = DateTime<Tz>;
 
@@ -242,10 +473,7 @@ where
             return None;
         }
 
-        let mut current_date = self.start.clone() + self.freq * self._current_count as i32;
-        if let Some(rolling_day) = self.rolling_day {
-            current_date = with_day(current_date, rolling_day).unwrap();
-        }
+        let current_date = self.nth_date(self._current_count);
 
         if let Some(end) = &self.end {
             if (*end >= self.start && current_date >= *end)
@@ -260,12 +488,363 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use chrono::{Datelike, Weekday};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{resolve_day, resolve_weekday, Calendar, DateRule, RelativeDuration};
+
+    // Every field is always written, so that non self-describing formats round-trip
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+    #[cfg_attr(feature = "schemars", schemars(rename = "DateRule_for_{D}_and_{C}"))]
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(bound = "D: schemars::JsonSchema, C: schemars::JsonSchema + Default + Serialize")
+    )]
+    #[serde(rename = "DateRule", deny_unknown_fields)]
+    struct Repr<D, C> {
+        start: D,
+        freq: RelativeDuration,
+        end: Option<D>,
+        count: Option<usize>,
+        #[cfg_attr(feature = "schemars", schemars(range(min = -31, max = 31)))]
+        rolling_day: Option<i32>,
+        // Missing from rules written before rolling weekdays were added
+        #[serde(default)]
+        rolling_weekday: Option<(Weekday, i32)>,
+        // Missing from rules written before calendars were added, which were all Gregorian
+        #[serde(default)]
+        calendar: C,
+        /// The number of dates already yielded.
+        position: usize,
+    }
+
+    #[cfg(feature = "schemars")]
+    impl<D, C> schemars::JsonSchema for DateRule<D, C>
+    where
+        D: Datelike + schemars::JsonSchema,
+        C: Calendar + Default + Serialize + schemars::JsonSchema,
+    {
+        fn schema_name() -> std::borrow::Cow<'static, str> {
+            Repr::<D, C>::schema_name()
+        }
+
+        fn schema_id() -> std::borrow::Cow<'static, str> {
+            Repr::<D, C>::schema_id()
+        }
+
+        /// Describes a `DateRule` as serialized: its start, frequency, end, count, rolling day,
+        /// calendar and position.
+        fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            Repr::<D, C>::json_schema(generator)
+        }
+    }
+
+    impl<D, C> Serialize for DateRule<D, C>
+    where
+        D: Datelike + Serialize,
+        C: Calendar + Serialize,
+    {
+        /// Serializes the start, frequency, end, count, rolling day and calendar of the
+        /// `DateRule`, along with the number of dates already yielded.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Repr {
+                start: &self.start,
+                freq: self.freq,
+                end: self.end.as_ref(),
+                count: self.count,
+                rolling_day: self.rolling_day,
+                rolling_weekday: self.rolling_weekday,
+                calendar: &self.calendar,
+                position: self._current_count,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, D, C> Deserialize<'de> for DateRule<D, C>
+    where
+        D: Datelike + Deserialize<'de>,
+        C: Calendar + Default + Deserialize<'de>,
+    {
+        /// Deserializes a `DateRule`, which resumes from where the serialized rule left off.
+        ///
+        /// Rules written without a calendar use the calendar's default.
+        fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+            let repr = Repr::<D, C>::deserialize(deserializer)?;
+            if let Some(rolling_day) = repr.rolling_day {
+                resolve_day(2000, 1, rolling_day).map_err(De::Error::custom)?;
+            }
+            if let Some((weekday, n)) = repr.rolling_weekday {
+                resolve_weekday(2000, 1, weekday, n).map_err(De::Error::custom)?;
+                if repr.rolling_day.is_some() {
+                    return Err(De::Error::custom(
+                        "rolling_day and rolling_weekday cannot both be set",
+                    ));
+                }
+            }
+
+            Ok(DateRule {
+                freq: repr.freq,
+                start: repr.start,
+                end: repr.end,
+                count: repr.count,
+                rolling_day: repr.rolling_day,
+                rolling_weekday: repr.rolling_weekday,
+                calendar: repr.calendar,
+                _current_count: repr.position,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+        use super::*;
+
+        #[test]
+        fn test_serde_json() {
+            let start = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+            let rule = DateRule::monthly(start)
+                .with_count(3)
+                .with_rolling_day(30)
+                .unwrap();
+
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                json,
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":30,"rolling_weekday":null,"calendar":null,"position":0}"#
+            );
+            let parsed: DateRule<NaiveDate> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, rule);
+            assert_eq!(parsed.collect::<Vec<_>>(), rule.collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn test_serde_rolling_weekday() {
+            let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+            let rule = DateRule::monthly(start)
+                .with_count(3)
+                .with_rolling_weekday(Weekday::Fri, -1)
+                .unwrap();
+
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                json,
+                r#"{"start":"2024-03-01","freq":"P1M","end":null,"count":3,"rolling_day":null,"rolling_weekday":["Fri",-1],"calendar":null,"position":0}"#
+            );
+            let parsed: DateRule<NaiveDate> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, rule);
+
+            // Rules written before rolling weekdays were added
+            let parsed: DateRule<NaiveDate> = serde_json::from_str(
+                r#"{"start":"2024-03-01","freq":"P1M","end":null,"count":3,"rolling_day":null,"position":0}"#,
+            )
+            .unwrap();
+            assert_eq!(parsed, DateRule::monthly(start).with_count(3));
+        }
+
+        #[test]
+        fn test_serde_resumes() {
+            let start = NaiveDateTime::new(
+                NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+                NaiveTime::from_hms_opt(1, 2, 3).unwrap(),
+            );
+            let end = NaiveDateTime::new(
+                NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+                NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+            );
+            let mut rule = DateRule::monthly(start).with_end(end);
+            rule.next();
+            rule.next();
+
+            let json = serde_json::to_string(&rule).unwrap();
+            let parsed: DateRule<NaiveDateTime> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.collect::<Vec<_>>(), rule.collect::<Vec<_>>());
+
+            let bytes = bincode::serialize(&rule).unwrap();
+            let parsed: DateRule<NaiveDateTime> = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(parsed, rule);
+            assert_eq!(parsed.collect::<Vec<_>>(), rule.collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn test_serde_calendar() {
+            use crate::calendar::{
+                Hebrew, HebrewAnniversary, Hijri, HijriCycle, Julian, JulianGregorian,
+            };
+            use crate::week::{Mmwr, Weeks};
+
+            let start = NaiveDate::from_ymd_opt(1752, 7, 2).unwrap();
+            let rule = DateRule::monthly(start)
+                .with_calendar(JulianGregorian::BRITISH)
+                .with_count(3);
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                json,
+                r#"{"start":"1752-07-02","freq":"P1M","end":null,"count":3,"rolling_day":null,"rolling_weekday":null,"calendar":{"changeover":"1752-09-14"},"position":0}"#
+            );
+            let parsed: DateRule<NaiveDate, JulianGregorian> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, rule);
+            assert_eq!(parsed.collect::<Vec<_>>(), rule.collect::<Vec<_>>());
+            let bytes = bincode::serialize(&rule).unwrap();
+            let parsed: DateRule<NaiveDate, JulianGregorian> =
+                bincode::deserialize(&bytes).unwrap();
+            assert_eq!(parsed, rule);
+
+            let rule = DateRule::yearly(start).with_calendar(Julian).with_count(3);
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                serde_json::from_str::<DateRule<_, Julian>>(&json).unwrap(),
+                rule
+            );
+
+            let rule = DateRule::monthly(start).with_calendar(Hijri::new(HijriCycle::Kushyar));
+            let json = serde_json::to_string(&rule).unwrap();
+            assert!(
+                json.contains(r#""calendar":{"cycle":"Kushyar"}"#),
+                "{}",
+                json
+            );
+            assert_eq!(
+                serde_json::from_str::<DateRule<_, Hijri>>(&json).unwrap(),
+                rule
+            );
+
+            let rule =
+                DateRule::yearly(start).with_calendar(Hebrew::new(HebrewAnniversary::Yahrzeit));
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                serde_json::from_str::<DateRule<_, Hebrew>>(&json).unwrap(),
+                rule
+            );
+
+            let rule = DateRule::numbered_weeks(start, Mmwr).with_count(3);
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                serde_json::from_str::<DateRule<_, Weeks<Mmwr>>>(&json).unwrap(),
+                rule
+            );
+
+            // Rules written before calendars were added use the default calendar
+            let parsed: DateRule<NaiveDate, JulianGregorian> = serde_json::from_str(
+                r#"{"start":"1752-07-02","freq":"P1M","end":null,"count":3,"rolling_day":null,"rolling_weekday":null,"position":0}"#,
+            )
+            .unwrap();
+            assert_eq!(
+                parsed,
+                DateRule::monthly(start)
+                    .with_calendar(JulianGregorian::PAPAL)
+                    .with_count(3)
+            );
+        }
+
+        #[cfg(feature = "schemars")]
+        #[test]
+        fn test_json_schema() {
+            let schema = schemars::schema_for!(DateRule<NaiveDate>);
+            let properties = schema.get("properties").unwrap();
+
+            assert_eq!(
+                schema.get("title"),
+                Some(&"DateRule_for_NaiveDate_and_Gregorian".into())
+            );
+            assert_eq!(properties["freq"]["type"], "string");
+            assert!(properties["freq"]["pattern"].is_string());
+            assert_eq!(properties["start"]["format"], "date");
+            assert_eq!(properties["rolling_day"]["minimum"], -31);
+            assert_eq!(properties["rolling_day"]["maximum"], 31);
+            assert_eq!(schema.get("additionalProperties"), Some(&false.into()));
+
+            let schema =
+                schemars::schema_for!(DateRule<NaiveDate, crate::calendar::JulianGregorian>);
+            let properties = schema.get("properties").unwrap();
+            assert_eq!(
+                schema.get("title"),
+                Some(&"DateRule_for_NaiveDate_and_JulianGregorian".into())
+            );
+            assert!(properties["calendar"].is_object());
+        }
+
+        #[test]
+        fn test_serde_errors() {
+            [
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":32,"position":0}"#,
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":0,"position":0}"#,
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":-32,"position":0}"#,
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":null,"rolling_weekday":["Fri",6],"position":0}"#,
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":1,"rolling_weekday":["Fri",1],"position":0}"#,
+                r#"{"start":"2020-01-31","freq":"1M","end":null,"count":3,"rolling_day":null,"position":0}"#,
+                r#"{"start":"2020-01-31","freq":"P1M","end":null,"count":3,"rolling_day":null}"#,
+            ]
+            .iter()
+            .for_each(|json| {
+                assert!(
+                    serde_json::from_str::<DateRule<NaiveDate>>(json).is_err(),
+                    "{}",
+                    json
+                )
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use chrono::{Duration, NaiveDateTime, NaiveTime};
 
+    #[test]
+    fn test_parse_from_iso8601() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        [
+            (
+                "R12/2025-01-31/P1M",
+                DateRule::monthly(start).with_count(12),
+            ),
+            ("R/2025-01-31/P1M", DateRule::monthly(start)),
+            ("R0/2025-01-31/P1D", DateRule::daily(start).with_count(0)),
+            (
+                "R2/2025-01-31/P-1W",
+                DateRule::new(start, -RelativeDuration::weeks(1)).with_count(2),
+            ),
+        ]
+        .iter()
+        .for_each(|(spec, expected)| {
+            assert_eq!(
+                spec.parse::<DateRule<NaiveDate>>().unwrap(),
+                *expected,
+                "{}",
+                spec
+            )
+        });
+
+        let start = NaiveDateTime::new(start, NaiveTime::from_hms_opt(9, 30, 0).unwrap());
+        assert_eq!(
+            DateRule::<NaiveDateTime>::parse_from_iso8601("R2/2025-01-31T09:30:00/PT1H"),
+            Ok(DateRule::hourly(start).with_count(2))
+        );
+
+        [
+            "",
+            "R12",
+            "R12/2025-01-31",
+            "12/2025-01-31/P1M",
+            "R-1/2025-01-31/P1M",
+            "Rx/2025-01-31/P1M",
+            "R12/2025-02-30/P1M",
+            "R12/2025-01-31/1M",
+            "R12/2025-01-31/P1M/2026-01-01",
+        ]
+        .iter()
+        .for_each(|spec| assert!(spec.parse::<DateRule<NaiveDate>>().is_err(), "{}", spec));
+    }
+
     #[test]
     fn test_rrule_with_date() {
         let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
@@ -466,6 +1045,130 @@ mod tests {
         // assert_eq!(dates.len(), 0);
     }
 
+    #[test]
+    fn test_rolling_day() {
+        let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+        [
+            (31, [31, 28, 31, 30]),
+            (30, [30, 28, 30, 30]),
+            (-1, [31, 28, 31, 30]),
+            (-2, [30, 27, 30, 29]),
+            (-30, [2, 1, 2, 1]),
+        ]
+        .iter()
+        .for_each(|(rolling_day, days)| {
+            let dates: Vec<u32> = DateRule::monthly(start)
+                .with_rolling_day(*rolling_day)
+                .unwrap()
+                .with_count(4)
+                .map(|date| date.day())
+                .collect();
+            assert_eq!(dates, days.to_vec(), "{}", rolling_day);
+        });
+
+        [0, 32, -32].iter().for_each(|rolling_day| {
+            let error = DateRule::monthly(start)
+                .with_rolling_day(*rolling_day)
+                .unwrap_err();
+            assert_eq!(error.day(), *rolling_day);
+        });
+    }
+
+    #[test]
+    fn test_rolling_weekday() {
+        let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        // 2024-03 to 2024-06 have 5, 4, 5 and 4 Fridays
+        [
+            (1, [1, 5, 3, 7]),
+            (3, [15, 19, 17, 21]),
+            (5, [29, 26, 31, 28]),
+            (-1, [29, 26, 31, 28]),
+            (-4, [8, 5, 10, 7]),
+            (-5, [1, 5, 3, 7]),
+        ]
+        .iter()
+        .for_each(|(n, expected)| {
+            let dates: Vec<NaiveDate> = DateRule::monthly(start)
+                .with_rolling_weekday(Weekday::Fri, *n)
+                .unwrap()
+                .with_count(4)
+                .collect();
+            assert!(dates.iter().all(|date| date.weekday() == Weekday::Fri));
+            let days: Vec<u32> = dates.iter().map(|date| date.day()).collect();
+            assert_eq!(days, expected.to_vec(), "{}", n);
+        });
+
+        // Rolling days and weekdays replace each other
+        let rule = DateRule::monthly(start)
+            .with_rolling_day(10)
+            .unwrap()
+            .with_rolling_weekday(Weekday::Mon, 1)
+            .unwrap();
+        assert_eq!(rule.clone().next(), NaiveDate::from_ymd_opt(2024, 3, 4));
+        assert_eq!(
+            rule.with_rolling_day(10).unwrap().next(),
+            NaiveDate::from_ymd_opt(2024, 3, 10)
+        );
+
+        [0, 6, -6].iter().for_each(|n| {
+            let error = DateRule::monthly(start)
+                .with_rolling_weekday(Weekday::Fri, *n)
+                .unwrap_err();
+            assert_eq!(error.ordinal(), *n);
+        });
+    }
+
+    #[test]
+    fn test_with_calendar() {
+        use crate::calendar::{Calendar, Julian, JulianGregorian};
+
+        // Julian 1700-01-31, a month before the Julian-only leap day
+        let start = Julian.date_from_calendar(1700, 1, 31).unwrap();
+        let dates: Vec<(i32, u32, u32)> = DateRule::monthly(start)
+            .with_calendar(Julian)
+            .with_count(4)
+            .map(|date| Julian.to_calendar_date(date))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![(1700, 1, 31), (1700, 2, 29), (1700, 3, 31), (1700, 4, 30)]
+        );
+
+        // Rolling days are taken in the calendar's own months
+        let dates: Vec<(i32, u32, u32)> = DateRule::monthly(start)
+            .with_rolling_day(-2)
+            .unwrap()
+            .with_calendar(Julian)
+            .with_count(3)
+            .map(|date| Julian.to_calendar_date(date))
+            .collect();
+        assert_eq!(dates, vec![(1700, 1, 30), (1700, 2, 28), (1700, 3, 30)]);
+
+        // Great Britain went from Wed 2nd to Thu 14th September 1752
+        let calendar = JulianGregorian::BRITISH;
+        let start = calendar.date_from_calendar(1752, 7, 2).unwrap();
+        let dates: Vec<NaiveDate> = DateRule::monthly(start)
+            .with_calendar(calendar)
+            .with_count(4)
+            .collect();
+        assert_eq!(
+            dates
+                .iter()
+                .map(|date| calendar.to_calendar_date(*date))
+                .collect::<Vec<_>>(),
+            vec![(1752, 7, 2), (1752, 8, 2), (1752, 9, 2), (1752, 10, 2)]
+        );
+        // so that September only had 19 days
+        assert_eq!(dates[3] - dates[2], Duration::days(19));
+
+        // The Gregorian calendar is the default
+        let start = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+        assert_eq!(
+            DateRule::monthly(start).with_calendar(Gregorian),
+            DateRule::monthly(start)
+        );
+    }
+
     #[test]
     fn test_backwards_rrule() {
         let start = NaiveDate::from_ymd_opt(2020, 3, 31).unwrap();
@@ -0,0 +1,369 @@
+//! Market tenor codes such as `ON`, `TN`, `1W`, `3M` or `2BD`.
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::delta::shift_months_opt;
+use crate::RelativeDuration;
+
+/// A market tenor, as used to label deposits, swaps and curve pillars.
+///
+/// The overnight family (`ON`, `TN` and `SN`) are day-count periods defined by business days
+/// relative to the trade date, rather than plain durations. Business day tenors (`2BD`) are also
+/// counted in business days. Day, week, month and year tenors run from the spot date and can be
+/// converted into a [`RelativeDuration`].
+///
+/// Tenors are ordered as curve pillars conventionally are: the overnight family first, then by
+/// approximate length, so that `ON < TN < SN < 1D < 1W < 1M < 18M < 2Y`. Tenors of equal length
+/// written in different units, such as `12M` and `1Y`, are ordered by unit.
+///
+/// # Example
+///
+/// ```
+/// use chronoutil::RelativeDuration;
+/// use chronoutil::tenor::Tenor;
+/// use std::convert::TryFrom;
+///
+/// let mut pillars: Vec<Tenor> = ["1Y", "ON", "3M", "1W", "TN", "18M"]
+///     .iter()
+///     .map(|code| code.parse().unwrap())
+///     .collect();
+/// pillars.sort();
+/// let codes: Vec<String> = pillars.iter().map(|tenor| tenor.to_string()).collect();
+/// assert_eq!(codes, ["ON", "TN", "1W", "3M", "1Y", "18M"]);
+///
+/// assert_eq!(RelativeDuration::try_from(Tenor::Months(18)), Ok(RelativeDuration::months(18)));
+/// assert!(RelativeDuration::try_from(Tenor::Overnight).is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Tenor {
+    /// Overnight (`ON` or `O/N`): from the trade date to the next business day.
+    Overnight,
+    /// Tomorrow-next (`TN` or `T/N`): from one to two business days after the trade date.
+    TomorrowNext,
+    /// Spot-next (`SN` or `S/N`): from the spot date to the next business day.
+    SpotNext,
+    /// A number of business days from the spot date, e.g. `2BD`.
+    BusinessDays(u32),
+    /// A number of calendar days from the spot date, e.g. `3D`.
+    Days(u32),
+    /// A number of weeks from the spot date, e.g. `1W`.
+    Weeks(u32),
+    /// A number of months from the spot date, e.g. `18M`.
+    Months(u32),
+    /// A number of years from the spot date, e.g. `5Y`.
+    Years(u32),
+}
+
+impl Tenor {
+    /// Parses a tenor code, ignoring case. See [`FromStr`].
+    ///
+    /// # Errors
+    ///
+    /// - Unknown codes or units
+    /// - Counts which do not fit in a `u32`
+    pub fn parse(code: &str) -> Result<Tenor, String> {
+        let upper = code.trim().to_ascii_uppercase();
+        match upper.as_str() {
+            "ON" | "O/N" => return Ok(Tenor::Overnight),
+            "TN" | "T/N" => return Ok(Tenor::TomorrowNext),
+            "SN" | "S/N" => return Ok(Tenor::SpotNext),
+            _ => {}
+        }
+
+        let split = upper
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| format!("tenor {} has no unit", code))?;
+        if split == 0 {
+            return Err(format!("invalid tenor {}", code));
+        }
+        let count = upper[..split]
+            .parse::<u32>()
+            .map_err(|_| format!("{} overflows in tenor {}", &upper[..split], code))?;
+        match &upper[split..] {
+            "BD" => Ok(Tenor::BusinessDays(count)),
+            "D" => Ok(Tenor::Days(count)),
+            "W" => Ok(Tenor::Weeks(count)),
+            "M" => Ok(Tenor::Months(count)),
+            "Y" => Ok(Tenor::Years(count)),
+            unit => Err(format!("unknown unit {} in tenor {}", unit, code)),
+        }
+    }
+
+    /// Returns the start and end dates of the tenor for a trade on the given date.
+    ///
+    /// The spot date is `spot_lag` business days after the trade date (2 in most markets).
+    /// Overnight tenors start on the trade date, tomorrow-next tenors one business day later,
+    /// and all others on the spot date. The end dates of day, week, month and year tenors are
+    /// computed with the same rules as adding a [`RelativeDuration`], and are not rolled onto a
+    /// business day; apply the relevant roll convention to them as needed.
+    ///
+    /// Returns `None` if a date is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Datelike, NaiveDate, Weekday};
+    /// use chronoutil::tenor::Tenor;
+    ///
+    /// let is_business_day = |date: NaiveDate| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+    /// // A Thursday
+    /// let trade = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+    /// let date = |d| NaiveDate::from_ymd_opt(2024, 2, d).unwrap();
+    ///
+    /// assert_eq!(Tenor::Overnight.resolve(trade, 2, is_business_day), Some((date(1), date(2))));
+    /// assert_eq!(Tenor::TomorrowNext.resolve(trade, 2, is_business_day), Some((date(2), date(5))));
+    /// assert_eq!(Tenor::SpotNext.resolve(trade, 2, is_business_day), Some((date(5), date(6))));
+    /// assert_eq!(Tenor::Weeks(1).resolve(trade, 2, is_business_day), Some((date(5), date(12))));
+    /// ```
+    pub fn resolve<F>(
+        &self,
+        trade_date: NaiveDate,
+        spot_lag: u32,
+        is_business_day: F,
+    ) -> Option<(NaiveDate, NaiveDate)>
+    where
+        F: Fn(NaiveDate) -> bool,
+    {
+        let add_business_days = |mut date: NaiveDate, days: u32| {
+            for _ in 0..days {
+                date = date.succ_opt()?;
+                while !is_business_day(date) {
+                    date = date.succ_opt()?;
+                }
+            }
+            Some(date)
+        };
+
+        let (start, business_days) = match self {
+            Tenor::Overnight => (trade_date, 1),
+            Tenor::TomorrowNext => (add_business_days(trade_date, 1)?, 1),
+            Tenor::SpotNext => (add_business_days(trade_date, spot_lag)?, 1),
+            Tenor::BusinessDays(days) => (add_business_days(trade_date, spot_lag)?, *days),
+            _ => {
+                let spot = add_business_days(trade_date, spot_lag)?;
+                let duration = RelativeDuration::try_from(*self).ok()?;
+                let end = shift_months_opt(spot, duration.months)?
+                    .checked_add_signed(duration.duration)?;
+                return Some((spot, end));
+            }
+        };
+        Some((start, add_business_days(start, business_days)?))
+    }
+
+    // Sorts the overnight family first, then by length in units of 1/4800 days (in which the
+    // mean Gregorian month is a whole number), then by unit
+    fn sort_key(&self) -> (u8, u64, u8) {
+        let count = |count: &u32| u64::from(*count);
+        match self {
+            Tenor::Overnight => (0, 0, 0),
+            Tenor::TomorrowNext => (0, 1, 0),
+            Tenor::SpotNext => (0, 2, 0),
+            Tenor::Days(n) => (1, count(n) * 4800, 0),
+            // Five business days to the week
+            Tenor::BusinessDays(n) => (1, count(n) * 4800 * 7 / 5, 1),
+            Tenor::Weeks(n) => (1, count(n) * 4800 * 7, 2),
+            Tenor::Months(n) => (1, count(n) * 146_097, 3),
+            Tenor::Years(n) => (1, count(n) * 146_097 * 12, 4),
+        }
+    }
+}
+
+impl PartialOrd for Tenor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tenor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl fmt::Display for Tenor {
+    /// Formats the tenor as its canonical code, e.g. `ON`, `2BD` or `18M`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tenor::Overnight => f.write_str("ON"),
+            Tenor::TomorrowNext => f.write_str("TN"),
+            Tenor::SpotNext => f.write_str("SN"),
+            Tenor::BusinessDays(n) => write!(f, "{}BD", n),
+            Tenor::Days(n) => write!(f, "{}D", n),
+            Tenor::Weeks(n) => write!(f, "{}W", n),
+            Tenor::Months(n) => write!(f, "{}M", n),
+            Tenor::Years(n) => write!(f, "{}Y", n),
+        }
+    }
+}
+
+impl FromStr for Tenor {
+    type Err = String;
+
+    /// Parses a tenor code such as `ON`, `T/N`, `2BD`, `3D`, `1W`, `18M` or `5Y`, ignoring case.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Tenor::parse(code)
+    }
+}
+
+impl TryFrom<Tenor> for RelativeDuration {
+    type Error = String;
+
+    /// Converts a day, week, month or year tenor into a [`RelativeDuration`].
+    ///
+    /// The overnight family and business day tenors depend on a business day calendar, so cannot
+    /// be converted; use [`Tenor::resolve`] for these instead.
+    fn try_from(tenor: Tenor) -> Result<Self, Self::Error> {
+        let months = |months: u32| {
+            i32::try_from(months)
+                .map(RelativeDuration::months)
+                .map_err(|_| format!("tenor {} overflows a RelativeDuration", tenor))
+        };
+        match tenor {
+            Tenor::Days(n) => Ok(RelativeDuration::from(Duration::days(n.into()))),
+            Tenor::Weeks(n) => Ok(RelativeDuration::from(Duration::weeks(n.into()))),
+            Tenor::Months(n) => months(n),
+            Tenor::Years(n) => months(
+                n.checked_mul(12)
+                    .ok_or_else(|| format!("tenor {} overflows a RelativeDuration", tenor))?,
+            ),
+            _ => Err(format!(
+                "tenor {} is counted in business days, so is not a RelativeDuration",
+                tenor
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Weekday};
+
+    fn is_business_day(date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_format() {
+        [
+            ("ON", Tenor::Overnight, "ON"),
+            ("o/n", Tenor::Overnight, "ON"),
+            ("T/N", Tenor::TomorrowNext, "TN"),
+            ("sn", Tenor::SpotNext, "SN"),
+            ("2BD", Tenor::BusinessDays(2), "2BD"),
+            ("3d", Tenor::Days(3), "3D"),
+            ("1W", Tenor::Weeks(1), "1W"),
+            ("18M", Tenor::Months(18), "18M"),
+            (" 1Y ", Tenor::Years(1), "1Y"),
+            ("0D", Tenor::Days(0), "0D"),
+        ]
+        .iter()
+        .for_each(|(code, tenor, formatted)| {
+            assert_eq!(code.parse::<Tenor>().unwrap(), *tenor, "{}", code);
+            assert_eq!(tenor.to_string(), *formatted);
+        })
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        [
+            "",
+            "N",
+            "1",
+            "M",
+            "1X",
+            "1B",
+            "-1M",
+            "1.5Y",
+            "99999999999M",
+            "1M1D",
+            "ONN",
+        ]
+        .iter()
+        .for_each(|code| assert!(Tenor::parse(code).is_err(), "{}", code))
+    }
+
+    #[test]
+    fn test_ordering() {
+        let sorted = [
+            "ON", "TN", "SN", "1D", "1BD", "2D", "2BD", "5BD", "1W", "2W", "1M", "3M", "6M", "12M",
+            "1Y", "18M", "2Y", "10Y",
+        ];
+        let mut tenors: Vec<Tenor> = sorted.iter().rev().map(|c| c.parse().unwrap()).collect();
+        tenors.sort();
+        let codes: Vec<String> = tenors.iter().map(|t| t.to_string()).collect();
+        assert_eq!(codes, sorted);
+
+        assert!(Tenor::Months(12) < Tenor::Years(1));
+        assert!(Tenor::Months(11) < Tenor::Years(1));
+        assert!(Tenor::Weeks(4) < Tenor::Months(1));
+        assert!(Tenor::Weeks(5) > Tenor::Months(1));
+    }
+
+    #[test]
+    fn test_relative_duration() {
+        [
+            (Tenor::Days(3), Ok(RelativeDuration::days(3))),
+            (Tenor::Weeks(2), Ok(RelativeDuration::weeks(2))),
+            (Tenor::Months(18), Ok(RelativeDuration::months(18))),
+            (Tenor::Years(5), Ok(RelativeDuration::years(5))),
+        ]
+        .iter()
+        .for_each(|(tenor, expected)| assert_eq!(RelativeDuration::try_from(*tenor), *expected));
+
+        [
+            Tenor::Overnight,
+            Tenor::TomorrowNext,
+            Tenor::SpotNext,
+            Tenor::BusinessDays(2),
+            Tenor::Months(u32::MAX),
+            Tenor::Years(u32::MAX / 12),
+        ]
+        .iter()
+        .for_each(|tenor| assert!(RelativeDuration::try_from(*tenor).is_err(), "{}", tenor));
+    }
+
+    #[test]
+    fn test_resolve() {
+        // Friday 2024-01-26, with spot on Tuesday 2024-01-30
+        let trade = date(2024, 1, 26);
+        [
+            (Tenor::Overnight, date(2024, 1, 26), date(2024, 1, 29)),
+            (Tenor::TomorrowNext, date(2024, 1, 29), date(2024, 1, 30)),
+            (Tenor::SpotNext, date(2024, 1, 30), date(2024, 1, 31)),
+            (Tenor::BusinessDays(5), date(2024, 1, 30), date(2024, 2, 6)),
+            (Tenor::Days(3), date(2024, 1, 30), date(2024, 2, 2)),
+            (Tenor::Weeks(1), date(2024, 1, 30), date(2024, 2, 6)),
+            (Tenor::Months(1), date(2024, 1, 30), date(2024, 2, 29)),
+            (Tenor::Years(1), date(2024, 1, 30), date(2025, 1, 30)),
+        ]
+        .iter()
+        .for_each(|(tenor, start, end)| {
+            assert_eq!(
+                tenor.resolve(trade, 2, is_business_day),
+                Some((*start, *end)),
+                "{}",
+                tenor
+            )
+        });
+
+        // With a holiday on the Monday and a spot lag of one day
+        let holiday = |date: NaiveDate| is_business_day(date) && date != self::date(2024, 1, 29);
+        assert_eq!(
+            Tenor::SpotNext.resolve(trade, 1, holiday),
+            Some((date(2024, 1, 30), date(2024, 1, 31)))
+        );
+        assert_eq!(
+            Tenor::Overnight.resolve(NaiveDate::MAX, 2, is_business_day),
+            None
+        );
+    }
+}
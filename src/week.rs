@@ -0,0 +1,650 @@
+//! Contains week-numbering schemes, which number the weeks of a week-year.
+//!
+//! Each scheme converts dates to and from a week-year, week and weekday:
+//!
+//! - [`Iso`] weeks start on Monday, and week 1 contains the year's first Thursday
+//! - [`Mmwr`] weeks, the CDC's epidemiological weeks, start on Sunday, and week 1 contains
+//!   the year's first Wednesday
+//! - [`Us`] weeks start on Sunday, and week 1 contains 1 January, so the first and last weeks
+//!   of a year may be partial
+//! - [`DayOfYear`] weeks are each seven days from 1 January, whatever the weekday, so week 53
+//!   has only one or two days
+//!
+//! ```rust
+//! # use chrono::{NaiveDate, Weekday};
+//! # use chronoutil::week::{Iso, Mmwr, Us, WeekNumbering};
+//! let date = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+//! assert_eq!(Iso.to_week_date(date), (2020, 53, Weekday::Sat));
+//! assert_eq!(Mmwr.to_week_date(date), (2020, 53, Weekday::Sat));
+//! assert_eq!(Us.to_week_date(date), (2021, 1, Weekday::Sat));
+//!
+//! assert_eq!(Mmwr.date_from_week(2021, 1, Weekday::Sun), NaiveDate::from_ymd_opt(2021, 1, 3));
+//! ```
+//!
+//! A week numbering wrapped in [`Weeks`] is a [`Calendar`] whose months are its weeks and
+//! whose days are the days of each week, so a [`DateRule`](crate::DateRule) can step through
+//! numbered weeks with [`DateRule::numbered_weeks`](crate::DateRule::numbered_weeks), and a
+//! yearly rule given `Weeks` with [`DateRule::with_calendar`](crate::DateRule::with_calendar)
+//! keeps the week number and weekday.
+use std::convert::TryInto;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::boundary::days_in_year;
+use crate::calendar::{naive_date, Calendar};
+use crate::delta::{self, is_leap_year, with_date};
+
+/// A scheme for numbering the weeks of a week-year.
+///
+/// Only [`to_week_date`](WeekNumbering::to_week_date),
+/// [`week_start`](WeekNumbering::week_start) and
+/// [`weeks_in_year`](WeekNumbering::weeks_in_year) are required. The other methods follow
+/// from these, and may be overridden with faster versions.
+pub trait WeekNumbering {
+    /// Returns the week-year, week and weekday of a date.
+    fn to_week_date(&self, date: NaiveDate) -> (i32, u32, Weekday);
+
+    /// Returns the first day of the given week of the given week-year which lies in that
+    /// week-year, or None if the week is out of range.
+    fn week_start(&self, year: i32, week: u32) -> Option<NaiveDate>;
+
+    /// Returns the number of weeks in the given week-year.
+    fn weeks_in_year(&self, year: i32) -> u32;
+
+    /// Returns the last day of the given week of the given week-year which lies in that
+    /// week-year, or None if the week is out of range.
+    fn week_end(&self, year: i32, week: u32) -> Option<NaiveDate> {
+        self.week_start(year, week)?;
+        let next = if week < self.weeks_in_year(year) {
+            self.week_start(year, week + 1)
+        } else {
+            self.week_start(year.checked_add(1)?, 1)
+        };
+        next?.pred_opt()
+    }
+
+    /// Returns the date with the given week-year, week and weekday.
+    ///
+    /// In a partial week, weekdays which fall in another week-year are moved onto the nearest
+    /// day of the week which does not: the first day for week 1, and the last day otherwise.
+    /// Returns None if the week is out of range.
+    fn date_from_week(&self, year: i32, week: u32, weekday: Weekday) -> Option<NaiveDate> {
+        let start = self.week_start(year, week)?;
+        let end = self.week_end(year, week)?;
+        let offset =
+            (7 + weekday.num_days_from_monday() - start.weekday().num_days_from_monday()) % 7;
+        let date = start.checked_add_signed(Duration::days(offset.into()))?;
+        if date <= end {
+            Some(date)
+        } else if week == 1 {
+            Some(start)
+        } else {
+            Some(end)
+        }
+    }
+
+    /// Shift the date to the given week of its week-year, keeping its weekday and any time of
+    /// day.
+    ///
+    /// Weeks beyond the end of the week-year are shifted back to its last week. Returns None
+    /// if the week is zero, or the result is an ambiguous or non-existing date/time (e.g. in a
+    /// DST transition).
+    fn with_week<D: Datelike>(&self, date: D, week: u32) -> Option<D> {
+        if week == 0 {
+            return None;
+        }
+        let (year, _, weekday) = self.to_week_date(naive_date(&date)?);
+        let week = week.min(self.weeks_in_year(year));
+        with_date(date, self.date_from_week(year, week, weekday)?)
+    }
+
+    /// Shift a date by the given number of numbered weeks, keeping its weekday and any time
+    /// of day.
+    ///
+    /// Panics if the result is out of range or an ambiguous or non-existing date/time.
+    fn shift_weeks<D: Datelike>(&self, date: D, weeks: i32) -> D {
+        self.shift_weeks_opt(date, weeks).unwrap()
+    }
+
+    /// Same as [`shift_weeks`](WeekNumbering::shift_weeks) except fallible on unresolvable
+    /// dates/times.
+    ///
+    /// Returns `None` rather than panicking when the result is out of range, or an ambiguous
+    /// or non-existing date/time (e.g. in a DST transition).
+    fn shift_weeks_opt<D: Datelike>(&self, date: D, weeks: i32) -> Option<D> {
+        let (mut year, week, weekday) = self.to_week_date(naive_date(&date)?);
+        let mut index = i64::from(week) - 1 + i64::from(weeks);
+
+        // The weekdays of the Gregorian calendar repeat every 400 years
+        if !(-CYCLE_YEARS..CYCLE_YEARS).contains(&index) {
+            let cycle: i64 = (0..CYCLE_YEARS as i32)
+                .map(|n| i64::from(self.weeks_in_year(year.wrapping_add(n))))
+                .sum();
+            let cycles = index.div_euclid(cycle);
+            year = year.checked_add((cycles * CYCLE_YEARS).try_into().ok()?)?;
+            index -= cycles * cycle;
+        }
+        while index < 0 {
+            year = year.checked_sub(1)?;
+            index += i64::from(self.weeks_in_year(year));
+        }
+        while index >= i64::from(self.weeks_in_year(year)) {
+            index -= i64::from(self.weeks_in_year(year));
+            year = year.checked_add(1)?;
+        }
+        with_date(date, self.date_from_week(year, index as u32 + 1, weekday)?)
+    }
+}
+
+const CYCLE_YEARS: i64 = 400;
+
+/// A [`Calendar`] whose months are the weeks of a week numbering, and whose days are the days
+/// of each week.
+///
+/// Months run from 1 to the number of weeks in the week-year, and years with more than 52
+/// weeks are leap-years. Shifting by years keeps the week and weekday, moving back to the last
+/// week of shorter years.
+///
+/// ```rust
+/// # use chrono::NaiveDate;
+/// # use chronoutil::calendar::Calendar;
+/// # use chronoutil::week::{Iso, Weeks};
+/// // 2020-W53-4
+/// let date = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+/// assert_eq!(Weeks(Iso).to_calendar_date(date), (2020, 53, 4));
+/// // 2021-W52-4
+/// assert_eq!(
+///     Weeks(Iso).shift_years(date, 1),
+///     NaiveDate::from_ymd_opt(2021, 12, 30).unwrap()
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Weeks<W>(pub W);
+
+impl<W: WeekNumbering> Calendar for Weeks<W> {
+    fn is_leap_year(&self, year: i32) -> bool {
+        self.0.weeks_in_year(year) > 52
+    }
+
+    fn to_calendar_date(&self, date: NaiveDate) -> (i32, u32, u32) {
+        let (year, week, _) = self.0.to_week_date(date);
+        // Count back to the first day of the week, which may be partial
+        let day = (1..7)
+            .take_while(|days| {
+                date.checked_sub_signed(Duration::days(*days))
+                    .is_some_and(|previous| {
+                        let (previous_year, previous_week, _) = self.0.to_week_date(previous);
+                        (previous_year, previous_week) == (year, week)
+                    })
+            })
+            .count();
+        (year, week, day as u32 + 1)
+    }
+
+    fn date_from_calendar(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        let start = self.0.week_start(year, month)?;
+        let end = self.0.week_end(year, month)?;
+        let date = start.checked_add_signed(Duration::days(i64::from(day) - 1))?;
+        if day == 0 || date > end {
+            None
+        } else {
+            Some(date)
+        }
+    }
+
+    /// Returns the number of days in the given week of the given week-year.
+    ///
+    /// Panics if the week is not in the range 1 to the number of weeks in the year.
+    fn days_in_month(&self, year: i32, month: u32) -> u32 {
+        match (self.0.week_start(year, month), self.0.week_end(year, month)) {
+            (Some(start), Some(end)) => (end - start).num_days() as u32 + 1,
+            _ => panic!("invalid week {}", month),
+        }
+    }
+
+    fn shift_months_opt<D: Datelike>(&self, date: D, months: i32) -> Option<D> {
+        self.0.shift_weeks_opt(date, months)
+    }
+
+    fn shift_years_opt<D: Datelike>(&self, date: D, years: i32) -> Option<D> {
+        let (year, week, weekday) = self.0.to_week_date(naive_date(&date)?);
+        let year = year.checked_add(years)?;
+        let week = week.min(self.0.weeks_in_year(year));
+        with_date(date, self.0.date_from_week(year, week, weekday)?)
+    }
+}
+
+/// ISO 8601 weeks, which start on Monday. Week 1 is the week containing the first Thursday of
+/// the year, so the week-year may start in late December or end in early January.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Iso;
+
+impl WeekNumbering for Iso {
+    #[inline]
+    fn to_week_date(&self, date: NaiveDate) -> (i32, u32, Weekday) {
+        let week = date.iso_week();
+        (week.year(), week.week(), date.weekday())
+    }
+
+    #[inline]
+    fn week_start(&self, year: i32, week: u32) -> Option<NaiveDate> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+    }
+
+    #[inline]
+    fn weeks_in_year(&self, year: i32) -> u32 {
+        delta::iso_weeks_in_year(year)
+    }
+
+    #[inline]
+    fn date_from_week(&self, year: i32, week: u32, weekday: Weekday) -> Option<NaiveDate> {
+        NaiveDate::from_isoywd_opt(year, week, weekday)
+    }
+
+    #[inline]
+    fn shift_weeks_opt<D: Datelike>(&self, date: D, weeks: i32) -> Option<D> {
+        delta::shift_iso_weeks_opt(date, weeks)
+    }
+}
+
+/// MMWR weeks, the epidemiological weeks of the US Centers for Disease Control and
+/// Prevention, which start on Sunday. Week 1 is the week containing the first Wednesday of
+/// the year, so that it ends on the first Saturday at least four days into January.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Mmwr;
+
+impl WeekNumbering for Mmwr {
+    fn to_week_date(&self, date: NaiveDate) -> (i32, u32, Weekday) {
+        // Each week belongs to the year containing its Wednesday
+        let mut year = date.year();
+        let mut ordinal = date.ordinal() as i32 + 3 - date.weekday().num_days_from_sunday() as i32;
+        if ordinal < 1 {
+            year -= 1;
+            ordinal += days_in_year(year) as i32;
+        } else if ordinal > days_in_year(year) as i32 {
+            ordinal -= days_in_year(year) as i32;
+            year += 1;
+        }
+        (year, (ordinal as u32 - 1) / 7 + 1, date.weekday())
+    }
+
+    fn week_start(&self, year: i32, week: u32) -> Option<NaiveDate> {
+        if week == 0 || week > self.weeks_in_year(year) {
+            return None;
+        }
+        let first =
+            delta::previous_weekday(NaiveDate::from_ymd_opt(year, 1, 4)?, Weekday::Sun, true)?;
+        first.checked_add_signed(Duration::weeks(i64::from(week) - 1))
+    }
+
+    fn weeks_in_year(&self, year: i32) -> u32 {
+        match NaiveDate::from_ymd_opt(year, 1, 1).map(|date| date.weekday()) {
+            Some(Weekday::Wed) => 53,
+            Some(Weekday::Tue) if is_leap_year(year) => 53,
+            _ => 52,
+        }
+    }
+}
+
+/// US weeks, which start on Sunday. Week 1 is the week containing 1 January, and the
+/// week-year is the calendar year, so the first and last weeks are partial unless the year
+/// starts on a Sunday or ends on a Saturday. Years have 53 weeks, or 54 in a leap-year
+/// starting on a Saturday.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Us;
+
+impl WeekNumbering for Us {
+    fn to_week_date(&self, date: NaiveDate) -> (i32, u32, Weekday) {
+        let ordinal = date.ordinal() - 1;
+        // The number of days in week 1 before 1 January
+        let offset = (7 + date.weekday().num_days_from_sunday() - ordinal % 7) % 7;
+        (date.year(), (ordinal + offset) / 7 + 1, date.weekday())
+    }
+
+    fn week_start(&self, year: i32, week: u32) -> Option<NaiveDate> {
+        if week == 0 || week > self.weeks_in_year(year) {
+            return None;
+        }
+        let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        if week == 1 {
+            return Some(first);
+        }
+        let offset = first.weekday().num_days_from_sunday();
+        first.checked_add_signed(Duration::days(i64::from(7 * (week - 1) - offset)))
+    }
+
+    fn weeks_in_year(&self, year: i32) -> u32 {
+        let offset = NaiveDate::from_ymd_opt(year, 1, 1)
+            .map_or(0, |date| date.weekday().num_days_from_sunday());
+        (days_in_year(year) + offset - 1) / 7 + 1
+    }
+}
+
+/// Weeks counted as the day of the year divided by seven, so every week starts on the same
+/// weekday as 1 January. Week 53 has only 31 December, and also 30 December in a leap-year.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DayOfYear;
+
+impl WeekNumbering for DayOfYear {
+    #[inline]
+    fn to_week_date(&self, date: NaiveDate) -> (i32, u32, Weekday) {
+        (date.year(), (date.ordinal() - 1) / 7 + 1, date.weekday())
+    }
+
+    fn week_start(&self, year: i32, week: u32) -> Option<NaiveDate> {
+        if week == 0 || week > 53 {
+            return None;
+        }
+        NaiveDate::from_yo_opt(year, 7 * (week - 1) + 1)
+    }
+
+    #[inline]
+    fn weeks_in_year(&self, _year: i32) -> u32 {
+        53
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Weekday};
+
+    use super::*;
+    use crate::DateRule;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_week_dates() {
+        // The date, then its ISO, MMWR, US and day-of-year week-year and week
+        let cases = [
+            (
+                (2020, 12, 31),
+                (2020, 53),
+                (2020, 53),
+                (2020, 53),
+                (2020, 53),
+            ),
+            ((2021, 1, 1), (2020, 53), (2020, 53), (2021, 1), (2021, 1)),
+            ((2021, 1, 3), (2020, 53), (2021, 1), (2021, 2), (2021, 1)),
+            (
+                (2021, 12, 31),
+                (2021, 52),
+                (2021, 52),
+                (2021, 53),
+                (2021, 53),
+            ),
+            ((2022, 1, 1), (2021, 52), (2021, 52), (2022, 1), (2022, 1)),
+            ((2022, 1, 2), (2021, 52), (2022, 1), (2022, 2), (2022, 1)),
+            (
+                (2022, 12, 31),
+                (2022, 52),
+                (2022, 52),
+                (2022, 53),
+                (2022, 53),
+            ),
+            (
+                (2024, 12, 29),
+                (2024, 52),
+                (2025, 1),
+                (2024, 53),
+                (2024, 52),
+            ),
+            ((2024, 12, 30), (2025, 1), (2025, 1), (2024, 53), (2024, 53)),
+            ((2026, 1, 1), (2026, 1), (2025, 53), (2026, 1), (2026, 1)),
+        ];
+        cases.iter().for_each(|((y, m, d), iso, mmwr, us, doy)| {
+            let date = ymd(*y, *m, *d);
+            let weekday = date.weekday();
+            assert_eq!(Iso.to_week_date(date), (iso.0, iso.1, weekday));
+            assert_eq!(Mmwr.to_week_date(date), (mmwr.0, mmwr.1, weekday));
+            assert_eq!(Us.to_week_date(date), (us.0, us.1, weekday));
+            assert_eq!(DayOfYear.to_week_date(date), (doy.0, doy.1, weekday));
+        });
+
+        let years = [
+            (2014, 52, 53, 53),
+            (2015, 53, 52, 53),
+            (2020, 53, 53, 53),
+            (2021, 52, 52, 53),
+            (2025, 52, 53, 53),
+            (2028, 52, 52, 54),
+        ];
+        years.iter().for_each(|(year, iso, mmwr, us)| {
+            assert_eq!(Iso.weeks_in_year(*year), *iso, "{}", year);
+            assert_eq!(Mmwr.weeks_in_year(*year), *mmwr, "{}", year);
+            assert_eq!(Us.weeks_in_year(*year), *us, "{}", year);
+            assert_eq!(DayOfYear.weeks_in_year(*year), 53);
+        });
+    }
+
+    #[test]
+    fn test_week_round_trip() {
+        let start = ymd(1999, 12, 1);
+        let numberings: [&dyn Fn(NaiveDate) -> bool; 4] = [
+            &|date| round_trips(Iso, date),
+            &|date| round_trips(Mmwr, date),
+            &|date| round_trips(Us, date),
+            &|date| round_trips(DayOfYear, date),
+        ];
+        (0..366 * 30).for_each(|n| {
+            let date = start + Duration::days(n);
+            numberings
+                .iter()
+                .for_each(|round_trip| assert!(round_trip(date), "{}", date));
+        });
+    }
+
+    fn round_trips<W: WeekNumbering + Copy>(numbering: W, date: NaiveDate) -> bool {
+        let (year, week, weekday) = numbering.to_week_date(date);
+        let (_, _, day) = Weeks(numbering).to_calendar_date(date);
+        week >= 1
+            && week <= numbering.weeks_in_year(year)
+            && numbering.date_from_week(year, week, weekday) == Some(date)
+            && Weeks(numbering).date_from_calendar(year, week, day) == Some(date)
+            && numbering.week_start(year, week).unwrap() <= date
+            && numbering.week_end(year, week).unwrap() >= date
+            && numbering.shift_weeks(date, 0) == date
+    }
+
+    #[test]
+    fn test_partial_weeks() {
+        let cases = [
+            // 2022-01-01 is a Saturday, alone in US week 1
+            (
+                Us.date_from_week(2022, 1, Weekday::Mon),
+                Some(ymd(2022, 1, 1)),
+            ),
+            (Us.date_from_week(2022, 54, Weekday::Sun), None),
+            (
+                Us.date_from_week(2024, 53, Weekday::Fri),
+                Some(ymd(2024, 12, 31)),
+            ),
+            (
+                Us.date_from_week(2024, 53, Weekday::Sun),
+                Some(ymd(2024, 12, 29)),
+            ),
+            (Us.week_start(2022, 2), Some(ymd(2022, 1, 2))),
+            (Us.week_end(2024, 53), Some(ymd(2024, 12, 31))),
+            (
+                DayOfYear.date_from_week(2024, 53, Weekday::Fri),
+                Some(ymd(2024, 12, 31)),
+            ),
+            (
+                DayOfYear.date_from_week(2023, 53, Weekday::Sun),
+                Some(ymd(2023, 12, 31)),
+            ),
+            (
+                DayOfYear.date_from_week(2023, 53, Weekday::Mon),
+                Some(ymd(2023, 12, 31)),
+            ),
+            (DayOfYear.date_from_week(2023, 54, Weekday::Mon), None),
+            (Iso.date_from_week(2021, 53, Weekday::Mon), None),
+            (Mmwr.date_from_week(2021, 0, Weekday::Mon), None),
+        ];
+        cases
+            .iter()
+            .for_each(|(actual, expected)| assert_eq!(actual, expected));
+
+        // 2028 is a leap year starting on a Saturday
+        assert_eq!(Us.weeks_in_year(2028), 54);
+        assert_eq!(Us.week_start(2028, 54), Some(ymd(2028, 12, 31)));
+        assert_eq!(Weeks(Us).days_in_month(2028, 54), 1);
+        assert_eq!(Weeks(Us).days_in_month(2028, 2), 7);
+        assert!(Weeks(Iso).is_leap_year(2020));
+        assert!(!Weeks(Iso).is_leap_year(2021));
+
+        // Days count from the start of partial weeks
+        [
+            (Weeks(Us).to_calendar_date(ymd(2022, 1, 1)), (2022, 1, 1)),
+            (Weeks(Us).to_calendar_date(ymd(2021, 12, 31)), (2021, 53, 6)),
+            (Weeks(Us).to_calendar_date(ymd(2022, 1, 2)), (2022, 2, 1)),
+            (Weeks(Iso).to_calendar_date(ymd(2021, 1, 3)), (2020, 53, 7)),
+            (
+                Weeks(DayOfYear).to_calendar_date(ymd(2024, 12, 31)),
+                (2024, 53, 2),
+            ),
+        ]
+        .iter()
+        .for_each(|(actual, expected)| assert_eq!(actual, expected));
+        assert_eq!(
+            Weeks(Us).date_from_calendar(2021, 53, 6),
+            Some(ymd(2021, 12, 31))
+        );
+        assert_eq!(Weeks(Us).date_from_calendar(2022, 1, 2), None);
+    }
+
+    #[test]
+    fn test_shift_weeks() {
+        let cases = [
+            // 2024-W52-7, and 2025-W01-7
+            (Iso.shift_weeks(ymd(2024, 12, 29), 1), ymd(2025, 1, 5)),
+            (
+                Iso.with_week(ymd(2025, 1, 5), 53).unwrap(),
+                ymd(2025, 12, 28),
+            ),
+            (Mmwr.shift_weeks(ymd(2020, 12, 27), 1), ymd(2021, 1, 3)),
+            (Mmwr.shift_weeks(ymd(2020, 12, 27), -53), ymd(2019, 12, 22)),
+            // US weeks are numbered from 1 January, so stepping over a year end skips days
+            (Us.shift_weeks(ymd(2021, 12, 31), 1), ymd(2022, 1, 1)),
+            (Us.shift_weeks(ymd(2022, 1, 1), 1), ymd(2022, 1, 8)),
+            (Us.shift_weeks(ymd(2021, 12, 28), 2), ymd(2022, 1, 4)),
+            (Us.shift_weeks(ymd(2022, 1, 4), -2), ymd(2021, 12, 28)),
+            (
+                DayOfYear.shift_weeks(ymd(2023, 12, 24), 1),
+                ymd(2023, 12, 31),
+            ),
+            (DayOfYear.shift_weeks(ymd(2023, 12, 24), 2), ymd(2024, 1, 7)),
+            (
+                DayOfYear.with_week(ymd(2024, 1, 1), 53).unwrap(),
+                ymd(2024, 12, 30),
+            ),
+            (
+                Weeks(Us).shift_years(ymd(2028, 12, 31), -1),
+                ymd(2027, 12, 26),
+            ),
+            (
+                Weeks(Iso).shift_years(ymd(2020, 12, 31), 1),
+                ymd(2021, 12, 30),
+            ),
+            (
+                Weeks(Mmwr).shift_years(ymd(2024, 3, 1), 1),
+                ymd(2025, 2, 28),
+            ),
+        ];
+        cases
+            .iter()
+            .for_each(|(actual, expected)| assert_eq!(actual, expected));
+        assert_eq!(Mmwr.with_week(ymd(2024, 3, 1), 0), None);
+
+        // Shifts across many years agree with the week counts of each year
+        let date = ymd(2024, 3, 1);
+        let shifts = [-100_000, -20_871, -401, 400, 20_871, 100_000];
+        shifts.iter().for_each(|weeks| {
+            assert_eq!(
+                Iso.shift_weeks(date, *weeks),
+                date + Duration::weeks(*weeks as i64)
+            );
+            assert_eq!(
+                Mmwr.shift_weeks(date, *weeks),
+                date + Duration::weeks(*weeks as i64)
+            );
+            let shifted = Us.shift_weeks(date, *weeks);
+            assert_eq!(
+                Us.to_week_date(shifted),
+                counted(Us, date, *weeks),
+                "{}",
+                weeks
+            );
+            let shifted = DayOfYear.shift_weeks(date, *weeks);
+            assert_eq!(
+                DayOfYear.to_week_date(shifted),
+                counted(DayOfYear, date, *weeks)
+            );
+        });
+    }
+
+    // Counts weeks one year at a time, for a date on a weekday present in the target week
+    fn counted<W: WeekNumbering>(numbering: W, date: NaiveDate, weeks: i32) -> (i32, u32, Weekday) {
+        let (mut year, week, weekday) = numbering.to_week_date(date);
+        let mut index = i64::from(week) - 1 + i64::from(weeks);
+        while index < 0 {
+            year -= 1;
+            index += i64::from(numbering.weeks_in_year(year));
+        }
+        while index >= i64::from(numbering.weeks_in_year(year)) {
+            index -= i64::from(numbering.weeks_in_year(year));
+            year += 1;
+        }
+        (year, index as u32 + 1, weekday)
+    }
+
+    #[test]
+    fn test_numbered_weeks_rule() {
+        // Mondays, but the Saturday in week 1 of 2022
+        let rule = DateRule::numbered_weeks(ymd(2021, 12, 20), Us).with_count(4);
+        assert_eq!(
+            rule.collect::<Vec<_>>(),
+            vec![
+                ymd(2021, 12, 20),
+                ymd(2021, 12, 27),
+                ymd(2022, 1, 1),
+                ymd(2022, 1, 3)
+            ]
+        );
+
+        // Week 53 falls back to week 52 in short years
+        let rule = DateRule::yearly(ymd(2020, 12, 31))
+            .with_calendar(Weeks(Iso))
+            .with_count(3);
+        assert_eq!(
+            rule.map(|date| Iso.to_week_date(date)).collect::<Vec<_>>(),
+            vec![
+                (2020, 53, Weekday::Thu),
+                (2021, 52, Weekday::Thu),
+                (2022, 52, Weekday::Thu)
+            ]
+        );
+
+        // The first day of each MMWR week
+        let rule = DateRule::numbered_weeks(ymd(2020, 12, 23), Mmwr)
+            .with_rolling_day(1)
+            .unwrap()
+            .with_count(3);
+        assert_eq!(
+            rule.collect::<Vec<_>>(),
+            vec![ymd(2020, 12, 20), ymd(2020, 12, 27), ymd(2021, 1, 3)]
+        );
+    }
+}